@@ -60,6 +60,8 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
                 pub fn from_remote_handle(uri: golem_wasm_rpc::Uri, id: u64) -> Self {
                     Self {
                         rpc: WasmRpc::new(&uri),
+                        timeout: std::cell::Cell::new(None),
+                        options: std::cell::RefCell::new(None),
                         id,
                         uri,
                     }
@@ -72,6 +74,8 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
         struct_defs.push(quote! {
            pub struct #interface_name {
                 rpc: WasmRpc,
+                timeout: std::cell::Cell<Option<u64>>,
+                options: std::cell::RefCell<Option<InvocationOptions>>,
                 #(#additional_fields),*
            }
 
@@ -112,6 +116,20 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
                 });
             }
         }
+        if !interface.functions.is_empty() {
+            let batch_result_wrapper = batch_result_wrapper_ident(interface);
+            let call_result = stub_type_ident(&interface.batch_call_result_type());
+            struct_defs.push(quote! {
+                pub struct #batch_result_wrapper {
+                    pub future_batch_invoke_result: FutureBatchInvokeResult,
+                    decoders: Vec<fn(WitValue) -> crate::bindings::exports::#root_ns::#root_name::#stub_interface_name::#call_result>
+                }
+            });
+
+            resource_type_aliases.push(quote! {
+                type #batch_result_wrapper = crate::#batch_result_wrapper;
+            });
+        }
     }
 
     let mut interface_impls = Vec::new();
@@ -128,9 +146,15 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
             } else {
                 FunctionMode::Global
             };
-            fn_impls.push(generate_function_stub_source(
-                def, function, interface, mode,
-            )?);
+            for with_options in [false, true] {
+                fn_impls.push(generate_function_stub_source(
+                    def,
+                    function,
+                    interface,
+                    mode,
+                    with_options,
+                )?);
+            }
 
             if !function.results.is_empty() {
                 let result_wrapper = result_wrapper_ident(function, interface);
@@ -146,9 +170,13 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
                         };
                         pollable
                     }
+
+                    fn cancel(&self) {
+                        self.future_invoke_result.cancel();
+                    }
                 };
 
-                let get = generate_result_wrapper_get_source(def, interface, function, mode)?;
+                let get = generate_result_wrapper_get_source(def, function, mode)?;
 
                 let result_wrapper_impl = quote! {
                     impl crate::bindings::exports::#root_ns::#root_name::#stub_interface_name::#result_wrapper_interface for #result_wrapper {
@@ -161,12 +189,15 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
         }
 
         for function in &interface.static_functions {
-            fn_impls.push(generate_function_stub_source(
-                def,
-                function,
-                interface,
-                FunctionMode::Static,
-            )?);
+            for with_options in [false, true] {
+                fn_impls.push(generate_function_stub_source(
+                    def,
+                    function,
+                    interface,
+                    FunctionMode::Static,
+                    with_options,
+                )?);
+            }
 
             if !function.results.is_empty() {
                 let result_wrapper = result_wrapper_ident(function, interface);
@@ -182,14 +213,13 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
                         };
                         pollable
                     }
+
+                    fn cancel(&self) {
+                        self.future_invoke_result.cancel();
+                    }
                 };
 
-                let get = generate_result_wrapper_get_source(
-                    def,
-                    interface,
-                    function,
-                    FunctionMode::Static,
-                )?;
+                let get = generate_result_wrapper_get_source(def, function, FunctionMode::Static)?;
 
                 let result_wrapper_impl = quote! {
                     impl crate::bindings::exports::#root_ns::#root_name::#stub_interface_name::#result_wrapper_interface for #result_wrapper {
@@ -201,6 +231,20 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
             }
         }
 
+        let batch = if interface.functions.is_empty() {
+            quote! {}
+        } else {
+            let mode = if interface.is_resource() {
+                FunctionMode::Method
+            } else {
+                FunctionMode::Global
+            };
+            let (batch_fns, batch_result_wrapper_impl) =
+                generate_batch_source(def, interface, mode)?;
+            interface_impls.push(batch_result_wrapper_impl);
+            batch_fns
+        };
+
         let constructor = if interface.is_resource() {
             let constructor_stub = FunctionStub {
                 name: "new".to_string(),
@@ -212,13 +256,16 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
                 &constructor_stub,
                 interface,
                 FunctionMode::Constructor,
+                false,
             )?
         } else {
             quote! {
                 fn new(location: crate::bindings::golem::rpc::types::Uri) -> Self {
                     let location = golem_wasm_rpc::Uri { value: location.value };
                     Self {
-                        rpc: WasmRpc::new(&location)
+                        rpc: WasmRpc::new(&location),
+                        timeout: std::cell::Cell::new(None),
+                        options: std::cell::RefCell::new(None)
                     }
                 }
             }
@@ -229,6 +276,16 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
                 #constructor
 
                 #(#fn_impls)*
+
+                fn set_invocation_timeout(&self, timeout_nanos: Option<u64>) {
+                    self.timeout.set(timeout_nanos);
+                }
+
+                fn set_invocation_options(&self, options: crate::bindings::golem::rpc::types::InvocationOptions) {
+                    *self.options.borrow_mut() = Some(to_invocation_options(options));
+                }
+
+                #batch
             }
         });
 
@@ -277,6 +334,61 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
 
         #(#interface_impls)*
 
+        fn to_invocation_options(options: crate::bindings::golem::rpc::types::InvocationOptions) -> InvocationOptions {
+            InvocationOptions {
+                idempotency_key: options.idempotency_key,
+                retry_policy: options.retry_policy.map(|policy| RetryPolicy {
+                    max_attempts: policy.max_attempts,
+                    min_delay_nanos: policy.min_delay_nanos,
+                    max_delay_nanos: policy.max_delay_nanos,
+                    multiplier: policy.multiplier,
+                }),
+                headers: options.headers,
+                timeout_nanos: options.timeout_nanos,
+            }
+        }
+
+        fn to_batch_options(options: crate::bindings::golem::rpc::types::BatchOptions) -> BatchOptions {
+            BatchOptions {
+                parallel: options.parallel,
+                stop_on_first_error: options.stop_on_first_error,
+            }
+        }
+
+        fn to_stub_rpc_error(error: RpcError) -> crate::bindings::golem::rpc::types::RpcError {
+            match error {
+                RpcError::ProtocolError(details) => crate::bindings::golem::rpc::types::RpcError::ProtocolError(details),
+                RpcError::Denied(details) => crate::bindings::golem::rpc::types::RpcError::Denied(details),
+                RpcError::NotFound(details) => crate::bindings::golem::rpc::types::RpcError::NotFound(details),
+                RpcError::RemoteInternalError(details) => crate::bindings::golem::rpc::types::RpcError::RemoteInternalError(details),
+                RpcError::Timeout(details) => crate::bindings::golem::rpc::types::RpcError::Timeout(details),
+            }
+        }
+
+        fn decode_batch_results<T>(results: Vec<Result<WitValue, RpcError>>, decoders: &[fn(WitValue) -> T]) -> Vec<Result<T, crate::bindings::golem::rpc::types::RpcError>> {
+            results
+                .into_iter()
+                .zip(decoders)
+                .map(|(result, decode)| result.map(*decode).map_err(to_stub_rpc_error))
+                .collect()
+        }
+
+        fn effective_invocation_options(options: Option<InvocationOptions>, timeout: Option<u64>) -> Option<InvocationOptions> {
+            match (options, timeout) {
+                (Some(options), timeout) => Some(InvocationOptions {
+                    timeout_nanos: options.timeout_nanos.or(timeout),
+                    ..options
+                }),
+                (None, Some(timeout_nanos)) => Some(InvocationOptions {
+                    idempotency_key: None,
+                    retry_policy: None,
+                    headers: vec![],
+                    timeout_nanos: Some(timeout_nanos),
+                }),
+                (None, None) => None,
+            }
+        }
+
         #(#exports)*
     };
 
@@ -315,28 +427,174 @@ fn result_wrapper_interface_ident(function: &FunctionStub, owner: &InterfaceStub
     )
 }
 
-fn generate_result_wrapper_get_source(
+fn batch_result_wrapper_ident(owner: &InterfaceStub) -> Ident {
+    stub_type_ident(&owner.async_batch_result_type())
+}
+
+fn stub_type_ident(name: &str) -> Ident {
+    Ident::new(
+        &to_rust_ident(name).to_upper_camel_case(),
+        Span::call_site(),
+    )
+}
+
+/// Generates the batch functions of a stub interface, and the implementation of the resource
+/// returned by the asynchronous one. The invocation timeout and options of the stub instance
+/// are not applied to batches, which only take the batch options.
+fn generate_batch_source(
     def: &StubDefinition,
     interface: &InterfaceStub,
+    mode: FunctionMode,
+) -> anyhow::Result<(TokenStream, TokenStream)> {
+    let root_ns = Ident::new(
+        &def.root_package_name.namespace.to_snake_case(),
+        Span::call_site(),
+    );
+    let root_name = Ident::new(
+        &format!("{}_stub", def.root_package_name.name.to_snake_case()),
+        Span::call_site(),
+    );
+    let stub_interface_name = format!("stub-{}", def.source_world_name()?);
+    let stub_interface_name = Ident::new(
+        &to_rust_ident(&stub_interface_name).to_snake_case(),
+        Span::call_site(),
+    );
+    let exports = quote! { crate::bindings::exports::#root_ns::#root_name::#stub_interface_name };
+    let call = stub_type_ident(&interface.batch_call_type());
+    let call_result = stub_type_ident(&interface.batch_call_result_type());
+    let batch_result_wrapper = batch_result_wrapper_ident(interface);
+    let batch_result_wrapper_interface =
+        stub_type_ident(&format!("guest-{}", interface.async_batch_result_type()));
+
+    let mut arms = Vec::new();
+    for function in &interface.functions {
+        let case = stub_type_ident(&function.name);
+
+        let mut input_values = Vec::new();
+        if mode == FunctionMode::Method {
+            input_values.push(quote! {
+                WitValue::builder().handle(self.uri.clone(), self.id)
+            });
+        }
+        let mut param_names = Vec::new();
+        for param in &function.params {
+            let param_name = Ident::new(&to_rust_ident(&param.name), Span::call_site());
+            input_values.push(wit_value_builder(
+                &param.typ,
+                &quote! { #param_name },
+                def,
+                quote! { WitValue::builder() },
+                false,
+            )?);
+            param_names.push(param_name);
+        }
+        let pattern = match param_names.as_slice() {
+            [] => quote! { #exports::#call::#case },
+            [param_name] => quote! { #exports::#call::#case(#param_name) },
+            param_names => quote! { #exports::#call::#case((#(#param_names),*)) },
+        };
+
+        let output_values = get_output_values_source(def, function, mode)?;
+        let decoded = match output_values.as_slice() {
+            [] => quote! { #exports::#call_result::#case },
+            [output_value] => quote! { #exports::#call_result::#case(#output_value) },
+            output_values => quote! { #exports::#call_result::#case((#(#output_values),*)) },
+        };
+
+        let remote_function_name = get_remote_function_name(
+            def,
+            &function.name,
+            interface.interface_name().as_ref(),
+            interface.resource_name().as_ref(),
+        );
+
+        arms.push(quote! {
+            #pattern => (
+                BatchInvocation {
+                    function_name: #remote_function_name.to_string(),
+                    function_params: vec![
+                        #(#input_values),*
+                    ],
+                },
+                (|result: WitValue| #decoded) as fn(WitValue) -> #exports::#call_result,
+            )
+        });
+    }
+
+    let invocations = quote! {
+        let (invocations, decoders): (Vec<BatchInvocation>, Vec<fn(WitValue) -> #exports::#call_result>) = calls
+            .into_iter()
+            .map(|call| match call {
+                #(#arms),*
+            })
+            .unzip();
+    };
+
+    let batch_fns = quote! {
+        fn blocking_invoke_batch(
+            &self,
+            calls: Vec<#exports::#call>,
+            options: crate::bindings::golem::rpc::types::BatchOptions,
+        ) -> Vec<Result<#exports::#call_result, crate::bindings::golem::rpc::types::RpcError>> {
+            #invocations
+            let results = self.rpc.invoke_batch(&invocations, to_batch_options(options));
+            decode_batch_results(results, &decoders)
+        }
+
+        fn invoke_batch(
+            &self,
+            calls: Vec<#exports::#call>,
+            options: crate::bindings::golem::rpc::types::BatchOptions,
+        ) -> #exports::#batch_result_wrapper {
+            #invocations
+            let result = self.rpc.async_invoke_batch(&invocations, to_batch_options(options));
+            #exports::#batch_result_wrapper::new(#batch_result_wrapper {
+                future_batch_invoke_result: result,
+                decoders,
+            })
+        }
+    };
+
+    let batch_result_wrapper_impl = quote! {
+        impl #exports::#batch_result_wrapper_interface for #batch_result_wrapper {
+            fn subscribe(&self) -> bindings::wasi::io::poll::Pollable {
+                let pollable = self.future_batch_invoke_result.subscribe();
+                let pollable = unsafe {
+                    bindings::wasi::io::poll::Pollable::from_handle(
+                        pollable.take_handle()
+                    )
+                };
+                pollable
+            }
+
+            fn get(&self) -> Option<Vec<Result<#exports::#call_result, crate::bindings::golem::rpc::types::RpcError>>> {
+                self.future_batch_invoke_result
+                    .get()
+                    .map(|results| decode_batch_results(results, &self.decoders))
+            }
+
+            fn cancel(&self) {
+                self.future_batch_invoke_result.cancel();
+            }
+        }
+    };
+
+    Ok((batch_fns, batch_result_wrapper_impl))
+}
+
+fn generate_result_wrapper_get_source(
+    def: &StubDefinition,
     function: &FunctionStub,
     mode: FunctionMode,
 ) -> anyhow::Result<TokenStream> {
-    let result_type = get_result_type_source(def, function)?;
-    let output_values = get_output_values_source(def, function, mode)?;
-
-    let remote_function_name = get_remote_function_name(
-        def,
-        &function.name,
-        interface.interface_name().as_ref(),
-        interface.resource_name().as_ref(),
-    );
+    let result_type = get_invocation_result_type_source(def, function)?;
+    let decode = get_output_decoder_source(def, function, mode)?;
 
     Ok(quote! {
         fn get(&self) -> Option<#result_type> {
-            self.future_invoke_result.get().map(|result| {
-                let result = result.expect(&format!("Failed to invoke remote {}", #remote_function_name));
-                (#(#output_values),*)
-            })
+            self.future_invoke_result
+                .get()
+                .map(|result| result.map(#decode).map_err(to_stub_rpc_error))
         }
     })
 }
@@ -346,8 +604,13 @@ fn generate_function_stub_source(
     function: &FunctionStub,
     owner: &InterfaceStub,
     mode: FunctionMode,
+    with_options: bool,
 ) -> anyhow::Result<TokenStream> {
-    let function_name = Ident::new(&to_rust_ident(&function.name), Span::call_site());
+    let suffix = if with_options { "-with-options" } else { "" };
+    let function_name = Ident::new(
+        &to_rust_ident(&format!("{}{suffix}", function.name)),
+        Span::call_site(),
+    );
     let mut params = Vec::new();
     let mut input_values = Vec::new();
 
@@ -382,8 +645,11 @@ fn generate_function_stub_source(
         )?);
     }
 
-    let result_type = get_result_type_source(def, function)?;
-    let output_values = get_output_values_source(def, function, mode)?;
+    if with_options {
+        params.push(quote! {
+            invocation_options: crate::bindings::golem::rpc::types::InvocationOptions
+        });
+    }
 
     let remote_function_name = get_remote_function_name(
         def,
@@ -392,7 +658,7 @@ fn generate_function_stub_source(
         owner.resource_name().as_ref(),
     );
 
-    let rpc = match mode {
+    let (rpc, this) = match mode {
         FunctionMode::Static => {
             let first_param = function
                 .params
@@ -419,14 +685,24 @@ fn generate_function_stub_source(
             }?;
             let first_param_type = resource_type_ident(type_id, &def.resolve)?;
 
-            quote! { #first_param_ident.get::<#first_param_type>().rpc }
-        }
-        FunctionMode::Constructor => {
-            quote! { rpc }
-        }
-        _ => {
-            quote! { self.rpc }
+            (
+                quote! { #first_param_ident.get::<#first_param_type>().rpc },
+                Some(quote! { #first_param_ident.get::<#first_param_type>() }),
+            )
         }
+        FunctionMode::Constructor => (quote! { rpc }, None),
+        _ => (quote! { self.rpc }, Some(quote! { self })),
+    };
+
+    // Per-call options replace the options set on the stub instance, the instance timeout still applies
+    let options = match (this, with_options) {
+        (Some(this), true) => quote! {
+            effective_invocation_options(Some(to_invocation_options(invocation_options)), #this.timeout.get())
+        },
+        (Some(this), false) => quote! {
+            effective_invocation_options(#this.options.borrow().clone(), #this.timeout.get())
+        },
+        (None, _) => quote! { None },
     };
 
     let init = if mode == FunctionMode::Constructor {
@@ -442,35 +718,73 @@ fn generate_function_stub_source(
         let blocking_function_name = if mode == FunctionMode::Constructor {
             function.name.clone()
         } else {
-            format!("blocking-{}", function.name)
+            format!("blocking-{}{suffix}", function.name)
         };
         let function_name = Ident::new(&to_rust_ident(&blocking_function_name), Span::call_site());
-        quote! {
-            fn #function_name(#(#params),*) -> #result_type {
-                #init
-                let result = #rpc.invoke_and_await(
+        let invocation = quote! {
+            match options {
+                Some(options) => #rpc.invoke_and_await_with_options(
+                    #remote_function_name,
+                    &[
+                        #(#input_values),*
+                    ],
+                    &options,
+                ),
+                None => #rpc.invoke_and_await(
                     #remote_function_name,
                     &[
                         #(#input_values),*
                     ],
-                ).expect(&format!("Failed to invoke-and-await remote {}", #remote_function_name));
-                (#(#output_values),*)
+                ),
+            }
+        };
+        if mode == FunctionMode::Constructor {
+            // constructors are invoked without options, so they are not subject to timeouts
+            let result_type = get_result_type_source(def, function)?;
+            let output_values = get_output_values_source(def, function, mode)?;
+            quote! {
+                fn #function_name(#(#params),*) -> #result_type {
+                    #init
+                    let options: Option<InvocationOptions> = #options;
+                    let result = #invocation.expect(&format!("Failed to invoke-and-await remote {}", #remote_function_name));
+                    (#(#output_values),*)
+                }
+            }
+        } else {
+            let result_type = get_invocation_result_type_source(def, function)?;
+            let decode = get_output_decoder_source(def, function, mode)?;
+            quote! {
+                fn #function_name(#(#params),*) -> #result_type {
+                    #init
+                    let options: Option<InvocationOptions> = #options;
+                    #invocation.map(#decode).map_err(to_stub_rpc_error)
+                }
             }
         }
     };
 
     let non_blocking = if mode != FunctionMode::Constructor {
         if function.results.is_empty() {
+            let result_type = get_invocation_result_type_source(def, function)?;
             quote! {
                 fn #function_name(#(#params),*) -> #result_type {
                     #init
-                    let result = #rpc.invoke(
-                        #remote_function_name,
-                        &[
-                            #(#input_values),*
-                        ],
-                    ).expect(&format!("Failed to invoke remote {}", #remote_function_name));
-                    (#(#output_values),*)
+                    let options: Option<InvocationOptions> = #options;
+                    match options {
+                        Some(options) => #rpc.invoke_with_options(
+                            #remote_function_name,
+                            &[
+                                #(#input_values),*
+                            ],
+                            &options,
+                        ),
+                        None => #rpc.invoke(
+                            #remote_function_name,
+                            &[
+                                #(#input_values),*
+                            ],
+                        ),
+                    }.map_err(to_stub_rpc_error)
                 }
             }
         } else {
@@ -491,12 +805,22 @@ fn generate_function_stub_source(
             quote! {
                 fn #function_name(#(#params),*) -> crate::bindings::exports::#root_ns::#root_name::#stub_interface_name::#result_wrapper {
                     #init
-                    let result = #rpc.async_invoke_and_await(
-                        #remote_function_name,
-                        &[
-                            #(#input_values),*
-                        ],
-                    );
+                    let options: Option<InvocationOptions> = #options;
+                    let result = match options {
+                        Some(options) => #rpc.async_invoke_and_await_with_options(
+                            #remote_function_name,
+                            &[
+                                #(#input_values),*
+                            ],
+                            &options,
+                        ),
+                        None => #rpc.async_invoke_and_await(
+                            #remote_function_name,
+                            &[
+                                #(#input_values),*
+                            ],
+                        ),
+                    };
                     crate::bindings::exports::#root_ns::#root_name::#stub_interface_name::#result_wrapper::new(#result_wrapper { future_invoke_result: result})
                 }
             }
@@ -540,6 +864,8 @@ fn get_output_values_source(
                     let (uri, id) = result.tuple_element(0).expect("tuple not found").handle().expect("handle not found");
                     Self {
                         rpc,
+                        timeout: std::cell::Cell::new(None),
+                        options: std::cell::RefCell::new(None),
                         id,
                         uri
                     }
@@ -555,6 +881,47 @@ fn get_output_values_source(
     Ok(output_values)
 }
 
+/// The result of an invocation which is subject to timeouts and cancellation, and so returns the
+/// `rpc-error` of failed invocations instead of trapping
+fn get_invocation_result_type_source(
+    def: &StubDefinition,
+    function: &FunctionStub,
+) -> anyhow::Result<TokenStream> {
+    let value_type = match &function.results {
+        FunctionResultStub::Single(typ) => type_to_rust_ident(typ, def)?,
+        FunctionResultStub::Multi(params) => {
+            let types = params
+                .iter()
+                .map(|param| type_to_rust_ident(&param.typ, def))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            quote! { (#(#types,)*) }
+        }
+        FunctionResultStub::SelfType => {
+            return Err(anyhow!(
+                "SelfType result is only supported for constructors"
+            ));
+        }
+    };
+    Ok(quote! {
+        Result<#value_type, crate::bindings::golem::rpc::types::RpcError>
+    })
+}
+
+/// A closure decoding the value returned by a successful invocation to the type of
+/// `get_invocation_result_type_source`
+fn get_output_decoder_source(
+    def: &StubDefinition,
+    function: &FunctionStub,
+    mode: FunctionMode,
+) -> anyhow::Result<TokenStream> {
+    let output_values = get_output_values_source(def, function, mode)?;
+    Ok(match &function.results {
+        FunctionResultStub::Multi(params) if params.is_empty() => quote! { |_| () },
+        FunctionResultStub::Multi(_) => quote! { |result| (#(#output_values,)*) },
+        _ => quote! { |result| #(#output_values)* },
+    })
+}
+
 fn get_result_type_source(
     def: &StubDefinition,
     function: &FunctionStub,
@@ -193,6 +193,20 @@ impl InterfaceStub {
             None
         }
     }
+
+    /// The variant with a case for calling each function, used by batch invocations
+    pub fn batch_call_type(&self) -> String {
+        format!("{}-call", self.name)
+    }
+
+    /// The variant with a case for the result of each function, used by batch invocations
+    pub fn batch_call_result_type(&self) -> String {
+        format!("{}-call-result", self.name)
+    }
+
+    pub fn async_batch_result_type(&self) -> String {
+        format!("future-{}-batch-result", self.name)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -66,7 +66,10 @@ pub fn get_stub_wit(
         .collect::<IndexMap<_, _>>();
 
     // Renaming the mandatory imports to avoid collisions with types coming from the stubbed package
-    writeln!(out, "  use golem:rpc/types@0.1.0.{{uri as golem-rpc-uri}};")?;
+    writeln!(
+        out,
+        "  use golem:rpc/types@0.1.0.{{uri as golem-rpc-uri, invocation-options as golem-rpc-invocation-options, batch-options as golem-rpc-batch-options, rpc-error as golem-rpc-rpc-error}};"
+    )?;
     writeln!(
         out,
         "  use wasi:io/poll@0.2.0.{{pollable as wasi-io-pollable}};"
@@ -111,10 +114,14 @@ pub fn get_stub_wit(
                 write_async_return_type(&mut out, function, interface, def)?;
             }
         }
+        if !interface.functions.is_empty() {
+            write_batch_types(&mut out, interface, def)?;
+        }
     }
 
     // Generating function definitions
     for interface in &def.interfaces {
+        check_generated_names(interface)?;
         writeln!(out, "  resource {} {{", &interface.name)?;
         match &interface.constructor_params {
             None => {
@@ -135,6 +142,36 @@ pub fn get_stub_wit(
         for function in &interface.static_functions {
             write_function_definition(&mut out, function, true, interface, def)?;
         }
+        writeln!(
+            out,
+            "    set-invocation-timeout: func(timeout-nanos: option<u64>);"
+        )?;
+        writeln!(
+            out,
+            "    set-invocation-options: func(options: golem-rpc-invocation-options);"
+        )?;
+        if !interface.functions.is_empty() {
+            writeln!(
+                out,
+                "    /// Batches are not affected by `set-invocation-timeout` and `set-invocation-options`"
+            )?;
+            writeln!(
+                out,
+                "    blocking-invoke-batch: func(calls: list<{}>, options: golem-rpc-batch-options) -> list<result<{}, golem-rpc-rpc-error>>;",
+                interface.batch_call_type(),
+                interface.batch_call_result_type()
+            )?;
+            writeln!(
+                out,
+                "    /// Like `blocking-invoke-batch`, without waiting for the results"
+            )?;
+            writeln!(
+                out,
+                "    invoke-batch: func(calls: list<{}>, options: golem-rpc-batch-options) -> {};",
+                interface.batch_call_type(),
+                interface.async_batch_result_type()
+            )?;
+        }
         writeln!(out, "  }}")?;
         writeln!(out)?;
     }
@@ -149,38 +186,102 @@ pub fn get_stub_wit(
     Ok(out)
 }
 
+/// Fails if a function name generated in the stub resource of an interface is also generated for
+/// another function, or if a parameter collides with the added `invocation-options` parameter
+fn check_generated_names(interface: &InterfaceStub) -> anyhow::Result<()> {
+    let mut generated: IndexMap<String, String> = IndexMap::new();
+    let mut add = |name: String, source: String| match generated.get(&name) {
+        Some(other) => Err(name_collision(&interface.name, &name, &source, other)),
+        None => {
+            generated.insert(name, source);
+            Ok(())
+        }
+    };
+
+    for function in interface
+        .functions
+        .iter()
+        .chain(interface.static_functions.iter())
+    {
+        let source = format!("function {}", function.name);
+        for suffix in ["", "-with-options"] {
+            add(format!("{}{suffix}", function.name), source.clone())?;
+            add(
+                format!("blocking-{}{suffix}", function.name),
+                source.clone(),
+            )?;
+        }
+        if function
+            .params
+            .iter()
+            .any(|param| param.name == "invocation-options")
+        {
+            bail!(
+                "Cannot generate the stub of {}: the parameter invocation-options of function {} collides with the one added to {}-with-options",
+                interface.name,
+                function.name,
+                function.name
+            );
+        }
+    }
+
+    let source = "setting the invocation options".to_string();
+    add("set-invocation-timeout".to_string(), source.clone())?;
+    add("set-invocation-options".to_string(), source)?;
+    if !interface.functions.is_empty() {
+        let source = "batch invocations".to_string();
+        add("blocking-invoke-batch".to_string(), source.clone())?;
+        add("invoke-batch".to_string(), source)?;
+    }
+    Ok(())
+}
+
+fn name_collision(interface_name: &str, name: &str, source: &str, other: &str) -> anyhow::Error {
+    anyhow!(
+        "Cannot generate the stub of {interface_name}: the function {name} generated for {source} \
+         collides with the one generated for {other}"
+    )
+}
+
 fn write_function_definition(
     out: &mut String,
     function: &FunctionStub,
     is_static: bool,
     owner: &InterfaceStub,
     def: &StubDefinition,
+) -> anyhow::Result<()> {
+    // Every function gets a variant taking the invocation options of a single call
+    for (suffix, with_options) in [("", false), ("-with-options", true)] {
+        write_function_variants(out, function, is_static, owner, def, suffix, with_options)?;
+    }
+    Ok(())
+}
+
+fn write_function_variants(
+    out: &mut String,
+    function: &FunctionStub,
+    is_static: bool,
+    owner: &InterfaceStub,
+    def: &StubDefinition,
+    suffix: &str,
+    with_options: bool,
 ) -> anyhow::Result<()> {
     let func = if is_static { "static_func" } else { "func" };
+    // Write the blocking function
+    write!(out, "    blocking-{}{suffix}: {func}(", function.name)?;
+    write_param_list_with_options(out, def, &function.params, with_options)?;
+    write!(out, ") -> ")?;
+    write_invocation_result_type(out, function, def)?;
+    writeln!(out, ";")?;
+
+    // Write the non-blocking function
+    write!(out, "    {}{suffix}: {func}(", function.name)?;
+    write_param_list_with_options(out, def, &function.params, with_options)?;
+    write!(out, ") -> ")?;
     if !function.results.is_empty() {
-        // Write the blocking function
-        write!(out, "    blocking-{}: {func}(", function.name)?;
-        write_param_list(out, def, &function.params)?;
-        write!(out, ")")?;
-        write!(out, " -> ")?;
-        write_function_result_type(out, function, def)?;
-        writeln!(out, ";")?;
-        // Write the non-blocking function
-        write!(out, "    {}: {func}(", function.name)?;
-        write_param_list(out, def, &function.params)?;
-        write!(out, ")")?;
-        write!(out, " -> {}", function.async_result_type(owner))?;
+        write!(out, "{}", function.async_result_type(owner))?;
     } else {
-        // Write the blocking function
-        write!(out, "    blocking-{}: {func}(", function.name)?;
-        write_param_list(out, def, &function.params)?;
-        write!(out, ")")?;
-        writeln!(out, ";")?;
-
-        // Write the non-blocking function
-        write!(out, "    {}: {func}(", function.name)?;
-        write_param_list(out, def, &function.params)?;
-        write!(out, ")")?;
+        write_invocation_result_type(out, function, def)?;
     }
     writeln!(out, ";")?;
     Ok(())
@@ -195,30 +296,92 @@ fn write_async_return_type(
     writeln!(out, "  resource {} {{", function.async_result_type(owner))?;
     writeln!(out, "    subscribe: func() -> wasi-io-pollable;")?;
     write!(out, "    get: func() -> option<")?;
-    write_function_result_type(out, function, def)?;
+    write_invocation_result_type(out, function, def)?;
     writeln!(out, ">;")?;
+    writeln!(out, "    cancel: func();")?;
     writeln!(out, "  }}")?;
     Ok(())
 }
 
-fn write_function_result_type(
+fn write_batch_types(
+    out: &mut String,
+    interface: &InterfaceStub,
+    def: &StubDefinition,
+) -> anyhow::Result<()> {
+    writeln!(out, "  variant {} {{", interface.batch_call_type())?;
+    for function in &interface.functions {
+        let types = function
+            .params
+            .iter()
+            .map(|param| param.typ.wit_type_string(&def.resolve))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        write_batch_case(out, &function.name, &types)?;
+    }
+    writeln!(out, "  }}")?;
+    writeln!(out)?;
+
+    writeln!(out, "  variant {} {{", interface.batch_call_result_type())?;
+    for function in &interface.functions {
+        let types = match &function.results {
+            FunctionResultStub::Single(typ) => vec![typ.wit_type_string(&def.resolve)?],
+            FunctionResultStub::Multi(params) => params
+                .iter()
+                .map(|param| param.typ.wit_type_string(&def.resolve))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            FunctionResultStub::SelfType => {
+                return Err(anyhow!("Unexpected return type in wit generator"));
+            }
+        };
+        write_batch_case(out, &function.name, &types)?;
+    }
+    writeln!(out, "  }}")?;
+    writeln!(out)?;
+
+    writeln!(out, "  resource {} {{", interface.async_batch_result_type())?;
+    writeln!(out, "    subscribe: func() -> wasi-io-pollable;")?;
+    writeln!(
+        out,
+        "    get: func() -> option<list<result<{}, golem-rpc-rpc-error>>>;",
+        interface.batch_call_result_type()
+    )?;
+    writeln!(out, "    cancel: func();")?;
+    writeln!(out, "  }}")?;
+    Ok(())
+}
+
+/// Writes a case of a batch variant, with a tuple payload if there are multiple types
+fn write_batch_case(out: &mut String, name: &str, types: &[String]) -> anyhow::Result<()> {
+    match types {
+        [] => writeln!(out, "    {name},")?,
+        [typ] => writeln!(out, "    {name}({typ}),")?,
+        types => writeln!(out, "    {name}(tuple<{}>),", types.join(", "))?,
+    }
+    Ok(())
+}
+
+/// Writes the result of an invocation, which returns the `rpc-error` of failed invocations as
+/// they are subject to timeouts and cancellation
+fn write_invocation_result_type(
     out: &mut String,
     function: &FunctionStub,
     def: &StubDefinition,
 ) -> anyhow::Result<()> {
-    match &function.results {
-        FunctionResultStub::Single(typ) => {
-            write!(out, "{}", typ.wit_type_string(&def.resolve)?)?;
-        }
-        FunctionResultStub::Multi(params) => {
-            write!(out, "(")?;
-            write_param_list(out, def, params)?;
-            write!(out, ")")?;
-        }
+    let value_type = match &function.results {
+        FunctionResultStub::Single(typ) => typ.wit_type_string(&def.resolve)?,
+        FunctionResultStub::Multi(params) if params.is_empty() => "_".to_string(),
+        FunctionResultStub::Multi(params) => format!(
+            "tuple<{}>",
+            params
+                .iter()
+                .map(|param| param.typ.wit_type_string(&def.resolve))
+                .collect::<anyhow::Result<Vec<_>>>()?
+                .join(", ")
+        ),
         FunctionResultStub::SelfType => {
             return Err(anyhow!("Unexpected return type in wit generator"));
         }
-    }
+    };
+    write!(out, "result<{value_type}, golem-rpc-rpc-error>")?;
     Ok(())
 }
 
@@ -482,6 +645,22 @@ fn write_param_list(
     Ok(())
 }
 
+fn write_param_list_with_options(
+    out: &mut String,
+    def: &StubDefinition,
+    params: &[FunctionParamStub],
+    with_options: bool,
+) -> anyhow::Result<()> {
+    write_param_list(out, def, params)?;
+    if with_options {
+        if !params.is_empty() {
+            write!(out, ", ")?;
+        }
+        write!(out, "invocation-options: golem-rpc-invocation-options")?;
+    }
+    Ok(())
+}
+
 pub fn copy_wit_files(def: &StubDefinition) -> anyhow::Result<()> {
     let mut all = def.unresolved_deps.clone();
     all.push(def.unresolved_root.clone());
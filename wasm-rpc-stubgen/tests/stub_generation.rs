@@ -0,0 +1,203 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates the stub of the example component and checks that the generated crate compiles,
+//! with bindings generated the same way as by `cargo component`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use fs_extra::dir::CopyOptions;
+use golem_wasm_rpc_stubgen::{generate, GenerateArgs, WasmRpcOverride};
+use wit_bindgen_core::wit_parser::Resolve;
+use wit_bindgen_core::Files;
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+/// Copies the example WIT, with `edit` applied to the root package
+fn example_wit(target: &Path, edit: impl FnOnce(String) -> String) -> PathBuf {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("example");
+    fs_extra::dir::copy(
+        example,
+        target,
+        &CopyOptions {
+            content_only: true,
+            ..CopyOptions::default()
+        },
+    )
+    .unwrap();
+    let main = target.join("main.wit");
+    fs::write(&main, edit(fs::read_to_string(&main).unwrap())).unwrap();
+    target.to_path_buf()
+}
+
+fn generate_stub(source_wit_root: &Path, dest_crate_root: &Path) -> anyhow::Result<()> {
+    generate(GenerateArgs {
+        source_wit_root: source_wit_root.to_path_buf(),
+        dest_crate_root: dest_crate_root.to_path_buf(),
+        world: None,
+        stub_crate_version: "0.0.1".to_string(),
+        wasm_rpc_override: WasmRpcOverride {
+            wasm_rpc_path_override: Some(
+                workspace_root()
+                    .join("wasm-rpc")
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            wasm_rpc_version_override: None,
+        },
+        always_inline_types: false,
+    })
+}
+
+/// Writes the guest bindings of the stub world to `src/bindings.rs`
+fn generate_bindings(stub_root: &Path) {
+    let mut resolve = Resolve::new();
+    let (package, _) = resolve.push_dir(&stub_root.join("wit")).unwrap();
+    let world = resolve.select_world(package, None).unwrap();
+
+    let mut files = Files::default();
+    wit_bindgen_rust::Opts {
+        runtime_path: Some("wit_bindgen_rt".to_string()),
+        bitflags_path: Some("wit_bindgen_rt::bitflags".to_string()),
+        ..Default::default()
+    }
+    .build()
+    .generate(&resolve, world, &mut files)
+    .unwrap();
+    let (_, source) = files.iter().next().unwrap();
+    fs::write(stub_root.join("src/bindings.rs"), source).unwrap();
+}
+
+/// Generates the stub of the example component with its bindings to `dir/stub`
+fn generate_example_stub(dir: &Path) -> PathBuf {
+    // static functions returning the resource itself are not supported by the generator
+    let source = example_wit(&dir.join("source"), |wit| {
+        wit.lines()
+            .filter(|line| !line.contains("merge: static func"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+    let stub_root = dir.join("stub");
+    generate_stub(&source, &stub_root).unwrap();
+    generate_bindings(&stub_root);
+    stub_root
+}
+
+fn assert_compiles(stub_root: &Path) {
+    let output = Command::new(env!("CARGO"))
+        .arg("check")
+        .arg("--offline")
+        .arg("--manifest-path")
+        .arg(stub_root.join("Cargo.toml"))
+        .env(
+            "CARGO_TARGET_DIR",
+            workspace_root().join("target").join("stub-generation"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn generated_stub_compiles() {
+    let dir = tempfile::tempdir().unwrap();
+    let stub_root = generate_example_stub(dir.path());
+    assert_compiles(&stub_root);
+}
+
+/// The stub can only be run as a component, so this checks that a caller can cancel an
+/// invocation and then handle the `timeout` error returned by `get` instead of trapping
+#[test]
+fn cancelled_invocations_return_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let stub_root = generate_example_stub(dir.path());
+
+    let wit = fs::read_to_string(stub_root.join("wit/_stub.wit")).unwrap();
+    assert!(
+        wit.contains("get: func() -> option<result<checkout-result, golem-rpc-rpc-error>>"),
+        "{wit}"
+    );
+    assert!(
+        wit.contains(
+            "/// Batches are not affected by `set-invocation-timeout` and `set-invocation-options`"
+        ),
+        "{wit}"
+    );
+
+    let lib = stub_root.join("src/lib.rs");
+    let source = fs::read_to_string(&lib).unwrap();
+    fs::write(
+        &lib,
+        format!(
+            r#"{source}
+#[allow(dead_code)]
+fn cancelled_checkout_times_out(cart: &Cart) -> bool {{
+    use crate::bindings::exports::test::main_stub::stub_api::{{
+        GuestCart, GuestFutureCartCheckoutResult,
+    }};
+    use crate::bindings::golem::rpc::types::RpcError;
+
+    let future = cart.checkout();
+    let future = future.get::<FutureCartCheckoutResult>();
+    future.cancel();
+    matches!(future.get(), Some(Err(RpcError::Timeout(_))))
+}}
+"#
+        ),
+    )
+    .unwrap();
+    assert_compiles(&stub_root);
+}
+
+#[test]
+fn colliding_function_names_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = example_wit(&dir.path().join("source"), |wit| {
+        wit.replace(
+            "checkout: func() -> checkout-result;",
+            "checkout: func() -> checkout-result;\n    checkout-with-options: func();",
+        )
+    });
+    let error = generate_stub(&source, &dir.path().join("stub")).unwrap_err();
+    assert!(
+        format!("{error:#}").contains(
+            "the function checkout-with-options generated for function checkout-with-options collides with the one generated for function checkout"
+        ),
+        "{error:#}"
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let source = example_wit(&dir.path().join("source"), |wit| {
+        wit.replace(
+            "checkout: func() -> checkout-result;",
+            "checkout: func(invocation-options: string) -> checkout-result;",
+        )
+    });
+    let error = generate_stub(&source, &dir.path().join("stub")).unwrap_err();
+    assert!(
+        format!("{error:#}").contains("the parameter invocation-options of function checkout"),
+        "{error:#}"
+    );
+}
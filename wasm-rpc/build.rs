@@ -16,6 +16,7 @@ fn main() -> Result<()> {
             "proto/wasm/rpc/val.proto",
             "proto/wasm/rpc/witvalue.proto",
             "proto/wasm/rpc/type_annotated_value.proto",
+            "proto/wasm/rpc/invocation.proto",
         ],
         &[&format!("{wasm_ast_root}/proto"), &"proto".to_string()],
     )?;
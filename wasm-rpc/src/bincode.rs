@@ -1,4 +1,4 @@
-use crate::{Uri, WitNode, WitValue};
+use crate::{RpcError, Uri, WitNode, WitValue};
 use bincode::de::{BorrowDecoder, Decoder};
 use bincode::enc::Encoder;
 use bincode::error::{AllowedEnumVariants, DecodeError, EncodeError};
@@ -24,6 +24,71 @@ impl<'de> BorrowDecode<'de> for Uri {
     }
 }
 
+impl Encode for RpcError {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            RpcError::ProtocolError(details) => {
+                0u8.encode(encoder)?;
+                details.encode(encoder)
+            }
+            RpcError::Denied(details) => {
+                1u8.encode(encoder)?;
+                details.encode(encoder)
+            }
+            RpcError::NotFound(details) => {
+                2u8.encode(encoder)?;
+                details.encode(encoder)
+            }
+            RpcError::RemoteInternalError(details) => {
+                3u8.encode(encoder)?;
+                details.encode(encoder)
+            }
+            RpcError::Timeout(details) => {
+                4u8.encode(encoder)?;
+                details.encode(encoder)
+            }
+        }
+    }
+}
+
+impl Decode for RpcError {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let tag: u8 = Decode::decode(decoder)?;
+        match tag {
+            0 => Ok(RpcError::ProtocolError(String::decode(decoder)?)),
+            1 => Ok(RpcError::Denied(String::decode(decoder)?)),
+            2 => Ok(RpcError::NotFound(String::decode(decoder)?)),
+            3 => Ok(RpcError::RemoteInternalError(String::decode(decoder)?)),
+            4 => Ok(RpcError::Timeout(String::decode(decoder)?)),
+            _ => Err(DecodeError::UnexpectedVariant {
+                found: tag as u32,
+                type_name: "RpcError",
+                allowed: &AllowedEnumVariants::Range { min: 0, max: 4 },
+            }),
+        }
+    }
+}
+
+impl<'de> BorrowDecode<'de> for RpcError {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let tag: u8 = BorrowDecode::borrow_decode(decoder)?;
+        match tag {
+            0 => Ok(RpcError::ProtocolError(String::borrow_decode(decoder)?)),
+            1 => Ok(RpcError::Denied(String::borrow_decode(decoder)?)),
+            2 => Ok(RpcError::NotFound(String::borrow_decode(decoder)?)),
+            3 => Ok(RpcError::RemoteInternalError(String::borrow_decode(
+                decoder,
+            )?)),
+            4 => Ok(RpcError::Timeout(String::borrow_decode(decoder)?)),
+            _ => Err(DecodeError::UnexpectedVariant {
+                found: tag as u32,
+                type_name: "RpcError",
+                allowed: &AllowedEnumVariants::Range { min: 0, max: 4 },
+            }),
+        }
+    }
+}
+
 impl Encode for WitValue {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.nodes.encode(encoder)
@@ -349,7 +414,7 @@ impl<'de> BorrowDecode<'de> for WitNode {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Value, WitValue};
+    use crate::{RpcError, Value, WitValue};
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb_sized;
 
@@ -377,4 +442,20 @@ mod tests {
             prop_assert_eq!(value, decoded);
         }
     }
+
+    #[test]
+    fn round_trip_rpc_error() {
+        for error in [
+            RpcError::ProtocolError("protocol".to_string()),
+            RpcError::Denied("denied".to_string()),
+            RpcError::NotFound("not found".to_string()),
+            RpcError::RemoteInternalError("internal".to_string()),
+            RpcError::Timeout("timeout".to_string()),
+        ] {
+            let encoded = bincode::encode_to_vec(&error, bincode::config::standard()).unwrap();
+            let (decoded, _): (RpcError, usize) =
+                bincode::decode_from_slice(&encoded, bincode::config::standard()).unwrap();
+            assert_eq!(format!("{decoded:?}"), format!("{error:?}"));
+        }
+    }
 }
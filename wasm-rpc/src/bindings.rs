@@ -119,6 +119,7 @@ pub mod golem {
                 Denied(_rt::String),
                 NotFound(_rt::String),
                 RemoteInternalError(_rt::String),
+                Timeout(_rt::String),
             }
             impl ::core::fmt::Debug for RpcError {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
@@ -134,6 +135,9 @@ pub mod golem {
                             .debug_tuple("RpcError::RemoteInternalError")
                             .field(e)
                             .finish(),
+                        RpcError::Timeout(e) => {
+                            f.debug_tuple("RpcError::Timeout").field(e).finish()
+                        }
                     }
                 }
             }
@@ -144,6 +148,80 @@ pub mod golem {
             }
 
             impl std::error::Error for RpcError {}
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct RetryPolicy {
+                /// The maximum number of attempts, including the first one
+                pub max_attempts: u32,
+                /// The delay before the first retry
+                pub min_delay_nanos: u64,
+                /// The upper bound of the delay between attempts
+                pub max_delay_nanos: u64,
+                /// The factor the delay grows by after each retry
+                pub multiplier: f64,
+            }
+            impl ::core::fmt::Debug for RetryPolicy {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct("RetryPolicy")
+                        .field("max-attempts", &self.max_attempts)
+                        .field("min-delay-nanos", &self.min_delay_nanos)
+                        .field("max-delay-nanos", &self.max_delay_nanos)
+                        .field("multiplier", &self.multiplier)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct InvocationOptions {
+                /// Lets the remote side recognize retried or repeated invocations
+                pub idempotency_key: Option<_rt::String>,
+                /// Retries invocations failing with `remote-internal-error` or `timeout`
+                pub retry_policy: Option<RetryPolicy>,
+                /// Metadata passed to the transport, such as tracing headers or tenant ids
+                pub headers: _rt::Vec<(_rt::String, _rt::String)>,
+                /// Bounds each attempt like `invoke-and-await-with-timeout`
+                pub timeout_nanos: Option<u64>,
+            }
+            impl ::core::fmt::Debug for InvocationOptions {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct("InvocationOptions")
+                        .field("idempotency-key", &self.idempotency_key)
+                        .field("retry-policy", &self.retry_policy)
+                        .field("headers", &self.headers)
+                        .field("timeout-nanos", &self.timeout_nanos)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct BatchInvocation {
+                pub function_name: _rt::String,
+                pub function_params: _rt::Vec<WitValue>,
+            }
+            impl ::core::fmt::Debug for BatchInvocation {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct("BatchInvocation")
+                        .field("function-name", &self.function_name)
+                        .field("function-params", &self.function_params)
+                        .finish()
+                }
+            }
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct BatchOptions {
+                /// Performs the invocations concurrently instead of one after the other. The results are
+                /// in the order of the invocations either way.
+                pub parallel: bool,
+                /// Ends the batch at the first failed invocation, which is the last result. The invocations
+                /// after it are not performed, or abandoned if they were performed in parallel.
+                pub stop_on_first_error: bool,
+            }
+            impl ::core::fmt::Debug for BatchOptions {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct("BatchOptions")
+                        .field("parallel", &self.parallel)
+                        .field("stop-on-first-error", &self.stop_on_first_error)
+                        .finish()
+                }
+            }
 
             #[derive(Debug)]
             #[repr(transparent)]
@@ -233,6 +311,94 @@ pub mod golem {
                 }
             }
 
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct FutureBatchInvokeResult {
+                handle: _rt::Resource<FutureBatchInvokeResult>,
+            }
+
+            impl FutureBatchInvokeResult {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: _rt::Resource::from_handle(handle),
+                    }
+                }
+
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+
+            unsafe impl _rt::WasmResource for FutureBatchInvokeResult {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unreachable!();
+
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[resource-drop]future-batch-invoke-result"]
+                            fn drop(_: u32);
+                        }
+
+                        drop(_handle);
+                    }
+                }
+            }
+
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct InvokeResultStream {
+                handle: _rt::Resource<InvokeResultStream>,
+            }
+
+            impl InvokeResultStream {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: _rt::Resource::from_handle(handle),
+                    }
+                }
+
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+
+            unsafe impl _rt::WasmResource for InvokeResultStream {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unreachable!();
+
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[resource-drop]invoke-result-stream"]
+                            fn drop(_: u32);
+                        }
+
+                        drop(_handle);
+                    }
+                }
+            }
+
             impl WasmRpc {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn new(location: &Uri) -> Self {
@@ -905,9 +1071,9 @@ pub mod golem {
                             1 => {
                                 let e = {
                                     let l63 = i32::from(*ptr13.add(4).cast::<u8>());
-                                    let v76 = match l63 {
+                                    let v79 = match l63 {
                                         0 => {
-                                            let e76 = {
+                                            let e79 = {
                                                 let l64 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l65 = *ptr13.add(12).cast::<usize>();
                                                 let len66 = l65;
@@ -919,10 +1085,10 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes66)
                                             };
-                                            RpcError::ProtocolError(e76)
+                                            RpcError::ProtocolError(e79)
                                         }
                                         1 => {
-                                            let e76 = {
+                                            let e79 = {
                                                 let l67 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l68 = *ptr13.add(12).cast::<usize>();
                                                 let len69 = l68;
@@ -934,10 +1100,10 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes69)
                                             };
-                                            RpcError::Denied(e76)
+                                            RpcError::Denied(e79)
                                         }
                                         2 => {
-                                            let e76 = {
+                                            let e79 = {
                                                 let l70 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l71 = *ptr13.add(12).cast::<usize>();
                                                 let len72 = l71;
@@ -949,11 +1115,10 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes72)
                                             };
-                                            RpcError::NotFound(e76)
+                                            RpcError::NotFound(e79)
                                         }
-                                        n => {
-                                            debug_assert_eq!(n, 3, "invalid enum discriminant");
-                                            let e76 = {
+                                        3 => {
+                                            let e79 = {
                                                 let l73 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l74 = *ptr13.add(12).cast::<usize>();
                                                 let len75 = l74;
@@ -965,11 +1130,27 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes75)
                                             };
-                                            RpcError::RemoteInternalError(e76)
+                                            RpcError::RemoteInternalError(e79)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e79 = {
+                                                let l76 = *ptr13.add(8).cast::<*mut u8>();
+                                                let l77 = *ptr13.add(12).cast::<usize>();
+                                                let len78 = l77;
+                                                let bytes78 = _rt::Vec::from_raw_parts(
+                                                    l76.cast(),
+                                                    len78,
+                                                    len78,
+                                                );
+
+                                                _rt::string_lift(bytes78)
+                                            };
+                                            RpcError::Timeout(e79)
                                         }
                                     };
 
-                                    v76
+                                    v79
                                 };
                                 Err(e)
                             }
@@ -980,6 +1161,8 @@ pub mod golem {
             }
             impl WasmRpc {
                 #[allow(unused_unsafe, clippy::all)]
+                /// Like `invoke-and-await`, ignoring the result of the invocation. It still waits until the
+                /// invocation has finished, and fails if the invocation failed.
                 pub fn invoke(
                     &self,
                     function_name: &str,
@@ -1292,9 +1475,9 @@ pub mod golem {
                             1 => {
                                 let e = {
                                     let l15 = i32::from(*ptr13.add(4).cast::<u8>());
-                                    let v28 = match l15 {
+                                    let v31 = match l15 {
                                         0 => {
-                                            let e28 = {
+                                            let e31 = {
                                                 let l16 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l17 = *ptr13.add(12).cast::<usize>();
                                                 let len18 = l17;
@@ -1306,10 +1489,10 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes18)
                                             };
-                                            RpcError::ProtocolError(e28)
+                                            RpcError::ProtocolError(e31)
                                         }
                                         1 => {
-                                            let e28 = {
+                                            let e31 = {
                                                 let l19 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l20 = *ptr13.add(12).cast::<usize>();
                                                 let len21 = l20;
@@ -1321,10 +1504,10 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes21)
                                             };
-                                            RpcError::Denied(e28)
+                                            RpcError::Denied(e31)
                                         }
                                         2 => {
-                                            let e28 = {
+                                            let e31 = {
                                                 let l22 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l23 = *ptr13.add(12).cast::<usize>();
                                                 let len24 = l23;
@@ -1336,11 +1519,10 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes24)
                                             };
-                                            RpcError::NotFound(e28)
+                                            RpcError::NotFound(e31)
                                         }
-                                        n => {
-                                            debug_assert_eq!(n, 3, "invalid enum discriminant");
-                                            let e28 = {
+                                        3 => {
+                                            let e31 = {
                                                 let l25 = *ptr13.add(8).cast::<*mut u8>();
                                                 let l26 = *ptr13.add(12).cast::<usize>();
                                                 let len27 = l26;
@@ -1352,11 +1534,27 @@ pub mod golem {
 
                                                 _rt::string_lift(bytes27)
                                             };
-                                            RpcError::RemoteInternalError(e28)
+                                            RpcError::RemoteInternalError(e31)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e31 = {
+                                                let l28 = *ptr13.add(8).cast::<*mut u8>();
+                                                let l29 = *ptr13.add(12).cast::<usize>();
+                                                let len30 = l29;
+                                                let bytes30 = _rt::Vec::from_raw_parts(
+                                                    l28.cast(),
+                                                    len30,
+                                                    len30,
+                                                );
+
+                                                _rt::string_lift(bytes30)
+                                            };
+                                            RpcError::Timeout(e31)
                                         }
                                     };
 
-                                    v28
+                                    v31
                                 };
                                 Err(e)
                             }
@@ -1662,152 +1860,5548 @@ pub mod golem {
                     }
                 }
             }
-            impl FutureInvokeResult {
-                #[allow(unused_unsafe, clippy::all)]
-                pub fn subscribe(&self) -> Pollable {
-                    unsafe {
-                        #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
-                        extern "C" {
-                            #[link_name = "[method]future-invoke-result.subscribe"]
-                            fn wit_import(_: i32) -> i32;
-                        }
-
-                        #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32) -> i32 {
-                            unreachable!()
-                        }
-                        let ret = wit_import((self).handle() as i32);
-                        super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32)
-                    }
-                }
-            }
-            impl FutureInvokeResult {
+            impl WasmRpc {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get(&self) -> Option<Result<WitValue, RpcError>> {
+                /// Like `invoke-and-await`, but fails with a `timeout` error if the invocation does not
+                /// finish within `timeout-nanos` nanoseconds
+                pub fn invoke_and_await_with_timeout(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                    timeout_nanos: u64,
+                ) -> Result<WitValue, RpcError> {
                     unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
                         #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 20]);
-                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 20]);
-                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
-                        extern "C" {
-                            #[link_name = "[method]future-invoke-result.get"]
-                            fn wit_import(_: i32, _: *mut u8);
-                        }
-
-                        #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: *mut u8) {
-                            unreachable!()
-                        }
-                        wit_import((self).handle() as i32, ptr0);
-                        let l1 = i32::from(*ptr0.add(0).cast::<u8>());
-                        match l1 {
-                            0 => None,
-                            1 => {
-                                let e = {
-                                    let l2 = i32::from(*ptr0.add(4).cast::<u8>());
-
-                                    match l2 {
-                                        0 => {
-                                            let e = {
-                                                let l3 = *ptr0.add(8).cast::<*mut u8>();
-                                                let l4 = *ptr0.add(12).cast::<usize>();
-                                                let base50 = l3;
-                                                let len50 = l4;
-                                                let mut result50 = _rt::Vec::with_capacity(len50);
-                                                for i in 0..len50 {
-                                                    let base = base50.add(i * 24);
-                                                    let e50 = {
-                                                        let l5 =
-                                                            i32::from(*base.add(0).cast::<u8>());
-                                                        let v49 = match l5 {
-                                                            0 => {
-                                                                let e49 = {
-                                                                    let l6 = *base
-                                                                        .add(8)
-                                                                        .cast::<*mut u8>();
-                                                                    let l7 = *base
-                                                                        .add(12)
-                                                                        .cast::<usize>();
-                                                                    let len8 = l7;
-
-                                                                    _rt::Vec::from_raw_parts(
-                                                                        l6.cast(),
-                                                                        len8,
-                                                                        len8,
-                                                                    )
-                                                                };
-                                                                WitNode::RecordValue(e49)
-                                                            }
-                                                            1 => {
-                                                                let e49 = {
-                                                                    let l9 =
-                                                                        *base.add(8).cast::<i32>();
-                                                                    let l10 = i32::from(
-                                                                        *base.add(12).cast::<u8>(),
-                                                                    );
-
-                                                                    (l9 as u32, match l10 {
-                                                                  0 => None,
-                                                                  1 => {
-                                                                    let e = {
-                                                                      let l11 = *base.add(16).cast::<i32>();
-
-                                                                      l11
-                                                                    };
-                                                                    Some(e)
-                                                                  }
-                                                                  _ => _rt::invalid_enum_discriminant(),
-                                                                })
-                                                                };
-                                                                WitNode::VariantValue(e49)
-                                                            }
-                                                            2 => {
-                                                                let e49 = {
-                                                                    let l12 =
-                                                                        *base.add(8).cast::<i32>();
-
-                                                                    l12 as u32
-                                                                };
-                                                                WitNode::EnumValue(e49)
-                                                            }
-                                                            3 => {
-                                                                let e49 = {
-                                                                    let l13 = *base
-                                                                        .add(8)
-                                                                        .cast::<*mut u8>();
-                                                                    let l14 = *base
-                                                                        .add(12)
-                                                                        .cast::<usize>();
-                                                                    let base16 = l13;
-                                                                    let len16 = l14;
-                                                                    let mut result16 =
-                                                                        _rt::Vec::with_capacity(
-                                                                            len16,
-                                                                        );
-                                                                    for i in 0..len16 {
-                                                                        let base =
-                                                                            base16.add(i * 1);
-                                                                        let e16 = {
-                                                                            let l15 = i32::from(
-                                                                                *base
-                                                                                    .add(0)
-                                                                                    .cast::<u8>(),
-                                                                            );
-
-                                                                            _rt::bool_lift(
-                                                                                l15 as u8,
-                                                                            )
-                                                                        };
-                                                                        result16.push(e16);
-                                                                    }
-                                                                    _rt::cabi_dealloc(
-                                                                        base16,
-                                                                        len16 * 1,
-                                                                        1,
-                                                                    );
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec12 = function_params;
+                        let len12 = vec12.len();
+                        let layout12 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec12.len() * 8, 4);
+                        let result12 = if layout12.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout12);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec12.into_iter().enumerate() {
+                            let base = result12.add(i * 8);
+                            {
+                                let WitValue { nodes: nodes1 } = e;
+                                let vec11 = nodes1;
+                                let len11 = vec11.len();
+                                let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec11.len() * 24,
+                                    8,
+                                );
+                                let result11 = if layout11.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout11);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec11.into_iter().enumerate() {
+                                    let base = result11.add(i * 24);
+                                    {
+                                        match e {
+                                            WitNode::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base.add(12).cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            WitNode::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 =
+                                                    _rt::alloc::Layout::from_size_align_unchecked(
+                                                        vec4.len() * 1,
+                                                        1,
+                                                    );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr =
+                                                        _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    {
+                                                        ::core::ptr::null_mut()
+                                                    }
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                }
+                                                *base.add(12).cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list
+                                                    .extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            WitNode::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base.add(12).cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            WitNode::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base.add(12).cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            WitNode::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            WitNode::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            WitNode::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            WitNode::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                })
+                                                    as u8;
+                                            }
+                                            WitNode::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base.add(12).cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            WitNode::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let Uri { value: value9 } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base.add(12).cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base.add(16).cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base.add(4).cast::<usize>() = len11;
+                                *base.add(0).cast::<*mut u8>() = result11;
+                                cleanup_list.extend_from_slice(&[(result11, layout11)]);
+                            }
+                        }
+                        let ptr13 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.invoke-and-await-with-timeout"]
+                            fn wit_import(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: i64,
+                                _: *mut u8,
+                            );
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i64,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            ptr0.cast_mut(),
+                            len0,
+                            result12,
+                            len12,
+                            _rt::as_i64(&timeout_nanos),
+                            ptr13,
+                        );
+                        let l14 = i32::from(*ptr13.add(0).cast::<u8>());
+                        if layout12.size() != 0 {
+                            _rt::alloc::dealloc(result12.cast(), layout12);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        match l14 {
+                            0 => {
+                                let e = {
+                                    let l15 = *ptr13.add(4).cast::<*mut u8>();
+                                    let l16 = *ptr13.add(8).cast::<usize>();
+                                    let base62 = l15;
+                                    let len62 = l16;
+                                    let mut result62 = _rt::Vec::with_capacity(len62);
+                                    for i in 0..len62 {
+                                        let base = base62.add(i * 24);
+                                        let e62 = {
+                                            let l17 = i32::from(*base.add(0).cast::<u8>());
+                                            let v61 = match l17 {
+                                                0 => {
+                                                    let e61 = {
+                                                        let l18 = *base.add(8).cast::<*mut u8>();
+                                                        let l19 = *base.add(12).cast::<usize>();
+                                                        let len20 = l19;
+
+                                                        _rt::Vec::from_raw_parts(
+                                                            l18.cast(),
+                                                            len20,
+                                                            len20,
+                                                        )
+                                                    };
+                                                    WitNode::RecordValue(e61)
+                                                }
+                                                1 => {
+                                                    let e61 = {
+                                                        let l21 = *base.add(8).cast::<i32>();
+                                                        let l22 =
+                                                            i32::from(*base.add(12).cast::<u8>());
+
+                                                        (
+                                                            l21 as u32,
+                                                            match l22 {
+                                                                0 => None,
+                                                                1 => {
+                                                                    let e = {
+                                                                        let l23 = *base
+                                                                            .add(16)
+                                                                            .cast::<i32>();
+
+                                                                        l23
+                                                                    };
+                                                                    Some(e)
+                                                                }
+                                                                _ => {
+                                                                    _rt::invalid_enum_discriminant()
+                                                                }
+                                                            },
+                                                        )
+                                                    };
+                                                    WitNode::VariantValue(e61)
+                                                }
+                                                2 => {
+                                                    let e61 = {
+                                                        let l24 = *base.add(8).cast::<i32>();
+
+                                                        l24 as u32
+                                                    };
+                                                    WitNode::EnumValue(e61)
+                                                }
+                                                3 => {
+                                                    let e61 = {
+                                                        let l25 = *base.add(8).cast::<*mut u8>();
+                                                        let l26 = *base.add(12).cast::<usize>();
+                                                        let base28 = l25;
+                                                        let len28 = l26;
+                                                        let mut result28 =
+                                                            _rt::Vec::with_capacity(len28);
+                                                        for i in 0..len28 {
+                                                            let base = base28.add(i * 1);
+                                                            let e28 = {
+                                                                let l27 = i32::from(
+                                                                    *base.add(0).cast::<u8>(),
+                                                                );
+
+                                                                _rt::bool_lift(l27 as u8)
+                                                            };
+                                                            result28.push(e28);
+                                                        }
+                                                        _rt::cabi_dealloc(base28, len28 * 1, 1);
+
+                                                        result28
+                                                    };
+                                                    WitNode::FlagsValue(e61)
+                                                }
+                                                4 => {
+                                                    let e61 = {
+                                                        let l29 = *base.add(8).cast::<*mut u8>();
+                                                        let l30 = *base.add(12).cast::<usize>();
+                                                        let len31 = l30;
+
+                                                        _rt::Vec::from_raw_parts(
+                                                            l29.cast(),
+                                                            len31,
+                                                            len31,
+                                                        )
+                                                    };
+                                                    WitNode::TupleValue(e61)
+                                                }
+                                                5 => {
+                                                    let e61 = {
+                                                        let l32 = *base.add(8).cast::<*mut u8>();
+                                                        let l33 = *base.add(12).cast::<usize>();
+                                                        let len34 = l33;
+
+                                                        _rt::Vec::from_raw_parts(
+                                                            l32.cast(),
+                                                            len34,
+                                                            len34,
+                                                        )
+                                                    };
+                                                    WitNode::ListValue(e61)
+                                                }
+                                                6 => {
+                                                    let e61 = {
+                                                        let l35 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        match l35 {
+                                                            0 => None,
+                                                            1 => {
+                                                                let e = {
+                                                                    let l36 =
+                                                                        *base.add(12).cast::<i32>();
+
+                                                                    l36
+                                                                };
+                                                                Some(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    WitNode::OptionValue(e61)
+                                                }
+                                                7 => {
+                                                    let e61 = {
+                                                        let l37 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        match l37 {
+                                                            0 => {
+                                                                let e = {
+                                                                    let l38 = i32::from(
+                                                                        *base.add(12).cast::<u8>(),
+                                                                    );
+
+                                                                    match l38 {
+                                                                            0 => None,
+                                                                            1 => {
+                                                                              let e = {
+                                                                                let l39 = *base.add(16).cast::<i32>();
+
+                                                                                l39
+                                                                              };
+                                                                              Some(e)
+                                                                            }
+                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                          }
+                                                                };
+                                                                Ok(e)
+                                                            }
+                                                            1 => {
+                                                                let e = {
+                                                                    let l40 = i32::from(
+                                                                        *base.add(12).cast::<u8>(),
+                                                                    );
+
+                                                                    match l40 {
+                                                                            0 => None,
+                                                                            1 => {
+                                                                              let e = {
+                                                                                let l41 = *base.add(16).cast::<i32>();
+
+                                                                                l41
+                                                                              };
+                                                                              Some(e)
+                                                                            }
+                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                          }
+                                                                };
+                                                                Err(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    WitNode::ResultValue(e61)
+                                                }
+                                                8 => {
+                                                    let e61 = {
+                                                        let l42 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        l42 as u8
+                                                    };
+                                                    WitNode::PrimU8(e61)
+                                                }
+                                                9 => {
+                                                    let e61 = {
+                                                        let l43 =
+                                                            i32::from(*base.add(8).cast::<u16>());
+
+                                                        l43 as u16
+                                                    };
+                                                    WitNode::PrimU16(e61)
+                                                }
+                                                10 => {
+                                                    let e61 = {
+                                                        let l44 = *base.add(8).cast::<i32>();
+
+                                                        l44 as u32
+                                                    };
+                                                    WitNode::PrimU32(e61)
+                                                }
+                                                11 => {
+                                                    let e61 = {
+                                                        let l45 = *base.add(8).cast::<i64>();
+
+                                                        l45 as u64
+                                                    };
+                                                    WitNode::PrimU64(e61)
+                                                }
+                                                12 => {
+                                                    let e61 = {
+                                                        let l46 =
+                                                            i32::from(*base.add(8).cast::<i8>());
+
+                                                        l46 as i8
+                                                    };
+                                                    WitNode::PrimS8(e61)
+                                                }
+                                                13 => {
+                                                    let e61 = {
+                                                        let l47 =
+                                                            i32::from(*base.add(8).cast::<i16>());
+
+                                                        l47 as i16
+                                                    };
+                                                    WitNode::PrimS16(e61)
+                                                }
+                                                14 => {
+                                                    let e61 = {
+                                                        let l48 = *base.add(8).cast::<i32>();
+
+                                                        l48
+                                                    };
+                                                    WitNode::PrimS32(e61)
+                                                }
+                                                15 => {
+                                                    let e61 = {
+                                                        let l49 = *base.add(8).cast::<i64>();
+
+                                                        l49
+                                                    };
+                                                    WitNode::PrimS64(e61)
+                                                }
+                                                16 => {
+                                                    let e61 = {
+                                                        let l50 = *base.add(8).cast::<f32>();
+
+                                                        l50
+                                                    };
+                                                    WitNode::PrimFloat32(e61)
+                                                }
+                                                17 => {
+                                                    let e61 = {
+                                                        let l51 = *base.add(8).cast::<f64>();
+
+                                                        l51
+                                                    };
+                                                    WitNode::PrimFloat64(e61)
+                                                }
+                                                18 => {
+                                                    let e61 = {
+                                                        let l52 = *base.add(8).cast::<i32>();
+
+                                                        _rt::char_lift(l52 as u32)
+                                                    };
+                                                    WitNode::PrimChar(e61)
+                                                }
+                                                19 => {
+                                                    let e61 = {
+                                                        let l53 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        _rt::bool_lift(l53 as u8)
+                                                    };
+                                                    WitNode::PrimBool(e61)
+                                                }
+                                                20 => {
+                                                    let e61 = {
+                                                        let l54 = *base.add(8).cast::<*mut u8>();
+                                                        let l55 = *base.add(12).cast::<usize>();
+                                                        let len56 = l55;
+                                                        let bytes56 = _rt::Vec::from_raw_parts(
+                                                            l54.cast(),
+                                                            len56,
+                                                            len56,
+                                                        );
+
+                                                        _rt::string_lift(bytes56)
+                                                    };
+                                                    WitNode::PrimString(e61)
+                                                }
+                                                n => {
+                                                    debug_assert_eq!(
+                                                        n, 21,
+                                                        "invalid enum discriminant"
+                                                    );
+                                                    let e61 = {
+                                                        let l57 = *base.add(8).cast::<*mut u8>();
+                                                        let l58 = *base.add(12).cast::<usize>();
+                                                        let len59 = l58;
+                                                        let bytes59 = _rt::Vec::from_raw_parts(
+                                                            l57.cast(),
+                                                            len59,
+                                                            len59,
+                                                        );
+                                                        let l60 = *base.add(16).cast::<i64>();
+
+                                                        (
+                                                            Uri {
+                                                                value: _rt::string_lift(bytes59),
+                                                            },
+                                                            l60 as u64,
+                                                        )
+                                                    };
+                                                    WitNode::Handle(e61)
+                                                }
+                                            };
+
+                                            v61
+                                        };
+                                        result62.push(e62);
+                                    }
+                                    _rt::cabi_dealloc(base62, len62 * 24, 8);
+
+                                    WitValue { nodes: result62 }
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l63 = i32::from(*ptr13.add(4).cast::<u8>());
+                                    let v79 = match l63 {
+                                        0 => {
+                                            let e79 = {
+                                                let l64 = *ptr13.add(8).cast::<*mut u8>();
+                                                let l65 = *ptr13.add(12).cast::<usize>();
+                                                let len66 = l65;
+                                                let bytes66 = _rt::Vec::from_raw_parts(
+                                                    l64.cast(),
+                                                    len66,
+                                                    len66,
+                                                );
+
+                                                _rt::string_lift(bytes66)
+                                            };
+                                            RpcError::ProtocolError(e79)
+                                        }
+                                        1 => {
+                                            let e79 = {
+                                                let l67 = *ptr13.add(8).cast::<*mut u8>();
+                                                let l68 = *ptr13.add(12).cast::<usize>();
+                                                let len69 = l68;
+                                                let bytes69 = _rt::Vec::from_raw_parts(
+                                                    l67.cast(),
+                                                    len69,
+                                                    len69,
+                                                );
+
+                                                _rt::string_lift(bytes69)
+                                            };
+                                            RpcError::Denied(e79)
+                                        }
+                                        2 => {
+                                            let e79 = {
+                                                let l70 = *ptr13.add(8).cast::<*mut u8>();
+                                                let l71 = *ptr13.add(12).cast::<usize>();
+                                                let len72 = l71;
+                                                let bytes72 = _rt::Vec::from_raw_parts(
+                                                    l70.cast(),
+                                                    len72,
+                                                    len72,
+                                                );
+
+                                                _rt::string_lift(bytes72)
+                                            };
+                                            RpcError::NotFound(e79)
+                                        }
+                                        3 => {
+                                            let e79 = {
+                                                let l73 = *ptr13.add(8).cast::<*mut u8>();
+                                                let l74 = *ptr13.add(12).cast::<usize>();
+                                                let len75 = l74;
+                                                let bytes75 = _rt::Vec::from_raw_parts(
+                                                    l73.cast(),
+                                                    len75,
+                                                    len75,
+                                                );
+
+                                                _rt::string_lift(bytes75)
+                                            };
+                                            RpcError::RemoteInternalError(e79)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e79 = {
+                                                let l76 = *ptr13.add(8).cast::<*mut u8>();
+                                                let l77 = *ptr13.add(12).cast::<usize>();
+                                                let len78 = l77;
+                                                let bytes78 = _rt::Vec::from_raw_parts(
+                                                    l76.cast(),
+                                                    len78,
+                                                    len78,
+                                                );
+
+                                                _rt::string_lift(bytes78)
+                                            };
+                                            RpcError::Timeout(e79)
+                                        }
+                                    };
+
+                                    v79
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Like `async-invoke-and-await`, but the result is a `timeout` error if the invocation does
+                /// not finish within `timeout-nanos` nanoseconds
+                pub fn async_invoke_and_await_with_timeout(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                    timeout_nanos: u64,
+                ) -> FutureInvokeResult {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec12 = function_params;
+                        let len12 = vec12.len();
+                        let layout12 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec12.len() * 8, 4);
+                        let result12 = if layout12.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout12);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec12.into_iter().enumerate() {
+                            let base = result12.add(i * 8);
+                            {
+                                let WitValue { nodes: nodes1 } = e;
+                                let vec11 = nodes1;
+                                let len11 = vec11.len();
+                                let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec11.len() * 24,
+                                    8,
+                                );
+                                let result11 = if layout11.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout11);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec11.into_iter().enumerate() {
+                                    let base = result11.add(i * 24);
+                                    {
+                                        match e {
+                                            WitNode::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base.add(12).cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            WitNode::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 =
+                                                    _rt::alloc::Layout::from_size_align_unchecked(
+                                                        vec4.len() * 1,
+                                                        1,
+                                                    );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr =
+                                                        _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    {
+                                                        ::core::ptr::null_mut()
+                                                    }
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                }
+                                                *base.add(12).cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list
+                                                    .extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            WitNode::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base.add(12).cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            WitNode::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base.add(12).cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            WitNode::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            WitNode::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            WitNode::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            WitNode::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                })
+                                                    as u8;
+                                            }
+                                            WitNode::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base.add(12).cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            WitNode::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let Uri { value: value9 } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base.add(12).cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base.add(16).cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base.add(4).cast::<usize>() = len11;
+                                *base.add(0).cast::<*mut u8>() = result11;
+                                cleanup_list.extend_from_slice(&[(result11, layout11)]);
+                            }
+                        }
+
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.async-invoke-and-await-with-timeout"]
+                            fn wit_import(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: i64,
+                            ) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i64,
+                        ) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(
+                            (self).handle() as i32,
+                            ptr0.cast_mut(),
+                            len0,
+                            result12,
+                            len12,
+                            _rt::as_i64(&timeout_nanos),
+                        );
+                        if layout12.size() != 0 {
+                            _rt::alloc::dealloc(result12.cast(), layout12);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        FutureInvokeResult::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn invoke_and_await_with_options(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                    options: &InvocationOptions,
+                ) -> Result<WitValue, RpcError> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[repr(align(8))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 104]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 104]);
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        *ptr0.add(0).cast::<i32>() = (self).handle() as i32;
+                        let vec1 = function_name;
+                        let ptr1 = vec1.as_ptr().cast::<u8>();
+                        let len1 = vec1.len();
+                        *ptr0.add(8).cast::<usize>() = len1;
+                        *ptr0.add(4).cast::<*mut u8>() = ptr1.cast_mut();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec13.len() * 8, 4);
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13.add(i * 8);
+                            {
+                                let WitValue { nodes: nodes2 } = e;
+                                let vec12 = nodes2;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len() * 24,
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12.add(i * 24);
+                                    {
+                                        match e {
+                                            WitNode::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec3 = e;
+                                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                                let len3 = vec3.len();
+                                                *base.add(12).cast::<usize>() = len3;
+                                                *base.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                                            }
+                                            WitNode::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t4_0, t4_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t4_0);
+                                                match t4_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec5 = e;
+                                                let len5 = vec5.len();
+                                                let layout5 =
+                                                    _rt::alloc::Layout::from_size_align_unchecked(
+                                                        vec5.len() * 1,
+                                                        1,
+                                                    );
+                                                let result5 = if layout5.size() != 0 {
+                                                    let ptr =
+                                                        _rt::alloc::alloc(layout5).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout5);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    {
+                                                        ::core::ptr::null_mut()
+                                                    }
+                                                };
+                                                for (i, e) in vec5.into_iter().enumerate() {
+                                                    let base = result5.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                }
+                                                *base.add(12).cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = result5;
+                                                cleanup_list
+                                                    .extend_from_slice(&[(result5, layout5)]);
+                                            }
+                                            WitNode::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base.add(12).cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            WitNode::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base.add(12).cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            WitNode::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            WitNode::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            WitNode::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            WitNode::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                })
+                                                    as u8;
+                                            }
+                                            WitNode::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec8 = e;
+                                                let ptr8 = vec8.as_ptr().cast::<u8>();
+                                                let len8 = vec8.len();
+                                                *base.add(12).cast::<usize>() = len8;
+                                                *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+                                            }
+                                            WitNode::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t9_0, t9_1) = e;
+                                                let Uri { value: value10 } = t9_0;
+                                                let vec11 = value10;
+                                                let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                let len11 = vec11.len();
+                                                *base.add(12).cast::<usize>() = len11;
+                                                *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                                *base.add(16).cast::<i64>() = _rt::as_i64(t9_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base.add(4).cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        *ptr0.add(16).cast::<usize>() = len13;
+                        *ptr0.add(12).cast::<*mut u8>() = result13;
+                        let InvocationOptions {
+                            idempotency_key: idempotency_key14,
+                            retry_policy: retry_policy14,
+                            headers: headers14,
+                            timeout_nanos: timeout_nanos14,
+                        } = options;
+                        match idempotency_key14 {
+                            Some(e) => {
+                                *ptr0.add(24).cast::<u8>() = (1i32) as u8;
+                                let vec15 = e;
+                                let ptr15 = vec15.as_ptr().cast::<u8>();
+                                let len15 = vec15.len();
+                                *ptr0.add(32).cast::<usize>() = len15;
+                                *ptr0.add(28).cast::<*mut u8>() = ptr15.cast_mut();
+                            }
+                            None => {
+                                *ptr0.add(24).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        match retry_policy14 {
+                            Some(e) => {
+                                *ptr0.add(40).cast::<u8>() = (1i32) as u8;
+                                let RetryPolicy {
+                                    max_attempts: max_attempts16,
+                                    min_delay_nanos: min_delay_nanos16,
+                                    max_delay_nanos: max_delay_nanos16,
+                                    multiplier: multiplier16,
+                                } = e;
+                                *ptr0.add(48).cast::<i32>() = _rt::as_i32(max_attempts16);
+                                *ptr0.add(56).cast::<i64>() = _rt::as_i64(min_delay_nanos16);
+                                *ptr0.add(64).cast::<i64>() = _rt::as_i64(max_delay_nanos16);
+                                *ptr0.add(72).cast::<f64>() = _rt::as_f64(multiplier16);
+                            }
+                            None => {
+                                *ptr0.add(40).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        let vec20 = headers14;
+                        let len20 = vec20.len();
+                        let layout20 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec20.len() * 16, 4);
+                        let result20 = if layout20.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout20).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout20);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec20.into_iter().enumerate() {
+                            let base = result20.add(i * 16);
+                            {
+                                let (t17_0, t17_1) = e;
+                                let vec18 = t17_0;
+                                let ptr18 = vec18.as_ptr().cast::<u8>();
+                                let len18 = vec18.len();
+                                *base.add(4).cast::<usize>() = len18;
+                                *base.add(0).cast::<*mut u8>() = ptr18.cast_mut();
+                                let vec19 = t17_1;
+                                let ptr19 = vec19.as_ptr().cast::<u8>();
+                                let len19 = vec19.len();
+                                *base.add(12).cast::<usize>() = len19;
+                                *base.add(8).cast::<*mut u8>() = ptr19.cast_mut();
+                            }
+                        }
+                        *ptr0.add(84).cast::<usize>() = len20;
+                        *ptr0.add(80).cast::<*mut u8>() = result20;
+                        match timeout_nanos14 {
+                            Some(e) => {
+                                *ptr0.add(88).cast::<u8>() = (1i32) as u8;
+                                *ptr0.add(96).cast::<i64>() = _rt::as_i64(e);
+                            }
+                            None => {
+                                *ptr0.add(88).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        let ptr21 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.invoke-and-await-with-options"]
+                            fn wit_import(_: *mut u8, _: *mut u8);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: *mut u8, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import(ptr0, ptr21);
+                        let l22 = i32::from(*ptr21.add(0).cast::<u8>());
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        if layout20.size() != 0 {
+                            _rt::alloc::dealloc(result20.cast(), layout20);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        match l22 {
+                            0 => {
+                                let e = {
+                                    let l23 = *ptr21.add(4).cast::<*mut u8>();
+                                    let l24 = *ptr21.add(8).cast::<usize>();
+                                    let base70 = l23;
+                                    let len70 = l24;
+                                    let mut result70 = _rt::Vec::with_capacity(len70);
+                                    for i in 0..len70 {
+                                        let base = base70.add(i * 24);
+                                        let e70 = {
+                                            let l25 = i32::from(*base.add(0).cast::<u8>());
+                                            let v69 = match l25 {
+                                                0 => {
+                                                    let e69 = {
+                                                        let l26 = *base.add(8).cast::<*mut u8>();
+                                                        let l27 = *base.add(12).cast::<usize>();
+                                                        let len28 = l27;
+
+                                                        _rt::Vec::from_raw_parts(
+                                                            l26.cast(),
+                                                            len28,
+                                                            len28,
+                                                        )
+                                                    };
+                                                    WitNode::RecordValue(e69)
+                                                }
+                                                1 => {
+                                                    let e69 = {
+                                                        let l29 = *base.add(8).cast::<i32>();
+                                                        let l30 =
+                                                            i32::from(*base.add(12).cast::<u8>());
+
+                                                        (
+                                                            l29 as u32,
+                                                            match l30 {
+                                                                0 => None,
+                                                                1 => {
+                                                                    let e = {
+                                                                        let l31 = *base
+                                                                            .add(16)
+                                                                            .cast::<i32>();
+
+                                                                        l31
+                                                                    };
+                                                                    Some(e)
+                                                                }
+                                                                _ => {
+                                                                    _rt::invalid_enum_discriminant()
+                                                                }
+                                                            },
+                                                        )
+                                                    };
+                                                    WitNode::VariantValue(e69)
+                                                }
+                                                2 => {
+                                                    let e69 = {
+                                                        let l32 = *base.add(8).cast::<i32>();
+
+                                                        l32 as u32
+                                                    };
+                                                    WitNode::EnumValue(e69)
+                                                }
+                                                3 => {
+                                                    let e69 = {
+                                                        let l33 = *base.add(8).cast::<*mut u8>();
+                                                        let l34 = *base.add(12).cast::<usize>();
+                                                        let base36 = l33;
+                                                        let len36 = l34;
+                                                        let mut result36 =
+                                                            _rt::Vec::with_capacity(len36);
+                                                        for i in 0..len36 {
+                                                            let base = base36.add(i * 1);
+                                                            let e36 = {
+                                                                let l35 = i32::from(
+                                                                    *base.add(0).cast::<u8>(),
+                                                                );
+
+                                                                _rt::bool_lift(l35 as u8)
+                                                            };
+                                                            result36.push(e36);
+                                                        }
+                                                        _rt::cabi_dealloc(base36, len36 * 1, 1);
+
+                                                        result36
+                                                    };
+                                                    WitNode::FlagsValue(e69)
+                                                }
+                                                4 => {
+                                                    let e69 = {
+                                                        let l37 = *base.add(8).cast::<*mut u8>();
+                                                        let l38 = *base.add(12).cast::<usize>();
+                                                        let len39 = l38;
+
+                                                        _rt::Vec::from_raw_parts(
+                                                            l37.cast(),
+                                                            len39,
+                                                            len39,
+                                                        )
+                                                    };
+                                                    WitNode::TupleValue(e69)
+                                                }
+                                                5 => {
+                                                    let e69 = {
+                                                        let l40 = *base.add(8).cast::<*mut u8>();
+                                                        let l41 = *base.add(12).cast::<usize>();
+                                                        let len42 = l41;
+
+                                                        _rt::Vec::from_raw_parts(
+                                                            l40.cast(),
+                                                            len42,
+                                                            len42,
+                                                        )
+                                                    };
+                                                    WitNode::ListValue(e69)
+                                                }
+                                                6 => {
+                                                    let e69 = {
+                                                        let l43 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        match l43 {
+                                                            0 => None,
+                                                            1 => {
+                                                                let e = {
+                                                                    let l44 =
+                                                                        *base.add(12).cast::<i32>();
+
+                                                                    l44
+                                                                };
+                                                                Some(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    WitNode::OptionValue(e69)
+                                                }
+                                                7 => {
+                                                    let e69 = {
+                                                        let l45 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        match l45 {
+                                                            0 => {
+                                                                let e = {
+                                                                    let l46 = i32::from(
+                                                                        *base.add(12).cast::<u8>(),
+                                                                    );
+
+                                                                    match l46 {
+                                                                                                0 => None,
+                                                                                                1 => {
+                                                                                                  let e = {
+                                                                                                    let l47 = *base.add(16).cast::<i32>();
+
+                                                                                                    l47
+                                                                                                  };
+                                                                                                  Some(e)
+                                                                                                }
+                                                                                                _ => _rt::invalid_enum_discriminant(),
+                                                                                              }
+                                                                };
+                                                                Ok(e)
+                                                            }
+                                                            1 => {
+                                                                let e = {
+                                                                    let l48 = i32::from(
+                                                                        *base.add(12).cast::<u8>(),
+                                                                    );
+
+                                                                    match l48 {
+                                                                                                0 => None,
+                                                                                                1 => {
+                                                                                                  let e = {
+                                                                                                    let l49 = *base.add(16).cast::<i32>();
+
+                                                                                                    l49
+                                                                                                  };
+                                                                                                  Some(e)
+                                                                                                }
+                                                                                                _ => _rt::invalid_enum_discriminant(),
+                                                                                              }
+                                                                };
+                                                                Err(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    WitNode::ResultValue(e69)
+                                                }
+                                                8 => {
+                                                    let e69 = {
+                                                        let l50 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        l50 as u8
+                                                    };
+                                                    WitNode::PrimU8(e69)
+                                                }
+                                                9 => {
+                                                    let e69 = {
+                                                        let l51 =
+                                                            i32::from(*base.add(8).cast::<u16>());
+
+                                                        l51 as u16
+                                                    };
+                                                    WitNode::PrimU16(e69)
+                                                }
+                                                10 => {
+                                                    let e69 = {
+                                                        let l52 = *base.add(8).cast::<i32>();
+
+                                                        l52 as u32
+                                                    };
+                                                    WitNode::PrimU32(e69)
+                                                }
+                                                11 => {
+                                                    let e69 = {
+                                                        let l53 = *base.add(8).cast::<i64>();
+
+                                                        l53 as u64
+                                                    };
+                                                    WitNode::PrimU64(e69)
+                                                }
+                                                12 => {
+                                                    let e69 = {
+                                                        let l54 =
+                                                            i32::from(*base.add(8).cast::<i8>());
+
+                                                        l54 as i8
+                                                    };
+                                                    WitNode::PrimS8(e69)
+                                                }
+                                                13 => {
+                                                    let e69 = {
+                                                        let l55 =
+                                                            i32::from(*base.add(8).cast::<i16>());
+
+                                                        l55 as i16
+                                                    };
+                                                    WitNode::PrimS16(e69)
+                                                }
+                                                14 => {
+                                                    let e69 = {
+                                                        let l56 = *base.add(8).cast::<i32>();
+
+                                                        l56
+                                                    };
+                                                    WitNode::PrimS32(e69)
+                                                }
+                                                15 => {
+                                                    let e69 = {
+                                                        let l57 = *base.add(8).cast::<i64>();
+
+                                                        l57
+                                                    };
+                                                    WitNode::PrimS64(e69)
+                                                }
+                                                16 => {
+                                                    let e69 = {
+                                                        let l58 = *base.add(8).cast::<f32>();
+
+                                                        l58
+                                                    };
+                                                    WitNode::PrimFloat32(e69)
+                                                }
+                                                17 => {
+                                                    let e69 = {
+                                                        let l59 = *base.add(8).cast::<f64>();
+
+                                                        l59
+                                                    };
+                                                    WitNode::PrimFloat64(e69)
+                                                }
+                                                18 => {
+                                                    let e69 = {
+                                                        let l60 = *base.add(8).cast::<i32>();
+
+                                                        _rt::char_lift(l60 as u32)
+                                                    };
+                                                    WitNode::PrimChar(e69)
+                                                }
+                                                19 => {
+                                                    let e69 = {
+                                                        let l61 =
+                                                            i32::from(*base.add(8).cast::<u8>());
+
+                                                        _rt::bool_lift(l61 as u8)
+                                                    };
+                                                    WitNode::PrimBool(e69)
+                                                }
+                                                20 => {
+                                                    let e69 = {
+                                                        let l62 = *base.add(8).cast::<*mut u8>();
+                                                        let l63 = *base.add(12).cast::<usize>();
+                                                        let len64 = l63;
+                                                        let bytes64 = _rt::Vec::from_raw_parts(
+                                                            l62.cast(),
+                                                            len64,
+                                                            len64,
+                                                        );
+
+                                                        _rt::string_lift(bytes64)
+                                                    };
+                                                    WitNode::PrimString(e69)
+                                                }
+                                                n => {
+                                                    debug_assert_eq!(
+                                                        n, 21,
+                                                        "invalid enum discriminant"
+                                                    );
+                                                    let e69 = {
+                                                        let l65 = *base.add(8).cast::<*mut u8>();
+                                                        let l66 = *base.add(12).cast::<usize>();
+                                                        let len67 = l66;
+                                                        let bytes67 = _rt::Vec::from_raw_parts(
+                                                            l65.cast(),
+                                                            len67,
+                                                            len67,
+                                                        );
+                                                        let l68 = *base.add(16).cast::<i64>();
+
+                                                        (
+                                                            Uri {
+                                                                value: _rt::string_lift(bytes67),
+                                                            },
+                                                            l68 as u64,
+                                                        )
+                                                    };
+                                                    WitNode::Handle(e69)
+                                                }
+                                            };
+
+                                            v69
+                                        };
+                                        result70.push(e70);
+                                    }
+                                    _rt::cabi_dealloc(base70, len70 * 24, 8);
+
+                                    WitValue { nodes: result70 }
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l71 = i32::from(*ptr21.add(4).cast::<u8>());
+                                    let v87 = match l71 {
+                                        0 => {
+                                            let e87 = {
+                                                let l72 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l73 = *ptr21.add(12).cast::<usize>();
+                                                let len74 = l73;
+                                                let bytes74 = _rt::Vec::from_raw_parts(
+                                                    l72.cast(),
+                                                    len74,
+                                                    len74,
+                                                );
+
+                                                _rt::string_lift(bytes74)
+                                            };
+                                            RpcError::ProtocolError(e87)
+                                        }
+                                        1 => {
+                                            let e87 = {
+                                                let l75 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l76 = *ptr21.add(12).cast::<usize>();
+                                                let len77 = l76;
+                                                let bytes77 = _rt::Vec::from_raw_parts(
+                                                    l75.cast(),
+                                                    len77,
+                                                    len77,
+                                                );
+
+                                                _rt::string_lift(bytes77)
+                                            };
+                                            RpcError::Denied(e87)
+                                        }
+                                        2 => {
+                                            let e87 = {
+                                                let l78 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l79 = *ptr21.add(12).cast::<usize>();
+                                                let len80 = l79;
+                                                let bytes80 = _rt::Vec::from_raw_parts(
+                                                    l78.cast(),
+                                                    len80,
+                                                    len80,
+                                                );
+
+                                                _rt::string_lift(bytes80)
+                                            };
+                                            RpcError::NotFound(e87)
+                                        }
+                                        3 => {
+                                            let e87 = {
+                                                let l81 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l82 = *ptr21.add(12).cast::<usize>();
+                                                let len83 = l82;
+                                                let bytes83 = _rt::Vec::from_raw_parts(
+                                                    l81.cast(),
+                                                    len83,
+                                                    len83,
+                                                );
+
+                                                _rt::string_lift(bytes83)
+                                            };
+                                            RpcError::RemoteInternalError(e87)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e87 = {
+                                                let l84 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l85 = *ptr21.add(12).cast::<usize>();
+                                                let len86 = l85;
+                                                let bytes86 = _rt::Vec::from_raw_parts(
+                                                    l84.cast(),
+                                                    len86,
+                                                    len86,
+                                                );
+
+                                                _rt::string_lift(bytes86)
+                                            };
+                                            RpcError::Timeout(e87)
+                                        }
+                                    };
+
+                                    v87
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Like `invoke-and-await-with-options`, ignoring the result of the invocation, which is
+                /// still awaited like in `invoke`
+                pub fn invoke_with_options(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                    options: &InvocationOptions,
+                ) -> Result<(), RpcError> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[repr(align(8))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 104]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 104]);
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        *ptr0.add(0).cast::<i32>() = (self).handle() as i32;
+                        let vec1 = function_name;
+                        let ptr1 = vec1.as_ptr().cast::<u8>();
+                        let len1 = vec1.len();
+                        *ptr0.add(8).cast::<usize>() = len1;
+                        *ptr0.add(4).cast::<*mut u8>() = ptr1.cast_mut();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec13.len() * 8, 4);
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13.add(i * 8);
+                            {
+                                let WitValue { nodes: nodes2 } = e;
+                                let vec12 = nodes2;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len() * 24,
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12.add(i * 24);
+                                    {
+                                        match e {
+                                            WitNode::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec3 = e;
+                                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                                let len3 = vec3.len();
+                                                *base.add(12).cast::<usize>() = len3;
+                                                *base.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                                            }
+                                            WitNode::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t4_0, t4_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t4_0);
+                                                match t4_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec5 = e;
+                                                let len5 = vec5.len();
+                                                let layout5 =
+                                                    _rt::alloc::Layout::from_size_align_unchecked(
+                                                        vec5.len() * 1,
+                                                        1,
+                                                    );
+                                                let result5 = if layout5.size() != 0 {
+                                                    let ptr =
+                                                        _rt::alloc::alloc(layout5).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout5);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    {
+                                                        ::core::ptr::null_mut()
+                                                    }
+                                                };
+                                                for (i, e) in vec5.into_iter().enumerate() {
+                                                    let base = result5.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                }
+                                                *base.add(12).cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = result5;
+                                                cleanup_list
+                                                    .extend_from_slice(&[(result5, layout5)]);
+                                            }
+                                            WitNode::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base.add(12).cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            WitNode::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base.add(12).cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            WitNode::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            WitNode::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            WitNode::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            WitNode::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                })
+                                                    as u8;
+                                            }
+                                            WitNode::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec8 = e;
+                                                let ptr8 = vec8.as_ptr().cast::<u8>();
+                                                let len8 = vec8.len();
+                                                *base.add(12).cast::<usize>() = len8;
+                                                *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+                                            }
+                                            WitNode::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t9_0, t9_1) = e;
+                                                let Uri { value: value10 } = t9_0;
+                                                let vec11 = value10;
+                                                let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                let len11 = vec11.len();
+                                                *base.add(12).cast::<usize>() = len11;
+                                                *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                                *base.add(16).cast::<i64>() = _rt::as_i64(t9_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base.add(4).cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        *ptr0.add(16).cast::<usize>() = len13;
+                        *ptr0.add(12).cast::<*mut u8>() = result13;
+                        let InvocationOptions {
+                            idempotency_key: idempotency_key14,
+                            retry_policy: retry_policy14,
+                            headers: headers14,
+                            timeout_nanos: timeout_nanos14,
+                        } = options;
+                        match idempotency_key14 {
+                            Some(e) => {
+                                *ptr0.add(24).cast::<u8>() = (1i32) as u8;
+                                let vec15 = e;
+                                let ptr15 = vec15.as_ptr().cast::<u8>();
+                                let len15 = vec15.len();
+                                *ptr0.add(32).cast::<usize>() = len15;
+                                *ptr0.add(28).cast::<*mut u8>() = ptr15.cast_mut();
+                            }
+                            None => {
+                                *ptr0.add(24).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        match retry_policy14 {
+                            Some(e) => {
+                                *ptr0.add(40).cast::<u8>() = (1i32) as u8;
+                                let RetryPolicy {
+                                    max_attempts: max_attempts16,
+                                    min_delay_nanos: min_delay_nanos16,
+                                    max_delay_nanos: max_delay_nanos16,
+                                    multiplier: multiplier16,
+                                } = e;
+                                *ptr0.add(48).cast::<i32>() = _rt::as_i32(max_attempts16);
+                                *ptr0.add(56).cast::<i64>() = _rt::as_i64(min_delay_nanos16);
+                                *ptr0.add(64).cast::<i64>() = _rt::as_i64(max_delay_nanos16);
+                                *ptr0.add(72).cast::<f64>() = _rt::as_f64(multiplier16);
+                            }
+                            None => {
+                                *ptr0.add(40).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        let vec20 = headers14;
+                        let len20 = vec20.len();
+                        let layout20 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec20.len() * 16, 4);
+                        let result20 = if layout20.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout20).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout20);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec20.into_iter().enumerate() {
+                            let base = result20.add(i * 16);
+                            {
+                                let (t17_0, t17_1) = e;
+                                let vec18 = t17_0;
+                                let ptr18 = vec18.as_ptr().cast::<u8>();
+                                let len18 = vec18.len();
+                                *base.add(4).cast::<usize>() = len18;
+                                *base.add(0).cast::<*mut u8>() = ptr18.cast_mut();
+                                let vec19 = t17_1;
+                                let ptr19 = vec19.as_ptr().cast::<u8>();
+                                let len19 = vec19.len();
+                                *base.add(12).cast::<usize>() = len19;
+                                *base.add(8).cast::<*mut u8>() = ptr19.cast_mut();
+                            }
+                        }
+                        *ptr0.add(84).cast::<usize>() = len20;
+                        *ptr0.add(80).cast::<*mut u8>() = result20;
+                        match timeout_nanos14 {
+                            Some(e) => {
+                                *ptr0.add(88).cast::<u8>() = (1i32) as u8;
+                                *ptr0.add(96).cast::<i64>() = _rt::as_i64(e);
+                            }
+                            None => {
+                                *ptr0.add(88).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        let ptr21 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.invoke-with-options"]
+                            fn wit_import(_: *mut u8, _: *mut u8);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: *mut u8, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import(ptr0, ptr21);
+                        let l22 = i32::from(*ptr21.add(0).cast::<u8>());
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        if layout20.size() != 0 {
+                            _rt::alloc::dealloc(result20.cast(), layout20);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        match l22 {
+                            0 => {
+                                let e = ();
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l23 = i32::from(*ptr21.add(4).cast::<u8>());
+                                    let v39 = match l23 {
+                                        0 => {
+                                            let e39 = {
+                                                let l24 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l25 = *ptr21.add(12).cast::<usize>();
+                                                let len26 = l25;
+                                                let bytes26 = _rt::Vec::from_raw_parts(
+                                                    l24.cast(),
+                                                    len26,
+                                                    len26,
+                                                );
+
+                                                _rt::string_lift(bytes26)
+                                            };
+                                            RpcError::ProtocolError(e39)
+                                        }
+                                        1 => {
+                                            let e39 = {
+                                                let l27 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l28 = *ptr21.add(12).cast::<usize>();
+                                                let len29 = l28;
+                                                let bytes29 = _rt::Vec::from_raw_parts(
+                                                    l27.cast(),
+                                                    len29,
+                                                    len29,
+                                                );
+
+                                                _rt::string_lift(bytes29)
+                                            };
+                                            RpcError::Denied(e39)
+                                        }
+                                        2 => {
+                                            let e39 = {
+                                                let l30 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l31 = *ptr21.add(12).cast::<usize>();
+                                                let len32 = l31;
+                                                let bytes32 = _rt::Vec::from_raw_parts(
+                                                    l30.cast(),
+                                                    len32,
+                                                    len32,
+                                                );
+
+                                                _rt::string_lift(bytes32)
+                                            };
+                                            RpcError::NotFound(e39)
+                                        }
+                                        3 => {
+                                            let e39 = {
+                                                let l33 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l34 = *ptr21.add(12).cast::<usize>();
+                                                let len35 = l34;
+                                                let bytes35 = _rt::Vec::from_raw_parts(
+                                                    l33.cast(),
+                                                    len35,
+                                                    len35,
+                                                );
+
+                                                _rt::string_lift(bytes35)
+                                            };
+                                            RpcError::RemoteInternalError(e39)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e39 = {
+                                                let l36 = *ptr21.add(8).cast::<*mut u8>();
+                                                let l37 = *ptr21.add(12).cast::<usize>();
+                                                let len38 = l37;
+                                                let bytes38 = _rt::Vec::from_raw_parts(
+                                                    l36.cast(),
+                                                    len38,
+                                                    len38,
+                                                );
+
+                                                _rt::string_lift(bytes38)
+                                            };
+                                            RpcError::Timeout(e39)
+                                        }
+                                    };
+
+                                    v39
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn async_invoke_and_await_with_options(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                    options: &InvocationOptions,
+                ) -> FutureInvokeResult {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[repr(align(8))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 104]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 104]);
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        *ptr0.add(0).cast::<i32>() = (self).handle() as i32;
+                        let vec1 = function_name;
+                        let ptr1 = vec1.as_ptr().cast::<u8>();
+                        let len1 = vec1.len();
+                        *ptr0.add(8).cast::<usize>() = len1;
+                        *ptr0.add(4).cast::<*mut u8>() = ptr1.cast_mut();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec13.len() * 8, 4);
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13.add(i * 8);
+                            {
+                                let WitValue { nodes: nodes2 } = e;
+                                let vec12 = nodes2;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len() * 24,
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12.add(i * 24);
+                                    {
+                                        match e {
+                                            WitNode::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec3 = e;
+                                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                                let len3 = vec3.len();
+                                                *base.add(12).cast::<usize>() = len3;
+                                                *base.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                                            }
+                                            WitNode::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t4_0, t4_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t4_0);
+                                                match t4_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec5 = e;
+                                                let len5 = vec5.len();
+                                                let layout5 =
+                                                    _rt::alloc::Layout::from_size_align_unchecked(
+                                                        vec5.len() * 1,
+                                                        1,
+                                                    );
+                                                let result5 = if layout5.size() != 0 {
+                                                    let ptr =
+                                                        _rt::alloc::alloc(layout5).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout5);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    {
+                                                        ::core::ptr::null_mut()
+                                                    }
+                                                };
+                                                for (i, e) in vec5.into_iter().enumerate() {
+                                                    let base = result5.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                }
+                                                *base.add(12).cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = result5;
+                                                cleanup_list
+                                                    .extend_from_slice(&[(result5, layout5)]);
+                                            }
+                                            WitNode::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base.add(12).cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            WitNode::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base.add(12).cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            WitNode::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            WitNode::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            WitNode::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            WitNode::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                })
+                                                    as u8;
+                                            }
+                                            WitNode::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec8 = e;
+                                                let ptr8 = vec8.as_ptr().cast::<u8>();
+                                                let len8 = vec8.len();
+                                                *base.add(12).cast::<usize>() = len8;
+                                                *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+                                            }
+                                            WitNode::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t9_0, t9_1) = e;
+                                                let Uri { value: value10 } = t9_0;
+                                                let vec11 = value10;
+                                                let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                let len11 = vec11.len();
+                                                *base.add(12).cast::<usize>() = len11;
+                                                *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                                *base.add(16).cast::<i64>() = _rt::as_i64(t9_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base.add(4).cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        *ptr0.add(16).cast::<usize>() = len13;
+                        *ptr0.add(12).cast::<*mut u8>() = result13;
+                        let InvocationOptions {
+                            idempotency_key: idempotency_key14,
+                            retry_policy: retry_policy14,
+                            headers: headers14,
+                            timeout_nanos: timeout_nanos14,
+                        } = options;
+                        match idempotency_key14 {
+                            Some(e) => {
+                                *ptr0.add(24).cast::<u8>() = (1i32) as u8;
+                                let vec15 = e;
+                                let ptr15 = vec15.as_ptr().cast::<u8>();
+                                let len15 = vec15.len();
+                                *ptr0.add(32).cast::<usize>() = len15;
+                                *ptr0.add(28).cast::<*mut u8>() = ptr15.cast_mut();
+                            }
+                            None => {
+                                *ptr0.add(24).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        match retry_policy14 {
+                            Some(e) => {
+                                *ptr0.add(40).cast::<u8>() = (1i32) as u8;
+                                let RetryPolicy {
+                                    max_attempts: max_attempts16,
+                                    min_delay_nanos: min_delay_nanos16,
+                                    max_delay_nanos: max_delay_nanos16,
+                                    multiplier: multiplier16,
+                                } = e;
+                                *ptr0.add(48).cast::<i32>() = _rt::as_i32(max_attempts16);
+                                *ptr0.add(56).cast::<i64>() = _rt::as_i64(min_delay_nanos16);
+                                *ptr0.add(64).cast::<i64>() = _rt::as_i64(max_delay_nanos16);
+                                *ptr0.add(72).cast::<f64>() = _rt::as_f64(multiplier16);
+                            }
+                            None => {
+                                *ptr0.add(40).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        let vec20 = headers14;
+                        let len20 = vec20.len();
+                        let layout20 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec20.len() * 16, 4);
+                        let result20 = if layout20.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout20).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout20);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec20.into_iter().enumerate() {
+                            let base = result20.add(i * 16);
+                            {
+                                let (t17_0, t17_1) = e;
+                                let vec18 = t17_0;
+                                let ptr18 = vec18.as_ptr().cast::<u8>();
+                                let len18 = vec18.len();
+                                *base.add(4).cast::<usize>() = len18;
+                                *base.add(0).cast::<*mut u8>() = ptr18.cast_mut();
+                                let vec19 = t17_1;
+                                let ptr19 = vec19.as_ptr().cast::<u8>();
+                                let len19 = vec19.len();
+                                *base.add(12).cast::<usize>() = len19;
+                                *base.add(8).cast::<*mut u8>() = ptr19.cast_mut();
+                            }
+                        }
+                        *ptr0.add(84).cast::<usize>() = len20;
+                        *ptr0.add(80).cast::<*mut u8>() = result20;
+                        match timeout_nanos14 {
+                            Some(e) => {
+                                *ptr0.add(88).cast::<u8>() = (1i32) as u8;
+                                *ptr0.add(96).cast::<i64>() = _rt::as_i64(e);
+                            }
+                            None => {
+                                *ptr0.add(88).cast::<u8>() = (0i32) as u8;
+                            }
+                        };
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.async-invoke-and-await-with-options"]
+                            fn wit_import(_: *mut u8) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: *mut u8) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(ptr0);
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        if layout20.size() != 0 {
+                            _rt::alloc::dealloc(result20.cast(), layout20);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        FutureInvokeResult::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Performs several invocations on the same worker, returning one result per invocation
+                pub fn invoke_batch(
+                    &self,
+                    invocations: &[BatchInvocation],
+                    options: BatchOptions,
+                ) -> _rt::Vec<Result<WitValue, RpcError>> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                        let vec14 = invocations;
+                        let len14 = vec14.len();
+                        let layout14 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec14.len() * 16, 4);
+                        let result14 = if layout14.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout14).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout14);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec14.into_iter().enumerate() {
+                            let base = result14.add(i * 16);
+                            {
+                                let BatchInvocation {
+                                    function_name: function_name0,
+                                    function_params: function_params0,
+                                } = e;
+                                let vec1 = function_name0;
+                                let ptr1 = vec1.as_ptr().cast::<u8>();
+                                let len1 = vec1.len();
+                                *base.add(4).cast::<usize>() = len1;
+                                *base.add(0).cast::<*mut u8>() = ptr1.cast_mut();
+                                let vec13 = function_params0;
+                                let len13 = vec13.len();
+                                let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec13.len() * 8,
+                                    4,
+                                );
+                                let result13 = if layout13.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout13);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec13.into_iter().enumerate() {
+                                    let base = result13.add(i * 8);
+                                    {
+                                        let WitValue { nodes: nodes2 } = e;
+                                        let vec12 = nodes2;
+                                        let len12 = vec12.len();
+                                        let layout12 =
+                                            _rt::alloc::Layout::from_size_align_unchecked(
+                                                vec12.len() * 24,
+                                                8,
+                                            );
+                                        let result12 = if layout12.size() != 0 {
+                                            let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                            if ptr.is_null() {
+                                                _rt::alloc::handle_alloc_error(layout12);
+                                            }
+                                            ptr
+                                        } else {
+                                            {
+                                                ::core::ptr::null_mut()
+                                            }
+                                        };
+                                        for (i, e) in vec12.into_iter().enumerate() {
+                                            let base = result12.add(i * 24);
+                                            {
+                                                match e {
+                                                    WitNode::RecordValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                        let vec3 = e;
+                                                        let ptr3 = vec3.as_ptr().cast::<u8>();
+                                                        let len3 = vec3.len();
+                                                        *base.add(12).cast::<usize>() = len3;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr3.cast_mut();
+                                                    }
+                                                    WitNode::VariantValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                        let (t4_0, t4_1) = e;
+                                                        *base.add(8).cast::<i32>() =
+                                                            _rt::as_i32(t4_0);
+                                                        match t4_1 {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    WitNode::EnumValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::FlagsValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                        let vec5 = e;
+                                                        let len5 = vec5.len();
+                                                        let layout5 = _rt::alloc::Layout::from_size_align_unchecked(vec5.len() * 1, 1);
+                                                        let result5 = if layout5.size() != 0 {
+                                                            let ptr = _rt::alloc::alloc(layout5)
+                                                                .cast::<u8>();
+                                                            if ptr.is_null() {
+                                                                _rt::alloc::handle_alloc_error(
+                                                                    layout5,
+                                                                );
+                                                            }
+                                                            ptr
+                                                        } else {
+                                                            {
+                                                                ::core::ptr::null_mut()
+                                                            }
+                                                        };
+                                                        for (i, e) in vec5.into_iter().enumerate() {
+                                                            let base = result5.add(i * 1);
+                                                            {
+                                                                *base.add(0).cast::<u8>() = (match e
+                                                                {
+                                                                    true => 1,
+                                                                    false => 0,
+                                                                })
+                                                                    as u8;
+                                                            }
+                                                        }
+                                                        *base.add(12).cast::<usize>() = len5;
+                                                        *base.add(8).cast::<*mut u8>() = result5;
+                                                        cleanup_list.extend_from_slice(&[(
+                                                            result5, layout5,
+                                                        )]);
+                                                    }
+                                                    WitNode::TupleValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                        let vec6 = e;
+                                                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                        let len6 = vec6.len();
+                                                        *base.add(12).cast::<usize>() = len6;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr6.cast_mut();
+                                                    }
+                                                    WitNode::ListValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                        let vec7 = e;
+                                                        let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                        let len7 = vec7.len();
+                                                        *base.add(12).cast::<usize>() = len7;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr7.cast_mut();
+                                                    }
+                                                    WitNode::OptionValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(12).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    WitNode::ResultValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                        match e {
+                                                            Ok(e) => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                                match e {
+                                                                    Some(e) => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (1i32) as u8;
+                                                                        *base
+                                                                            .add(16)
+                                                                            .cast::<i32>() =
+                                                                            _rt::as_i32(e);
+                                                                    }
+                                                                    None => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (0i32) as u8;
+                                                                    }
+                                                                };
+                                                            }
+                                                            Err(e) => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                match e {
+                                                                    Some(e) => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (1i32) as u8;
+                                                                        *base
+                                                                            .add(16)
+                                                                            .cast::<i32>() =
+                                                                            _rt::as_i32(e);
+                                                                    }
+                                                                    None => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (0i32) as u8;
+                                                                    }
+                                                                };
+                                                            }
+                                                        };
+                                                    }
+                                                    WitNode::PrimU8(e) => {
+                                                        *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                        *base.add(8).cast::<u8>() =
+                                                            (_rt::as_i32(e)) as u8;
+                                                    }
+                                                    WitNode::PrimU16(e) => {
+                                                        *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                        *base.add(8).cast::<u16>() =
+                                                            (_rt::as_i32(e)) as u16;
+                                                    }
+                                                    WitNode::PrimU32(e) => {
+                                                        *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::PrimU64(e) => {
+                                                        *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                        *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                                    }
+                                                    WitNode::PrimS8(e) => {
+                                                        *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                        *base.add(8).cast::<u8>() =
+                                                            (_rt::as_i32(e)) as u8;
+                                                    }
+                                                    WitNode::PrimS16(e) => {
+                                                        *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                        *base.add(8).cast::<u16>() =
+                                                            (_rt::as_i32(e)) as u16;
+                                                    }
+                                                    WitNode::PrimS32(e) => {
+                                                        *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::PrimS64(e) => {
+                                                        *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                        *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                                    }
+                                                    WitNode::PrimFloat32(e) => {
+                                                        *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                        *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                                    }
+                                                    WitNode::PrimFloat64(e) => {
+                                                        *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                        *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                                    }
+                                                    WitNode::PrimChar(e) => {
+                                                        *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::PrimBool(e) => {
+                                                        *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                        *base.add(8).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                    WitNode::PrimString(e) => {
+                                                        *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                        let vec8 = e;
+                                                        let ptr8 = vec8.as_ptr().cast::<u8>();
+                                                        let len8 = vec8.len();
+                                                        *base.add(12).cast::<usize>() = len8;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr8.cast_mut();
+                                                    }
+                                                    WitNode::Handle(e) => {
+                                                        *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                        let (t9_0, t9_1) = e;
+                                                        let Uri { value: value10 } = t9_0;
+                                                        let vec11 = value10;
+                                                        let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                        let len11 = vec11.len();
+                                                        *base.add(12).cast::<usize>() = len11;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr11.cast_mut();
+                                                        *base.add(16).cast::<i64>() =
+                                                            _rt::as_i64(t9_1);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        *base.add(4).cast::<usize>() = len12;
+                                        *base.add(0).cast::<*mut u8>() = result12;
+                                        cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                                    }
+                                }
+                                *base.add(12).cast::<usize>() = len13;
+                                *base.add(8).cast::<*mut u8>() = result13;
+                                cleanup_list.extend_from_slice(&[(result13, layout13)]);
+                            }
+                        }
+                        let BatchOptions {
+                            parallel: parallel15,
+                            stop_on_first_error: stop_on_first_error15,
+                        } = options;
+                        let ptr16 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.invoke-batch"]
+                            fn wit_import(_: i32, _: *mut u8, _: usize, _: i32, _: i32, _: *mut u8);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8, _: usize, _: i32, _: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            result14,
+                            len14,
+                            match parallel15 {
+                                true => 1,
+                                false => 0,
+                            },
+                            match stop_on_first_error15 {
+                                true => 1,
+                                false => 0,
+                            },
+                            ptr16,
+                        );
+                        let l17 = *ptr16.add(0).cast::<*mut u8>();
+                        let l18 = *ptr16.add(4).cast::<usize>();
+                        let base85 = l17;
+                        let len85 = l18;
+                        let mut result85 = _rt::Vec::with_capacity(len85);
+                        for i in 0..len85 {
+                            let base = base85.add(i * 16);
+                            let e85 = {
+                                let l19 = i32::from(*base.add(0).cast::<u8>());
+
+                                match l19 {
+                                    0 => {
+                                        let e = {
+                                            let l20 = *base.add(4).cast::<*mut u8>();
+                                            let l21 = *base.add(8).cast::<usize>();
+                                            let base67 = l20;
+                                            let len67 = l21;
+                                            let mut result67 = _rt::Vec::with_capacity(len67);
+                                            for i in 0..len67 {
+                                                let base = base67.add(i * 24);
+                                                let e67 = {
+                                                    let l22 = i32::from(*base.add(0).cast::<u8>());
+                                                    let v66 = match l22 {
+                                                        0 => {
+                                                            let e66 = {
+                                                                let l23 =
+                                                                    *base.add(8).cast::<*mut u8>();
+                                                                let l24 =
+                                                                    *base.add(12).cast::<usize>();
+                                                                let len25 = l24;
+
+                                                                _rt::Vec::from_raw_parts(
+                                                                    l23.cast(),
+                                                                    len25,
+                                                                    len25,
+                                                                )
+                                                            };
+                                                            WitNode::RecordValue(e66)
+                                                        }
+                                                        1 => {
+                                                            let e66 = {
+                                                                let l26 =
+                                                                    *base.add(8).cast::<i32>();
+                                                                let l27 = i32::from(
+                                                                    *base.add(12).cast::<u8>(),
+                                                                );
+
+                                                                (l26 as u32, match l27 {
+                                                                                                                            0 => None,
+                                                                                                                            1 => {
+                                                                                                                              let e = {
+                                                                                                                                let l28 = *base.add(16).cast::<i32>();
+
+                                                                                                                                l28
+                                                                                                                              };
+                                                                                                                              Some(e)
+                                                                                                                            }
+                                                                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                                                                          })
+                                                            };
+                                                            WitNode::VariantValue(e66)
+                                                        }
+                                                        2 => {
+                                                            let e66 = {
+                                                                let l29 =
+                                                                    *base.add(8).cast::<i32>();
+
+                                                                l29 as u32
+                                                            };
+                                                            WitNode::EnumValue(e66)
+                                                        }
+                                                        3 => {
+                                                            let e66 = {
+                                                                let l30 =
+                                                                    *base.add(8).cast::<*mut u8>();
+                                                                let l31 =
+                                                                    *base.add(12).cast::<usize>();
+                                                                let base33 = l30;
+                                                                let len33 = l31;
+                                                                let mut result33 =
+                                                                    _rt::Vec::with_capacity(len33);
+                                                                for i in 0..len33 {
+                                                                    let base = base33.add(i * 1);
+                                                                    let e33 = {
+                                                                        let l32 = i32::from(
+                                                                            *base
+                                                                                .add(0)
+                                                                                .cast::<u8>(),
+                                                                        );
+
+                                                                        _rt::bool_lift(l32 as u8)
+                                                                    };
+                                                                    result33.push(e33);
+                                                                }
+                                                                _rt::cabi_dealloc(
+                                                                    base33,
+                                                                    len33 * 1,
+                                                                    1,
+                                                                );
+
+                                                                result33
+                                                            };
+                                                            WitNode::FlagsValue(e66)
+                                                        }
+                                                        4 => {
+                                                            let e66 = {
+                                                                let l34 =
+                                                                    *base.add(8).cast::<*mut u8>();
+                                                                let l35 =
+                                                                    *base.add(12).cast::<usize>();
+                                                                let len36 = l35;
+
+                                                                _rt::Vec::from_raw_parts(
+                                                                    l34.cast(),
+                                                                    len36,
+                                                                    len36,
+                                                                )
+                                                            };
+                                                            WitNode::TupleValue(e66)
+                                                        }
+                                                        5 => {
+                                                            let e66 = {
+                                                                let l37 =
+                                                                    *base.add(8).cast::<*mut u8>();
+                                                                let l38 =
+                                                                    *base.add(12).cast::<usize>();
+                                                                let len39 = l38;
+
+                                                                _rt::Vec::from_raw_parts(
+                                                                    l37.cast(),
+                                                                    len39,
+                                                                    len39,
+                                                                )
+                                                            };
+                                                            WitNode::ListValue(e66)
+                                                        }
+                                                        6 => {
+                                                            let e66 = {
+                                                                let l40 = i32::from(
+                                                                    *base.add(8).cast::<u8>(),
+                                                                );
+
+                                                                match l40 {
+                                                                                                                            0 => None,
+                                                                                                                            1 => {
+                                                                                                                              let e = {
+                                                                                                                                let l41 = *base.add(12).cast::<i32>();
+
+                                                                                                                                l41
+                                                                                                                              };
+                                                                                                                              Some(e)
+                                                                                                                            }
+                                                                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                                                                          }
+                                                            };
+                                                            WitNode::OptionValue(e66)
+                                                        }
+                                                        7 => {
+                                                            let e66 = {
+                                                                let l42 = i32::from(
+                                                                    *base.add(8).cast::<u8>(),
+                                                                );
+
+                                                                match l42 {
+                                                                                                                            0 => {
+                                                                                                                              let e = {
+                                                                                                                                let l43 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                match l43 {
+                                                                                                                                  0 => None,
+                                                                                                                                  1 => {
+                                                                                                                                    let e = {
+                                                                                                                                      let l44 = *base.add(16).cast::<i32>();
+
+                                                                                                                                      l44
+                                                                                                                                    };
+                                                                                                                                    Some(e)
+                                                                                                                                  }
+                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                }
+                                                                                                                              };
+                                                                                                                              Ok(e)
+                                                                                                                            }
+                                                                                                                            1 => {
+                                                                                                                              let e = {
+                                                                                                                                let l45 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                match l45 {
+                                                                                                                                  0 => None,
+                                                                                                                                  1 => {
+                                                                                                                                    let e = {
+                                                                                                                                      let l46 = *base.add(16).cast::<i32>();
+
+                                                                                                                                      l46
+                                                                                                                                    };
+                                                                                                                                    Some(e)
+                                                                                                                                  }
+                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                }
+                                                                                                                              };
+                                                                                                                              Err(e)
+                                                                                                                            }
+                                                                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                                                                          }
+                                                            };
+                                                            WitNode::ResultValue(e66)
+                                                        }
+                                                        8 => {
+                                                            let e66 = {
+                                                                let l47 = i32::from(
+                                                                    *base.add(8).cast::<u8>(),
+                                                                );
+
+                                                                l47 as u8
+                                                            };
+                                                            WitNode::PrimU8(e66)
+                                                        }
+                                                        9 => {
+                                                            let e66 = {
+                                                                let l48 = i32::from(
+                                                                    *base.add(8).cast::<u16>(),
+                                                                );
+
+                                                                l48 as u16
+                                                            };
+                                                            WitNode::PrimU16(e66)
+                                                        }
+                                                        10 => {
+                                                            let e66 = {
+                                                                let l49 =
+                                                                    *base.add(8).cast::<i32>();
+
+                                                                l49 as u32
+                                                            };
+                                                            WitNode::PrimU32(e66)
+                                                        }
+                                                        11 => {
+                                                            let e66 = {
+                                                                let l50 =
+                                                                    *base.add(8).cast::<i64>();
+
+                                                                l50 as u64
+                                                            };
+                                                            WitNode::PrimU64(e66)
+                                                        }
+                                                        12 => {
+                                                            let e66 = {
+                                                                let l51 = i32::from(
+                                                                    *base.add(8).cast::<i8>(),
+                                                                );
+
+                                                                l51 as i8
+                                                            };
+                                                            WitNode::PrimS8(e66)
+                                                        }
+                                                        13 => {
+                                                            let e66 = {
+                                                                let l52 = i32::from(
+                                                                    *base.add(8).cast::<i16>(),
+                                                                );
+
+                                                                l52 as i16
+                                                            };
+                                                            WitNode::PrimS16(e66)
+                                                        }
+                                                        14 => {
+                                                            let e66 = {
+                                                                let l53 =
+                                                                    *base.add(8).cast::<i32>();
+
+                                                                l53
+                                                            };
+                                                            WitNode::PrimS32(e66)
+                                                        }
+                                                        15 => {
+                                                            let e66 = {
+                                                                let l54 =
+                                                                    *base.add(8).cast::<i64>();
+
+                                                                l54
+                                                            };
+                                                            WitNode::PrimS64(e66)
+                                                        }
+                                                        16 => {
+                                                            let e66 = {
+                                                                let l55 =
+                                                                    *base.add(8).cast::<f32>();
+
+                                                                l55
+                                                            };
+                                                            WitNode::PrimFloat32(e66)
+                                                        }
+                                                        17 => {
+                                                            let e66 = {
+                                                                let l56 =
+                                                                    *base.add(8).cast::<f64>();
+
+                                                                l56
+                                                            };
+                                                            WitNode::PrimFloat64(e66)
+                                                        }
+                                                        18 => {
+                                                            let e66 = {
+                                                                let l57 =
+                                                                    *base.add(8).cast::<i32>();
+
+                                                                _rt::char_lift(l57 as u32)
+                                                            };
+                                                            WitNode::PrimChar(e66)
+                                                        }
+                                                        19 => {
+                                                            let e66 = {
+                                                                let l58 = i32::from(
+                                                                    *base.add(8).cast::<u8>(),
+                                                                );
+
+                                                                _rt::bool_lift(l58 as u8)
+                                                            };
+                                                            WitNode::PrimBool(e66)
+                                                        }
+                                                        20 => {
+                                                            let e66 = {
+                                                                let l59 =
+                                                                    *base.add(8).cast::<*mut u8>();
+                                                                let l60 =
+                                                                    *base.add(12).cast::<usize>();
+                                                                let len61 = l60;
+                                                                let bytes61 =
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l59.cast(),
+                                                                        len61,
+                                                                        len61,
+                                                                    );
+
+                                                                _rt::string_lift(bytes61)
+                                                            };
+                                                            WitNode::PrimString(e66)
+                                                        }
+                                                        n => {
+                                                            debug_assert_eq!(
+                                                                n, 21,
+                                                                "invalid enum discriminant"
+                                                            );
+                                                            let e66 = {
+                                                                let l62 =
+                                                                    *base.add(8).cast::<*mut u8>();
+                                                                let l63 =
+                                                                    *base.add(12).cast::<usize>();
+                                                                let len64 = l63;
+                                                                let bytes64 =
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l62.cast(),
+                                                                        len64,
+                                                                        len64,
+                                                                    );
+                                                                let l65 =
+                                                                    *base.add(16).cast::<i64>();
+
+                                                                (
+                                                                    Uri {
+                                                                        value: _rt::string_lift(
+                                                                            bytes64,
+                                                                        ),
+                                                                    },
+                                                                    l65 as u64,
+                                                                )
+                                                            };
+                                                            WitNode::Handle(e66)
+                                                        }
+                                                    };
+
+                                                    v66
+                                                };
+                                                result67.push(e67);
+                                            }
+                                            _rt::cabi_dealloc(base67, len67 * 24, 8);
+
+                                            WitValue { nodes: result67 }
+                                        };
+                                        Ok(e)
+                                    }
+                                    1 => {
+                                        let e = {
+                                            let l68 = i32::from(*base.add(4).cast::<u8>());
+                                            let v84 = match l68 {
+                                                0 => {
+                                                    let e84 = {
+                                                        let l69 = *base.add(8).cast::<*mut u8>();
+                                                        let l70 = *base.add(12).cast::<usize>();
+                                                        let len71 = l70;
+                                                        let bytes71 = _rt::Vec::from_raw_parts(
+                                                            l69.cast(),
+                                                            len71,
+                                                            len71,
+                                                        );
+
+                                                        _rt::string_lift(bytes71)
+                                                    };
+                                                    RpcError::ProtocolError(e84)
+                                                }
+                                                1 => {
+                                                    let e84 = {
+                                                        let l72 = *base.add(8).cast::<*mut u8>();
+                                                        let l73 = *base.add(12).cast::<usize>();
+                                                        let len74 = l73;
+                                                        let bytes74 = _rt::Vec::from_raw_parts(
+                                                            l72.cast(),
+                                                            len74,
+                                                            len74,
+                                                        );
+
+                                                        _rt::string_lift(bytes74)
+                                                    };
+                                                    RpcError::Denied(e84)
+                                                }
+                                                2 => {
+                                                    let e84 = {
+                                                        let l75 = *base.add(8).cast::<*mut u8>();
+                                                        let l76 = *base.add(12).cast::<usize>();
+                                                        let len77 = l76;
+                                                        let bytes77 = _rt::Vec::from_raw_parts(
+                                                            l75.cast(),
+                                                            len77,
+                                                            len77,
+                                                        );
+
+                                                        _rt::string_lift(bytes77)
+                                                    };
+                                                    RpcError::NotFound(e84)
+                                                }
+                                                3 => {
+                                                    let e84 = {
+                                                        let l78 = *base.add(8).cast::<*mut u8>();
+                                                        let l79 = *base.add(12).cast::<usize>();
+                                                        let len80 = l79;
+                                                        let bytes80 = _rt::Vec::from_raw_parts(
+                                                            l78.cast(),
+                                                            len80,
+                                                            len80,
+                                                        );
+
+                                                        _rt::string_lift(bytes80)
+                                                    };
+                                                    RpcError::RemoteInternalError(e84)
+                                                }
+                                                n => {
+                                                    debug_assert_eq!(
+                                                        n, 4,
+                                                        "invalid enum discriminant"
+                                                    );
+                                                    let e84 = {
+                                                        let l81 = *base.add(8).cast::<*mut u8>();
+                                                        let l82 = *base.add(12).cast::<usize>();
+                                                        let len83 = l82;
+                                                        let bytes83 = _rt::Vec::from_raw_parts(
+                                                            l81.cast(),
+                                                            len83,
+                                                            len83,
+                                                        );
+
+                                                        _rt::string_lift(bytes83)
+                                                    };
+                                                    RpcError::Timeout(e84)
+                                                }
+                                            };
+
+                                            v84
+                                        };
+                                        Err(e)
+                                    }
+                                    _ => _rt::invalid_enum_discriminant(),
+                                }
+                            };
+                            result85.push(e85);
+                        }
+                        _rt::cabi_dealloc(base85, len85 * 16, 4);
+                        if layout14.size() != 0 {
+                            _rt::alloc::dealloc(result14.cast(), layout14);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        result85
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn async_invoke_batch(
+                    &self,
+                    invocations: &[BatchInvocation],
+                    options: BatchOptions,
+                ) -> FutureBatchInvokeResult {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        let vec14 = invocations;
+                        let len14 = vec14.len();
+                        let layout14 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec14.len() * 16, 4);
+                        let result14 = if layout14.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout14).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout14);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec14.into_iter().enumerate() {
+                            let base = result14.add(i * 16);
+                            {
+                                let BatchInvocation {
+                                    function_name: function_name0,
+                                    function_params: function_params0,
+                                } = e;
+                                let vec1 = function_name0;
+                                let ptr1 = vec1.as_ptr().cast::<u8>();
+                                let len1 = vec1.len();
+                                *base.add(4).cast::<usize>() = len1;
+                                *base.add(0).cast::<*mut u8>() = ptr1.cast_mut();
+                                let vec13 = function_params0;
+                                let len13 = vec13.len();
+                                let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec13.len() * 8,
+                                    4,
+                                );
+                                let result13 = if layout13.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout13);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec13.into_iter().enumerate() {
+                                    let base = result13.add(i * 8);
+                                    {
+                                        let WitValue { nodes: nodes2 } = e;
+                                        let vec12 = nodes2;
+                                        let len12 = vec12.len();
+                                        let layout12 =
+                                            _rt::alloc::Layout::from_size_align_unchecked(
+                                                vec12.len() * 24,
+                                                8,
+                                            );
+                                        let result12 = if layout12.size() != 0 {
+                                            let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                            if ptr.is_null() {
+                                                _rt::alloc::handle_alloc_error(layout12);
+                                            }
+                                            ptr
+                                        } else {
+                                            {
+                                                ::core::ptr::null_mut()
+                                            }
+                                        };
+                                        for (i, e) in vec12.into_iter().enumerate() {
+                                            let base = result12.add(i * 24);
+                                            {
+                                                match e {
+                                                    WitNode::RecordValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                        let vec3 = e;
+                                                        let ptr3 = vec3.as_ptr().cast::<u8>();
+                                                        let len3 = vec3.len();
+                                                        *base.add(12).cast::<usize>() = len3;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr3.cast_mut();
+                                                    }
+                                                    WitNode::VariantValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                        let (t4_0, t4_1) = e;
+                                                        *base.add(8).cast::<i32>() =
+                                                            _rt::as_i32(t4_0);
+                                                        match t4_1 {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    WitNode::EnumValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::FlagsValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                        let vec5 = e;
+                                                        let len5 = vec5.len();
+                                                        let layout5 = _rt::alloc::Layout::from_size_align_unchecked(vec5.len() * 1, 1);
+                                                        let result5 = if layout5.size() != 0 {
+                                                            let ptr = _rt::alloc::alloc(layout5)
+                                                                .cast::<u8>();
+                                                            if ptr.is_null() {
+                                                                _rt::alloc::handle_alloc_error(
+                                                                    layout5,
+                                                                );
+                                                            }
+                                                            ptr
+                                                        } else {
+                                                            {
+                                                                ::core::ptr::null_mut()
+                                                            }
+                                                        };
+                                                        for (i, e) in vec5.into_iter().enumerate() {
+                                                            let base = result5.add(i * 1);
+                                                            {
+                                                                *base.add(0).cast::<u8>() = (match e
+                                                                {
+                                                                    true => 1,
+                                                                    false => 0,
+                                                                })
+                                                                    as u8;
+                                                            }
+                                                        }
+                                                        *base.add(12).cast::<usize>() = len5;
+                                                        *base.add(8).cast::<*mut u8>() = result5;
+                                                        cleanup_list.extend_from_slice(&[(
+                                                            result5, layout5,
+                                                        )]);
+                                                    }
+                                                    WitNode::TupleValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                        let vec6 = e;
+                                                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                        let len6 = vec6.len();
+                                                        *base.add(12).cast::<usize>() = len6;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr6.cast_mut();
+                                                    }
+                                                    WitNode::ListValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                        let vec7 = e;
+                                                        let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                        let len7 = vec7.len();
+                                                        *base.add(12).cast::<usize>() = len7;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr7.cast_mut();
+                                                    }
+                                                    WitNode::OptionValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(12).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    WitNode::ResultValue(e) => {
+                                                        *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                        match e {
+                                                            Ok(e) => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                                match e {
+                                                                    Some(e) => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (1i32) as u8;
+                                                                        *base
+                                                                            .add(16)
+                                                                            .cast::<i32>() =
+                                                                            _rt::as_i32(e);
+                                                                    }
+                                                                    None => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (0i32) as u8;
+                                                                    }
+                                                                };
+                                                            }
+                                                            Err(e) => {
+                                                                *base.add(8).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                match e {
+                                                                    Some(e) => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (1i32) as u8;
+                                                                        *base
+                                                                            .add(16)
+                                                                            .cast::<i32>() =
+                                                                            _rt::as_i32(e);
+                                                                    }
+                                                                    None => {
+                                                                        *base
+                                                                            .add(12)
+                                                                            .cast::<u8>() =
+                                                                            (0i32) as u8;
+                                                                    }
+                                                                };
+                                                            }
+                                                        };
+                                                    }
+                                                    WitNode::PrimU8(e) => {
+                                                        *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                        *base.add(8).cast::<u8>() =
+                                                            (_rt::as_i32(e)) as u8;
+                                                    }
+                                                    WitNode::PrimU16(e) => {
+                                                        *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                        *base.add(8).cast::<u16>() =
+                                                            (_rt::as_i32(e)) as u16;
+                                                    }
+                                                    WitNode::PrimU32(e) => {
+                                                        *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::PrimU64(e) => {
+                                                        *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                        *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                                    }
+                                                    WitNode::PrimS8(e) => {
+                                                        *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                        *base.add(8).cast::<u8>() =
+                                                            (_rt::as_i32(e)) as u8;
+                                                    }
+                                                    WitNode::PrimS16(e) => {
+                                                        *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                        *base.add(8).cast::<u16>() =
+                                                            (_rt::as_i32(e)) as u16;
+                                                    }
+                                                    WitNode::PrimS32(e) => {
+                                                        *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::PrimS64(e) => {
+                                                        *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                        *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                                    }
+                                                    WitNode::PrimFloat32(e) => {
+                                                        *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                        *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                                    }
+                                                    WitNode::PrimFloat64(e) => {
+                                                        *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                        *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                                    }
+                                                    WitNode::PrimChar(e) => {
+                                                        *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                        *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    WitNode::PrimBool(e) => {
+                                                        *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                        *base.add(8).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                    WitNode::PrimString(e) => {
+                                                        *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                        let vec8 = e;
+                                                        let ptr8 = vec8.as_ptr().cast::<u8>();
+                                                        let len8 = vec8.len();
+                                                        *base.add(12).cast::<usize>() = len8;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr8.cast_mut();
+                                                    }
+                                                    WitNode::Handle(e) => {
+                                                        *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                        let (t9_0, t9_1) = e;
+                                                        let Uri { value: value10 } = t9_0;
+                                                        let vec11 = value10;
+                                                        let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                        let len11 = vec11.len();
+                                                        *base.add(12).cast::<usize>() = len11;
+                                                        *base.add(8).cast::<*mut u8>() =
+                                                            ptr11.cast_mut();
+                                                        *base.add(16).cast::<i64>() =
+                                                            _rt::as_i64(t9_1);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        *base.add(4).cast::<usize>() = len12;
+                                        *base.add(0).cast::<*mut u8>() = result12;
+                                        cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                                    }
+                                }
+                                *base.add(12).cast::<usize>() = len13;
+                                *base.add(8).cast::<*mut u8>() = result13;
+                                cleanup_list.extend_from_slice(&[(result13, layout13)]);
+                            }
+                        }
+                        let BatchOptions {
+                            parallel: parallel15,
+                            stop_on_first_error: stop_on_first_error15,
+                        } = options;
+
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.async-invoke-batch"]
+                            fn wit_import(_: i32, _: *mut u8, _: usize, _: i32, _: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8, _: usize, _: i32, _: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(
+                            (self).handle() as i32,
+                            result14,
+                            len14,
+                            match parallel15 {
+                                true => 1,
+                                false => 0,
+                            },
+                            match stop_on_first_error15 {
+                                true => 1,
+                                false => 0,
+                            },
+                        );
+                        if layout14.size() != 0 {
+                            _rt::alloc::dealloc(result14.cast(), layout14);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        FutureBatchInvokeResult::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Invokes a function producing its result in parts, such as the pages of a list
+                pub fn stream_invoke(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                ) -> InvokeResultStream {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec12 = function_params;
+                        let len12 = vec12.len();
+                        let layout12 =
+                            _rt::alloc::Layout::from_size_align_unchecked(vec12.len() * 8, 4);
+                        let result12 = if layout12.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout12);
+                            }
+                            ptr
+                        } else {
+                            {
+                                ::core::ptr::null_mut()
+                            }
+                        };
+                        for (i, e) in vec12.into_iter().enumerate() {
+                            let base = result12.add(i * 8);
+                            {
+                                let WitValue { nodes: nodes1 } = e;
+                                let vec11 = nodes1;
+                                let len11 = vec11.len();
+                                let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec11.len() * 24,
+                                    8,
+                                );
+                                let result11 = if layout11.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout11);
+                                    }
+                                    ptr
+                                } else {
+                                    {
+                                        ::core::ptr::null_mut()
+                                    }
+                                };
+                                for (i, e) in vec11.into_iter().enumerate() {
+                                    let base = result11.add(i * 24);
+                                    {
+                                        match e {
+                                            WitNode::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base.add(12).cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            WitNode::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 =
+                                                    _rt::alloc::Layout::from_size_align_unchecked(
+                                                        vec4.len() * 1,
+                                                        1,
+                                                    );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr =
+                                                        _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    {
+                                                        ::core::ptr::null_mut()
+                                                    }
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        })
+                                                            as u8;
+                                                    }
+                                                }
+                                                *base.add(12).cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list
+                                                    .extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            WitNode::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base.add(12).cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            WitNode::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base.add(12).cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            WitNode::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() =
+                                                            _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            WitNode::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() =
+                                                                    _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() =
+                                                                    (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            WitNode::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            WitNode::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() =
+                                                    (_rt::as_i32(e)) as u16;
+                                            }
+                                            WitNode::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            WitNode::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            WitNode::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            WitNode::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            WitNode::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                })
+                                                    as u8;
+                                            }
+                                            WitNode::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base.add(12).cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            WitNode::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let Uri { value: value9 } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base.add(12).cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base.add(16).cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base.add(4).cast::<usize>() = len11;
+                                *base.add(0).cast::<*mut u8>() = result11;
+                                cleanup_list.extend_from_slice(&[(result11, layout11)]);
+                            }
+                        }
+
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]wasm-rpc.stream-invoke"]
+                            fn wit_import(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                            ) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8, _: usize) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(
+                            (self).handle() as i32,
+                            ptr0.cast_mut(),
+                            len0,
+                            result12,
+                            len12,
+                        );
+                        if layout12.size() != 0 {
+                            _rt::alloc::dealloc(result12.cast(), layout12);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        InvokeResultStream::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl FutureInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn subscribe(&self) -> Pollable {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]future-invoke-result.subscribe"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl FutureInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn get(&self) -> Option<Result<WitValue, RpcError>> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 20]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 20]);
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]future-invoke-result.get"]
+                            fn wit_import(_: i32, _: *mut u8);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, ptr0);
+                        let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                        match l1 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l2 = i32::from(*ptr0.add(4).cast::<u8>());
+
+                                    match l2 {
+                                        0 => {
+                                            let e = {
+                                                let l3 = *ptr0.add(8).cast::<*mut u8>();
+                                                let l4 = *ptr0.add(12).cast::<usize>();
+                                                let base50 = l3;
+                                                let len50 = l4;
+                                                let mut result50 = _rt::Vec::with_capacity(len50);
+                                                for i in 0..len50 {
+                                                    let base = base50.add(i * 24);
+                                                    let e50 = {
+                                                        let l5 =
+                                                            i32::from(*base.add(0).cast::<u8>());
+                                                        let v49 = match l5 {
+                                                            0 => {
+                                                                let e49 = {
+                                                                    let l6 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l7 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len8 = l7;
+
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l6.cast(),
+                                                                        len8,
+                                                                        len8,
+                                                                    )
+                                                                };
+                                                                WitNode::RecordValue(e49)
+                                                            }
+                                                            1 => {
+                                                                let e49 = {
+                                                                    let l9 =
+                                                                        *base.add(8).cast::<i32>();
+                                                                    let l10 = i32::from(
+                                                                        *base.add(12).cast::<u8>(),
+                                                                    );
+
+                                                                    (l9 as u32, match l10 {
+                                                                                                                                                  0 => None,
+                                                                                                                                                  1 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l11 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                      l11
+                                                                                                                                                    };
+                                                                                                                                                    Some(e)
+                                                                                                                                                  }
+                                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                })
+                                                                };
+                                                                WitNode::VariantValue(e49)
+                                                            }
+                                                            2 => {
+                                                                let e49 = {
+                                                                    let l12 =
+                                                                        *base.add(8).cast::<i32>();
+
+                                                                    l12 as u32
+                                                                };
+                                                                WitNode::EnumValue(e49)
+                                                            }
+                                                            3 => {
+                                                                let e49 = {
+                                                                    let l13 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l14 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let base16 = l13;
+                                                                    let len16 = l14;
+                                                                    let mut result16 =
+                                                                        _rt::Vec::with_capacity(
+                                                                            len16,
+                                                                        );
+                                                                    for i in 0..len16 {
+                                                                        let base =
+                                                                            base16.add(i * 1);
+                                                                        let e16 = {
+                                                                            let l15 = i32::from(
+                                                                                *base
+                                                                                    .add(0)
+                                                                                    .cast::<u8>(),
+                                                                            );
+
+                                                                            _rt::bool_lift(
+                                                                                l15 as u8,
+                                                                            )
+                                                                        };
+                                                                        result16.push(e16);
+                                                                    }
+                                                                    _rt::cabi_dealloc(
+                                                                        base16,
+                                                                        len16 * 1,
+                                                                        1,
+                                                                    );
+
+                                                                    result16
+                                                                };
+                                                                WitNode::FlagsValue(e49)
+                                                            }
+                                                            4 => {
+                                                                let e49 = {
+                                                                    let l17 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l18 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len19 = l18;
+
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l17.cast(),
+                                                                        len19,
+                                                                        len19,
+                                                                    )
+                                                                };
+                                                                WitNode::TupleValue(e49)
+                                                            }
+                                                            5 => {
+                                                                let e49 = {
+                                                                    let l20 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l21 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len22 = l21;
+
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l20.cast(),
+                                                                        len22,
+                                                                        len22,
+                                                                    )
+                                                                };
+                                                                WitNode::ListValue(e49)
+                                                            }
+                                                            6 => {
+                                                                let e49 = {
+                                                                    let l23 = i32::from(
+                                                                        *base.add(8).cast::<u8>(),
+                                                                    );
+
+                                                                    match l23 {
+                                                                                                                                                  0 => None,
+                                                                                                                                                  1 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l24 = *base.add(12).cast::<i32>();
+
+                                                                                                                                                      l24
+                                                                                                                                                    };
+                                                                                                                                                    Some(e)
+                                                                                                                                                  }
+                                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                }
+                                                                };
+                                                                WitNode::OptionValue(e49)
+                                                            }
+                                                            7 => {
+                                                                let e49 = {
+                                                                    let l25 = i32::from(
+                                                                        *base.add(8).cast::<u8>(),
+                                                                    );
+
+                                                                    match l25 {
+                                                                                                                                                  0 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l26 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                                      match l26 {
+                                                                                                                                                        0 => None,
+                                                                                                                                                        1 => {
+                                                                                                                                                          let e = {
+                                                                                                                                                            let l27 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                            l27
+                                                                                                                                                          };
+                                                                                                                                                          Some(e)
+                                                                                                                                                        }
+                                                                                                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                      }
+                                                                                                                                                    };
+                                                                                                                                                    Ok(e)
+                                                                                                                                                  }
+                                                                                                                                                  1 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l28 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                                      match l28 {
+                                                                                                                                                        0 => None,
+                                                                                                                                                        1 => {
+                                                                                                                                                          let e = {
+                                                                                                                                                            let l29 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                            l29
+                                                                                                                                                          };
+                                                                                                                                                          Some(e)
+                                                                                                                                                        }
+                                                                                                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                      }
+                                                                                                                                                    };
+                                                                                                                                                    Err(e)
+                                                                                                                                                  }
+                                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                }
+                                                                };
+                                                                WitNode::ResultValue(e49)
+                                                            }
+                                                            8 => {
+                                                                let e49 = {
+                                                                    let l30 = i32::from(
+                                                                        *base.add(8).cast::<u8>(),
+                                                                    );
+
+                                                                    l30 as u8
+                                                                };
+                                                                WitNode::PrimU8(e49)
+                                                            }
+                                                            9 => {
+                                                                let e49 = {
+                                                                    let l31 = i32::from(
+                                                                        *base.add(8).cast::<u16>(),
+                                                                    );
+
+                                                                    l31 as u16
+                                                                };
+                                                                WitNode::PrimU16(e49)
+                                                            }
+                                                            10 => {
+                                                                let e49 = {
+                                                                    let l32 =
+                                                                        *base.add(8).cast::<i32>();
+
+                                                                    l32 as u32
+                                                                };
+                                                                WitNode::PrimU32(e49)
+                                                            }
+                                                            11 => {
+                                                                let e49 = {
+                                                                    let l33 =
+                                                                        *base.add(8).cast::<i64>();
+
+                                                                    l33 as u64
+                                                                };
+                                                                WitNode::PrimU64(e49)
+                                                            }
+                                                            12 => {
+                                                                let e49 = {
+                                                                    let l34 = i32::from(
+                                                                        *base.add(8).cast::<i8>(),
+                                                                    );
+
+                                                                    l34 as i8
+                                                                };
+                                                                WitNode::PrimS8(e49)
+                                                            }
+                                                            13 => {
+                                                                let e49 = {
+                                                                    let l35 = i32::from(
+                                                                        *base.add(8).cast::<i16>(),
+                                                                    );
+
+                                                                    l35 as i16
+                                                                };
+                                                                WitNode::PrimS16(e49)
+                                                            }
+                                                            14 => {
+                                                                let e49 = {
+                                                                    let l36 =
+                                                                        *base.add(8).cast::<i32>();
+
+                                                                    l36
+                                                                };
+                                                                WitNode::PrimS32(e49)
+                                                            }
+                                                            15 => {
+                                                                let e49 = {
+                                                                    let l37 =
+                                                                        *base.add(8).cast::<i64>();
+
+                                                                    l37
+                                                                };
+                                                                WitNode::PrimS64(e49)
+                                                            }
+                                                            16 => {
+                                                                let e49 = {
+                                                                    let l38 =
+                                                                        *base.add(8).cast::<f32>();
+
+                                                                    l38
+                                                                };
+                                                                WitNode::PrimFloat32(e49)
+                                                            }
+                                                            17 => {
+                                                                let e49 = {
+                                                                    let l39 =
+                                                                        *base.add(8).cast::<f64>();
+
+                                                                    l39
+                                                                };
+                                                                WitNode::PrimFloat64(e49)
+                                                            }
+                                                            18 => {
+                                                                let e49 = {
+                                                                    let l40 =
+                                                                        *base.add(8).cast::<i32>();
+
+                                                                    _rt::char_lift(l40 as u32)
+                                                                };
+                                                                WitNode::PrimChar(e49)
+                                                            }
+                                                            19 => {
+                                                                let e49 = {
+                                                                    let l41 = i32::from(
+                                                                        *base.add(8).cast::<u8>(),
+                                                                    );
+
+                                                                    _rt::bool_lift(l41 as u8)
+                                                                };
+                                                                WitNode::PrimBool(e49)
+                                                            }
+                                                            20 => {
+                                                                let e49 = {
+                                                                    let l42 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l43 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len44 = l43;
+                                                                    let bytes44 =
+                                                                        _rt::Vec::from_raw_parts(
+                                                                            l42.cast(),
+                                                                            len44,
+                                                                            len44,
+                                                                        );
+
+                                                                    _rt::string_lift(bytes44)
+                                                                };
+                                                                WitNode::PrimString(e49)
+                                                            }
+                                                            n => {
+                                                                debug_assert_eq!(
+                                                                    n, 21,
+                                                                    "invalid enum discriminant"
+                                                                );
+                                                                let e49 = {
+                                                                    let l45 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l46 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len47 = l46;
+                                                                    let bytes47 =
+                                                                        _rt::Vec::from_raw_parts(
+                                                                            l45.cast(),
+                                                                            len47,
+                                                                            len47,
+                                                                        );
+                                                                    let l48 =
+                                                                        *base.add(16).cast::<i64>();
+
+                                                                    (
+                                                                        Uri {
+                                                                            value: _rt::string_lift(
+                                                                                bytes47,
+                                                                            ),
+                                                                        },
+                                                                        l48 as u64,
+                                                                    )
+                                                                };
+                                                                WitNode::Handle(e49)
+                                                            }
+                                                        };
+
+                                                        v49
+                                                    };
+                                                    result50.push(e50);
+                                                }
+                                                _rt::cabi_dealloc(base50, len50 * 24, 8);
+
+                                                WitValue { nodes: result50 }
+                                            };
+                                            Ok(e)
+                                        }
+                                        1 => {
+                                            let e = {
+                                                let l51 = i32::from(*ptr0.add(8).cast::<u8>());
+                                                let v67 = match l51 {
+                                                    0 => {
+                                                        let e67 = {
+                                                            let l52 =
+                                                                *ptr0.add(12).cast::<*mut u8>();
+                                                            let l53 = *ptr0.add(16).cast::<usize>();
+                                                            let len54 = l53;
+                                                            let bytes54 = _rt::Vec::from_raw_parts(
+                                                                l52.cast(),
+                                                                len54,
+                                                                len54,
+                                                            );
+
+                                                            _rt::string_lift(bytes54)
+                                                        };
+                                                        RpcError::ProtocolError(e67)
+                                                    }
+                                                    1 => {
+                                                        let e67 = {
+                                                            let l55 =
+                                                                *ptr0.add(12).cast::<*mut u8>();
+                                                            let l56 = *ptr0.add(16).cast::<usize>();
+                                                            let len57 = l56;
+                                                            let bytes57 = _rt::Vec::from_raw_parts(
+                                                                l55.cast(),
+                                                                len57,
+                                                                len57,
+                                                            );
+
+                                                            _rt::string_lift(bytes57)
+                                                        };
+                                                        RpcError::Denied(e67)
+                                                    }
+                                                    2 => {
+                                                        let e67 = {
+                                                            let l58 =
+                                                                *ptr0.add(12).cast::<*mut u8>();
+                                                            let l59 = *ptr0.add(16).cast::<usize>();
+                                                            let len60 = l59;
+                                                            let bytes60 = _rt::Vec::from_raw_parts(
+                                                                l58.cast(),
+                                                                len60,
+                                                                len60,
+                                                            );
+
+                                                            _rt::string_lift(bytes60)
+                                                        };
+                                                        RpcError::NotFound(e67)
+                                                    }
+                                                    3 => {
+                                                        let e67 = {
+                                                            let l61 =
+                                                                *ptr0.add(12).cast::<*mut u8>();
+                                                            let l62 = *ptr0.add(16).cast::<usize>();
+                                                            let len63 = l62;
+                                                            let bytes63 = _rt::Vec::from_raw_parts(
+                                                                l61.cast(),
+                                                                len63,
+                                                                len63,
+                                                            );
+
+                                                            _rt::string_lift(bytes63)
+                                                        };
+                                                        RpcError::RemoteInternalError(e67)
+                                                    }
+                                                    n => {
+                                                        debug_assert_eq!(
+                                                            n, 4,
+                                                            "invalid enum discriminant"
+                                                        );
+                                                        let e67 = {
+                                                            let l64 =
+                                                                *ptr0.add(12).cast::<*mut u8>();
+                                                            let l65 = *ptr0.add(16).cast::<usize>();
+                                                            let len66 = l65;
+                                                            let bytes66 = _rt::Vec::from_raw_parts(
+                                                                l64.cast(),
+                                                                len66,
+                                                                len66,
+                                                            );
+
+                                                            _rt::string_lift(bytes66)
+                                                        };
+                                                        RpcError::Timeout(e67)
+                                                    }
+                                                };
+
+                                                v67
+                                            };
+                                            Err(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    }
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
+            impl FutureInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Stops waiting for the invocation. The pollable becomes ready, and `get` keeps returning
+                /// the result if the invocation had already finished, or a `timeout` error otherwise. Whether
+                /// a cancelled invocation was performed by the remote worker is unknown.
+                pub fn cancel(&self) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]future-invoke-result.cancel"]
+                            fn wit_import(_: i32);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32);
+                    }
+                }
+            }
+            impl FutureBatchInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn subscribe(&self) -> Pollable {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]future-batch-invoke-result.subscribe"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl FutureBatchInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn get(&self) -> Option<_rt::Vec<Result<WitValue, RpcError>>> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]future-batch-invoke-result.get"]
+                            fn wit_import(_: i32, _: *mut u8);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, ptr0);
+                        let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                        match l1 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l2 = *ptr0.add(4).cast::<*mut u8>();
+                                    let l3 = *ptr0.add(8).cast::<usize>();
+                                    let base70 = l2;
+                                    let len70 = l3;
+                                    let mut result70 = _rt::Vec::with_capacity(len70);
+                                    for i in 0..len70 {
+                                        let base = base70.add(i * 16);
+                                        let e70 = {
+                                            let l4 = i32::from(*base.add(0).cast::<u8>());
+
+                                            match l4 {
+                                                0 => {
+                                                    let e = {
+                                                        let l5 = *base.add(4).cast::<*mut u8>();
+                                                        let l6 = *base.add(8).cast::<usize>();
+                                                        let base52 = l5;
+                                                        let len52 = l6;
+                                                        let mut result52 =
+                                                            _rt::Vec::with_capacity(len52);
+                                                        for i in 0..len52 {
+                                                            let base = base52.add(i * 24);
+                                                            let e52 = {
+                                                                let l7 = i32::from(
+                                                                    *base.add(0).cast::<u8>(),
+                                                                );
+                                                                let v51 = match l7 {
+                                                                    0 => {
+                                                                        let e51 = {
+                                                                            let l8 = *base
+                                                                                .add(8)
+                                                                                .cast::<*mut u8>(
+                                                                            );
+                                                                            let l9 = *base
+                                                                                .add(12)
+                                                                                .cast::<usize>(
+                                                                            );
+                                                                            let len10 = l9;
+
+                                                                            _rt::Vec::from_raw_parts(
+                                                                                l8.cast(),
+                                                                                len10,
+                                                                                len10,
+                                                                            )
+                                                                        };
+                                                                        WitNode::RecordValue(e51)
+                                                                    }
+                                                                    1 => {
+                                                                        let e51 = {
+                                                                            let l11 = *base
+                                                                                .add(8)
+                                                                                .cast::<i32>(
+                                                                            );
+                                                                            let l12 = i32::from(
+                                                                                *base
+                                                                                    .add(12)
+                                                                                    .cast::<u8>(),
+                                                                            );
+
+                                                                            (l11 as u32, match l12 {
+                                                                                                                                                      0 => None,
+                                                                                                                                                      1 => {
+                                                                                                                                                        let e = {
+                                                                                                                                                          let l13 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                          l13
+                                                                                                                                                        };
+                                                                                                                                                        Some(e)
+                                                                                                                                                      }
+                                                                                                                                                      _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                    })
+                                                                        };
+                                                                        WitNode::VariantValue(e51)
+                                                                    }
+                                                                    2 => {
+                                                                        let e51 = {
+                                                                            let l14 = *base
+                                                                                .add(8)
+                                                                                .cast::<i32>(
+                                                                            );
+
+                                                                            l14 as u32
+                                                                        };
+                                                                        WitNode::EnumValue(e51)
+                                                                    }
+                                                                    3 => {
+                                                                        let e51 = {
+                                                                            let l15 = *base
+                                                                                .add(8)
+                                                                                .cast::<*mut u8>(
+                                                                            );
+                                                                            let l16 = *base
+                                                                                .add(12)
+                                                                                .cast::<usize>();
+                                                                            let base18 = l15;
+                                                                            let len18 = l16;
+                                                                            let mut result18 = _rt::Vec::with_capacity(len18);
+                                                                            for i in 0..len18 {
+                                                                                let base = base18
+                                                                                    .add(i * 1);
+                                                                                let e18 = {
+                                                                                    let l17 = i32::from(*base.add(0).cast::<u8>());
+
+                                                                                    _rt::bool_lift(
+                                                                                        l17 as u8,
+                                                                                    )
+                                                                                };
+                                                                                result18.push(e18);
+                                                                            }
+                                                                            _rt::cabi_dealloc(
+                                                                                base18,
+                                                                                len18 * 1,
+                                                                                1,
+                                                                            );
+
+                                                                            result18
+                                                                        };
+                                                                        WitNode::FlagsValue(e51)
+                                                                    }
+                                                                    4 => {
+                                                                        let e51 = {
+                                                                            let l19 = *base
+                                                                                .add(8)
+                                                                                .cast::<*mut u8>(
+                                                                            );
+                                                                            let l20 = *base
+                                                                                .add(12)
+                                                                                .cast::<usize>();
+                                                                            let len21 = l20;
+
+                                                                            _rt::Vec::from_raw_parts(
+                                                                                l19.cast(),
+                                                                                len21,
+                                                                                len21,
+                                                                            )
+                                                                        };
+                                                                        WitNode::TupleValue(e51)
+                                                                    }
+                                                                    5 => {
+                                                                        let e51 = {
+                                                                            let l22 = *base
+                                                                                .add(8)
+                                                                                .cast::<*mut u8>(
+                                                                            );
+                                                                            let l23 = *base
+                                                                                .add(12)
+                                                                                .cast::<usize>();
+                                                                            let len24 = l23;
+
+                                                                            _rt::Vec::from_raw_parts(
+                                                                                l22.cast(),
+                                                                                len24,
+                                                                                len24,
+                                                                            )
+                                                                        };
+                                                                        WitNode::ListValue(e51)
+                                                                    }
+                                                                    6 => {
+                                                                        let e51 = {
+                                                                            let l25 = i32::from(
+                                                                                *base
+                                                                                    .add(8)
+                                                                                    .cast::<u8>(),
+                                                                            );
+
+                                                                            match l25 {
+                                                                                                                                                      0 => None,
+                                                                                                                                                      1 => {
+                                                                                                                                                        let e = {
+                                                                                                                                                          let l26 = *base.add(12).cast::<i32>();
+
+                                                                                                                                                          l26
+                                                                                                                                                        };
+                                                                                                                                                        Some(e)
+                                                                                                                                                      }
+                                                                                                                                                      _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                    }
+                                                                        };
+                                                                        WitNode::OptionValue(e51)
+                                                                    }
+                                                                    7 => {
+                                                                        let e51 = {
+                                                                            let l27 = i32::from(
+                                                                                *base
+                                                                                    .add(8)
+                                                                                    .cast::<u8>(),
+                                                                            );
+
+                                                                            match l27 {
+                                                                                                                                                      0 => {
+                                                                                                                                                        let e = {
+                                                                                                                                                          let l28 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                                          match l28 {
+                                                                                                                                                            0 => None,
+                                                                                                                                                            1 => {
+                                                                                                                                                              let e = {
+                                                                                                                                                                let l29 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                                l29
+                                                                                                                                                              };
+                                                                                                                                                              Some(e)
+                                                                                                                                                            }
+                                                                                                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                          }
+                                                                                                                                                        };
+                                                                                                                                                        Ok(e)
+                                                                                                                                                      }
+                                                                                                                                                      1 => {
+                                                                                                                                                        let e = {
+                                                                                                                                                          let l30 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                                          match l30 {
+                                                                                                                                                            0 => None,
+                                                                                                                                                            1 => {
+                                                                                                                                                              let e = {
+                                                                                                                                                                let l31 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                                l31
+                                                                                                                                                              };
+                                                                                                                                                              Some(e)
+                                                                                                                                                            }
+                                                                                                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                          }
+                                                                                                                                                        };
+                                                                                                                                                        Err(e)
+                                                                                                                                                      }
+                                                                                                                                                      _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                    }
+                                                                        };
+                                                                        WitNode::ResultValue(e51)
+                                                                    }
+                                                                    8 => {
+                                                                        let e51 = {
+                                                                            let l32 = i32::from(
+                                                                                *base
+                                                                                    .add(8)
+                                                                                    .cast::<u8>(),
+                                                                            );
+
+                                                                            l32 as u8
+                                                                        };
+                                                                        WitNode::PrimU8(e51)
+                                                                    }
+                                                                    9 => {
+                                                                        let e51 = {
+                                                                            let l33 = i32::from(
+                                                                                *base
+                                                                                    .add(8)
+                                                                                    .cast::<u16>(),
+                                                                            );
+
+                                                                            l33 as u16
+                                                                        };
+                                                                        WitNode::PrimU16(e51)
+                                                                    }
+                                                                    10 => {
+                                                                        let e51 = {
+                                                                            let l34 = *base
+                                                                                .add(8)
+                                                                                .cast::<i32>(
+                                                                            );
+
+                                                                            l34 as u32
+                                                                        };
+                                                                        WitNode::PrimU32(e51)
+                                                                    }
+                                                                    11 => {
+                                                                        let e51 = {
+                                                                            let l35 = *base
+                                                                                .add(8)
+                                                                                .cast::<i64>(
+                                                                            );
+
+                                                                            l35 as u64
+                                                                        };
+                                                                        WitNode::PrimU64(e51)
+                                                                    }
+                                                                    12 => {
+                                                                        let e51 = {
+                                                                            let l36 = i32::from(
+                                                                                *base
+                                                                                    .add(8)
+                                                                                    .cast::<i8>(),
+                                                                            );
+
+                                                                            l36 as i8
+                                                                        };
+                                                                        WitNode::PrimS8(e51)
+                                                                    }
+                                                                    13 => {
+                                                                        let e51 = {
+                                                                            let l37 = i32::from(
+                                                                                *base
+                                                                                    .add(8)
+                                                                                    .cast::<i16>(),
+                                                                            );
+
+                                                                            l37 as i16
+                                                                        };
+                                                                        WitNode::PrimS16(e51)
+                                                                    }
+                                                                    14 => {
+                                                                        let e51 = {
+                                                                            let l38 = *base
+                                                                                .add(8)
+                                                                                .cast::<i32>(
+                                                                            );
+
+                                                                            l38
+                                                                        };
+                                                                        WitNode::PrimS32(e51)
+                                                                    }
+                                                                    15 => {
+                                                                        let e51 = {
+                                                                            let l39 = *base
+                                                                                .add(8)
+                                                                                .cast::<i64>(
+                                                                            );
+
+                                                                            l39
+                                                                        };
+                                                                        WitNode::PrimS64(e51)
+                                                                    }
+                                                                    16 => {
+                                                                        let e51 = {
+                                                                            let l40 = *base
+                                                                                .add(8)
+                                                                                .cast::<f32>(
+                                                                            );
+
+                                                                            l40
+                                                                        };
+                                                                        WitNode::PrimFloat32(e51)
+                                                                    }
+                                                                    17 => {
+                                                                        let e51 = {
+                                                                            let l41 = *base
+                                                                                .add(8)
+                                                                                .cast::<f64>(
+                                                                            );
+
+                                                                            l41
+                                                                        };
+                                                                        WitNode::PrimFloat64(e51)
+                                                                    }
+                                                                    18 => {
+                                                                        let e51 = {
+                                                                            let l42 = *base
+                                                                                .add(8)
+                                                                                .cast::<i32>(
+                                                                            );
+
+                                                                            _rt::char_lift(
+                                                                                l42 as u32,
+                                                                            )
+                                                                        };
+                                                                        WitNode::PrimChar(e51)
+                                                                    }
+                                                                    19 => {
+                                                                        let e51 = {
+                                                                            let l43 = i32::from(
+                                                                                *base
+                                                                                    .add(8)
+                                                                                    .cast::<u8>(),
+                                                                            );
+
+                                                                            _rt::bool_lift(
+                                                                                l43 as u8,
+                                                                            )
+                                                                        };
+                                                                        WitNode::PrimBool(e51)
+                                                                    }
+                                                                    20 => {
+                                                                        let e51 = {
+                                                                            let l44 = *base
+                                                                                .add(8)
+                                                                                .cast::<*mut u8>(
+                                                                            );
+                                                                            let l45 = *base
+                                                                                .add(12)
+                                                                                .cast::<usize>();
+                                                                            let len46 = l45;
+                                                                            let bytes46 = _rt::Vec::from_raw_parts(l44.cast(), len46, len46);
+
+                                                                            _rt::string_lift(
+                                                                                bytes46,
+                                                                            )
+                                                                        };
+                                                                        WitNode::PrimString(e51)
+                                                                    }
+                                                                    n => {
+                                                                        debug_assert_eq!(n, 21, "invalid enum discriminant");
+                                                                        let e51 = {
+                                                                            let l47 = *base
+                                                                                .add(8)
+                                                                                .cast::<*mut u8>(
+                                                                            );
+                                                                            let l48 = *base
+                                                                                .add(12)
+                                                                                .cast::<usize>();
+                                                                            let len49 = l48;
+                                                                            let bytes49 = _rt::Vec::from_raw_parts(l47.cast(), len49, len49);
+                                                                            let l50 = *base
+                                                                                .add(16)
+                                                                                .cast::<i64>();
+
+                                                                            (Uri{
+                                                                                                                                                      value: _rt::string_lift(bytes49),
+                                                                                                                                                    }, l50 as u64)
+                                                                        };
+                                                                        WitNode::Handle(e51)
+                                                                    }
+                                                                };
+
+                                                                v51
+                                                            };
+                                                            result52.push(e52);
+                                                        }
+                                                        _rt::cabi_dealloc(base52, len52 * 24, 8);
+
+                                                        WitValue { nodes: result52 }
+                                                    };
+                                                    Ok(e)
+                                                }
+                                                1 => {
+                                                    let e = {
+                                                        let l53 =
+                                                            i32::from(*base.add(4).cast::<u8>());
+                                                        let v69 = match l53 {
+                                                            0 => {
+                                                                let e69 = {
+                                                                    let l54 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l55 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len56 = l55;
+                                                                    let bytes56 =
+                                                                        _rt::Vec::from_raw_parts(
+                                                                            l54.cast(),
+                                                                            len56,
+                                                                            len56,
+                                                                        );
+
+                                                                    _rt::string_lift(bytes56)
+                                                                };
+                                                                RpcError::ProtocolError(e69)
+                                                            }
+                                                            1 => {
+                                                                let e69 = {
+                                                                    let l57 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l58 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len59 = l58;
+                                                                    let bytes59 =
+                                                                        _rt::Vec::from_raw_parts(
+                                                                            l57.cast(),
+                                                                            len59,
+                                                                            len59,
+                                                                        );
+
+                                                                    _rt::string_lift(bytes59)
+                                                                };
+                                                                RpcError::Denied(e69)
+                                                            }
+                                                            2 => {
+                                                                let e69 = {
+                                                                    let l60 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l61 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len62 = l61;
+                                                                    let bytes62 =
+                                                                        _rt::Vec::from_raw_parts(
+                                                                            l60.cast(),
+                                                                            len62,
+                                                                            len62,
+                                                                        );
+
+                                                                    _rt::string_lift(bytes62)
+                                                                };
+                                                                RpcError::NotFound(e69)
+                                                            }
+                                                            3 => {
+                                                                let e69 = {
+                                                                    let l63 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l64 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len65 = l64;
+                                                                    let bytes65 =
+                                                                        _rt::Vec::from_raw_parts(
+                                                                            l63.cast(),
+                                                                            len65,
+                                                                            len65,
+                                                                        );
+
+                                                                    _rt::string_lift(bytes65)
+                                                                };
+                                                                RpcError::RemoteInternalError(e69)
+                                                            }
+                                                            n => {
+                                                                debug_assert_eq!(
+                                                                    n, 4,
+                                                                    "invalid enum discriminant"
+                                                                );
+                                                                let e69 = {
+                                                                    let l66 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l67 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len68 = l67;
+                                                                    let bytes68 =
+                                                                        _rt::Vec::from_raw_parts(
+                                                                            l66.cast(),
+                                                                            len68,
+                                                                            len68,
+                                                                        );
+
+                                                                    _rt::string_lift(bytes68)
+                                                                };
+                                                                RpcError::Timeout(e69)
+                                                            }
+                                                        };
+
+                                                        v69
+                                                    };
+                                                    Err(e)
+                                                }
+                                                _ => _rt::invalid_enum_discriminant(),
+                                            }
+                                        };
+                                        result70.push(e70);
+                                    }
+                                    _rt::cabi_dealloc(base70, len70 * 16, 4);
+
+                                    result70
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
+            impl FutureBatchInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Stops waiting for the batch, like `future-invoke-result.cancel`. If the batch had not
+                /// finished, `get` returns a `timeout` error for each of its invocations.
+                pub fn cancel(&self) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]future-batch-invoke-result.cancel"]
+                            fn wit_import(_: i32);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32);
+                    }
+                }
+            }
+            impl InvokeResultStream {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Becomes ready when the next part is available, or when the stream has ended
+                pub fn subscribe(&self) -> Pollable {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]invoke-result-stream.subscribe"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl InvokeResultStream {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Returns the next part if it is available. Returning `none` after the pollable became
+                /// ready means the stream has ended. An error does not necessarily end the stream.
+                pub fn get_next(&self) -> Option<Result<WitValue, RpcError>> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 20]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 20]);
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.1.0")]
+                        extern "C" {
+                            #[link_name = "[method]invoke-result-stream.get-next"]
+                            fn wit_import(_: i32, _: *mut u8);
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, ptr0);
+                        let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                        match l1 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l2 = i32::from(*ptr0.add(4).cast::<u8>());
+
+                                    match l2 {
+                                        0 => {
+                                            let e = {
+                                                let l3 = *ptr0.add(8).cast::<*mut u8>();
+                                                let l4 = *ptr0.add(12).cast::<usize>();
+                                                let base50 = l3;
+                                                let len50 = l4;
+                                                let mut result50 = _rt::Vec::with_capacity(len50);
+                                                for i in 0..len50 {
+                                                    let base = base50.add(i * 24);
+                                                    let e50 = {
+                                                        let l5 =
+                                                            i32::from(*base.add(0).cast::<u8>());
+                                                        let v49 = match l5 {
+                                                            0 => {
+                                                                let e49 = {
+                                                                    let l6 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l7 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let len8 = l7;
+
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l6.cast(),
+                                                                        len8,
+                                                                        len8,
+                                                                    )
+                                                                };
+                                                                WitNode::RecordValue(e49)
+                                                            }
+                                                            1 => {
+                                                                let e49 = {
+                                                                    let l9 =
+                                                                        *base.add(8).cast::<i32>();
+                                                                    let l10 = i32::from(
+                                                                        *base.add(12).cast::<u8>(),
+                                                                    );
+
+                                                                    (l9 as u32, match l10 {
+                                                                                                                                                  0 => None,
+                                                                                                                                                  1 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l11 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                      l11
+                                                                                                                                                    };
+                                                                                                                                                    Some(e)
+                                                                                                                                                  }
+                                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                })
+                                                                };
+                                                                WitNode::VariantValue(e49)
+                                                            }
+                                                            2 => {
+                                                                let e49 = {
+                                                                    let l12 =
+                                                                        *base.add(8).cast::<i32>();
+
+                                                                    l12 as u32
+                                                                };
+                                                                WitNode::EnumValue(e49)
+                                                            }
+                                                            3 => {
+                                                                let e49 = {
+                                                                    let l13 = *base
+                                                                        .add(8)
+                                                                        .cast::<*mut u8>();
+                                                                    let l14 = *base
+                                                                        .add(12)
+                                                                        .cast::<usize>();
+                                                                    let base16 = l13;
+                                                                    let len16 = l14;
+                                                                    let mut result16 =
+                                                                        _rt::Vec::with_capacity(
+                                                                            len16,
+                                                                        );
+                                                                    for i in 0..len16 {
+                                                                        let base =
+                                                                            base16.add(i * 1);
+                                                                        let e16 = {
+                                                                            let l15 = i32::from(
+                                                                                *base
+                                                                                    .add(0)
+                                                                                    .cast::<u8>(),
+                                                                            );
+
+                                                                            _rt::bool_lift(
+                                                                                l15 as u8,
+                                                                            )
+                                                                        };
+                                                                        result16.push(e16);
+                                                                    }
+                                                                    _rt::cabi_dealloc(
+                                                                        base16,
+                                                                        len16 * 1,
+                                                                        1,
+                                                                    );
 
                                                                     result16
                                                                 };
@@ -1856,17 +7450,17 @@ pub mod golem {
                                                                     );
 
                                                                     match l23 {
-                                                                  0 => None,
-                                                                  1 => {
-                                                                    let e = {
-                                                                      let l24 = *base.add(12).cast::<i32>();
-
-                                                                      l24
-                                                                    };
-                                                                    Some(e)
-                                                                  }
-                                                                  _ => _rt::invalid_enum_discriminant(),
-                                                                }
+                                                                                                                                                  0 => None,
+                                                                                                                                                  1 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l24 = *base.add(12).cast::<i32>();
+
+                                                                                                                                                      l24
+                                                                                                                                                    };
+                                                                                                                                                    Some(e)
+                                                                                                                                                  }
+                                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                }
                                                                 };
                                                                 WitNode::OptionValue(e49)
                                                             }
@@ -1877,46 +7471,46 @@ pub mod golem {
                                                                     );
 
                                                                     match l25 {
-                                                                  0 => {
-                                                                    let e = {
-                                                                      let l26 = i32::from(*base.add(12).cast::<u8>());
-
-                                                                      match l26 {
-                                                                        0 => None,
-                                                                        1 => {
-                                                                          let e = {
-                                                                            let l27 = *base.add(16).cast::<i32>();
-
-                                                                            l27
-                                                                          };
-                                                                          Some(e)
-                                                                        }
-                                                                        _ => _rt::invalid_enum_discriminant(),
-                                                                      }
-                                                                    };
-                                                                    Ok(e)
-                                                                  }
-                                                                  1 => {
-                                                                    let e = {
-                                                                      let l28 = i32::from(*base.add(12).cast::<u8>());
-
-                                                                      match l28 {
-                                                                        0 => None,
-                                                                        1 => {
-                                                                          let e = {
-                                                                            let l29 = *base.add(16).cast::<i32>();
-
-                                                                            l29
-                                                                          };
-                                                                          Some(e)
-                                                                        }
-                                                                        _ => _rt::invalid_enum_discriminant(),
-                                                                      }
-                                                                    };
-                                                                    Err(e)
-                                                                  }
-                                                                  _ => _rt::invalid_enum_discriminant(),
-                                                                }
+                                                                                                                                                  0 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l26 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                                      match l26 {
+                                                                                                                                                        0 => None,
+                                                                                                                                                        1 => {
+                                                                                                                                                          let e = {
+                                                                                                                                                            let l27 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                            l27
+                                                                                                                                                          };
+                                                                                                                                                          Some(e)
+                                                                                                                                                        }
+                                                                                                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                      }
+                                                                                                                                                    };
+                                                                                                                                                    Ok(e)
+                                                                                                                                                  }
+                                                                                                                                                  1 => {
+                                                                                                                                                    let e = {
+                                                                                                                                                      let l28 = i32::from(*base.add(12).cast::<u8>());
+
+                                                                                                                                                      match l28 {
+                                                                                                                                                        0 => None,
+                                                                                                                                                        1 => {
+                                                                                                                                                          let e = {
+                                                                                                                                                            let l29 = *base.add(16).cast::<i32>();
+
+                                                                                                                                                            l29
+                                                                                                                                                          };
+                                                                                                                                                          Some(e)
+                                                                                                                                                        }
+                                                                                                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                      }
+                                                                                                                                                    };
+                                                                                                                                                    Err(e)
+                                                                                                                                                  }
+                                                                                                                                                  _ => _rt::invalid_enum_discriminant(),
+                                                                                                                                                }
                                                                 };
                                                                 WitNode::ResultValue(e49)
                                                             }
@@ -2101,9 +7695,9 @@ pub mod golem {
                                         1 => {
                                             let e = {
                                                 let l51 = i32::from(*ptr0.add(8).cast::<u8>());
-                                                let v64 = match l51 {
+                                                let v67 = match l51 {
                                                     0 => {
-                                                        let e64 = {
+                                                        let e67 = {
                                                             let l52 =
                                                                 *ptr0.add(12).cast::<*mut u8>();
                                                             let l53 = *ptr0.add(16).cast::<usize>();
@@ -2116,10 +7710,10 @@ pub mod golem {
 
                                                             _rt::string_lift(bytes54)
                                                         };
-                                                        RpcError::ProtocolError(e64)
+                                                        RpcError::ProtocolError(e67)
                                                     }
                                                     1 => {
-                                                        let e64 = {
+                                                        let e67 = {
                                                             let l55 =
                                                                 *ptr0.add(12).cast::<*mut u8>();
                                                             let l56 = *ptr0.add(16).cast::<usize>();
@@ -2132,10 +7726,10 @@ pub mod golem {
 
                                                             _rt::string_lift(bytes57)
                                                         };
-                                                        RpcError::Denied(e64)
+                                                        RpcError::Denied(e67)
                                                     }
                                                     2 => {
-                                                        let e64 = {
+                                                        let e67 = {
                                                             let l58 =
                                                                 *ptr0.add(12).cast::<*mut u8>();
                                                             let l59 = *ptr0.add(16).cast::<usize>();
@@ -2148,14 +7742,10 @@ pub mod golem {
 
                                                             _rt::string_lift(bytes60)
                                                         };
-                                                        RpcError::NotFound(e64)
+                                                        RpcError::NotFound(e67)
                                                     }
-                                                    n => {
-                                                        debug_assert_eq!(
-                                                            n, 3,
-                                                            "invalid enum discriminant"
-                                                        );
-                                                        let e64 = {
+                                                    3 => {
+                                                        let e67 = {
                                                             let l61 =
                                                                 *ptr0.add(12).cast::<*mut u8>();
                                                             let l62 = *ptr0.add(16).cast::<usize>();
@@ -2168,11 +7758,31 @@ pub mod golem {
 
                                                             _rt::string_lift(bytes63)
                                                         };
-                                                        RpcError::RemoteInternalError(e64)
+                                                        RpcError::RemoteInternalError(e67)
+                                                    }
+                                                    n => {
+                                                        debug_assert_eq!(
+                                                            n, 4,
+                                                            "invalid enum discriminant"
+                                                        );
+                                                        let e67 = {
+                                                            let l64 =
+                                                                *ptr0.add(12).cast::<*mut u8>();
+                                                            let l65 = *ptr0.add(16).cast::<usize>();
+                                                            let len66 = l65;
+                                                            let bytes66 = _rt::Vec::from_raw_parts(
+                                                                l64.cast(),
+                                                                len66,
+                                                                len66,
+                                                            );
+
+                                                            _rt::string_lift(bytes66)
+                                                        };
+                                                        RpcError::Timeout(e67)
                                                     }
                                                 };
 
-                                                v64
+                                                v67
                                             };
                                             Err(e)
                                         }
@@ -2647,12 +8257,12 @@ mod _rt {
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.25.0:wit-value:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1310] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x9e\x09\x01A\x02\x01\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 2770] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xd2\x14\x01A\x02\x01\
 A\x05\x01B\x0a\x04\0\x08pollable\x03\x01\x01h\0\x01@\x01\x04self\x01\0\x7f\x04\0\
 \x16[method]pollable.ready\x01\x02\x01@\x01\x04self\x01\x01\0\x04\0\x16[method]p\
 ollable.block\x01\x03\x01p\x01\x01py\x01@\x01\x02in\x04\0\x05\x04\0\x04poll\x01\x06\
-\x03\x01\x12wasi:io/poll@0.2.0\x05\0\x02\x03\0\0\x08pollable\x01B*\x02\x03\x02\x01\
+\x03\x01\x12wasi:io/poll@0.2.0\x05\0\x02\x03\0\0\x08pollable\x01B\\\x02\x03\x02\x01\
 \x01\x04\0\x08pollable\x03\0\0\x01z\x04\0\x0anode-index\x03\0\x02\x01r\x01\x05va\
 lues\x04\0\x03uri\x03\0\x04\x01p\x03\x01k\x03\x01o\x02y\x07\x01p\x7f\x01j\x01\x07\
 \x01\x07\x01o\x02\x05w\x01q\x16\x0crecord-value\x01\x06\0\x0dvariant-value\x01\x08\
@@ -2662,20 +8272,45 @@ t-value\x01\x06\0\x0coption-value\x01\x07\0\x0cresult-value\x01\x0a\0\x07prim-u8
 \0\x08prim-s16\x01|\0\x08prim-s32\x01z\0\x08prim-s64\x01x\0\x0cprim-float32\x01v\
 \0\x0cprim-float64\x01u\0\x09prim-char\x01t\0\x09prim-bool\x01\x7f\0\x0bprim-str\
 ing\x01s\0\x06handle\x01\x0b\0\x04\0\x08wit-node\x03\0\x0c\x01p\x0d\x01r\x01\x05\
-nodes\x0e\x04\0\x09wit-value\x03\0\x0f\x01q\x04\x0eprotocol-error\x01s\0\x06deni\
-ed\x01s\0\x09not-found\x01s\0\x15remote-internal-error\x01s\0\x04\0\x09rpc-error\
-\x03\0\x11\x04\0\x08wasm-rpc\x03\x01\x04\0\x14future-invoke-result\x03\x01\x01i\x13\
-\x01@\x01\x08location\x05\0\x15\x04\0\x15[constructor]wasm-rpc\x01\x16\x01h\x13\x01\
-p\x10\x01j\x01\x10\x01\x12\x01@\x03\x04self\x17\x0dfunction-names\x0ffunction-pa\
-rams\x18\0\x19\x04\0![method]wasm-rpc.invoke-and-await\x01\x1a\x01j\0\x01\x12\x01\
-@\x03\x04self\x17\x0dfunction-names\x0ffunction-params\x18\0\x1b\x04\0\x17[metho\
-d]wasm-rpc.invoke\x01\x1c\x01i\x14\x01@\x03\x04self\x17\x0dfunction-names\x0ffun\
-ction-params\x18\0\x1d\x04\0'[method]wasm-rpc.async-invoke-and-await\x01\x1e\x01\
-h\x14\x01i\x01\x01@\x01\x04self\x1f\0\x20\x04\0&[method]future-invoke-result.sub\
-scribe\x01!\x01k\x19\x01@\x01\x04self\x1f\0\"\x04\0\x20[method]future-invoke-res\
-ult.get\x01#\x03\x01\x15golem:rpc/types@0.1.0\x05\x02\x04\x01\x19golem:rpc/wit-v\
-alue@0.1.0\x04\0\x0b\x0f\x01\0\x09wit-value\x03\0\0\0G\x09producers\x01\x0cproce\
-ssed-by\x02\x0dwit-component\x070.208.1\x10wit-bindgen-rust\x060.25.0";
+nodes\x0e\x04\0\x09wit-value\x03\0\x0f\x01q\x05\x0eprotocol-error\x01s\0\x06deni\
+ed\x01s\0\x09not-found\x01s\0\x15remote-internal-error\x01s\0\x07timeout\x01s\0\x04\
+\0\x09rpc-error\x03\0\x11\x01r\x04\x0cmax-attemptsy\x0fmin-delay-nanosw\x0fmax-d\
+elay-nanosw\x0amultiplieru\x04\0\x0cretry-policy\x03\0\x13\x01ks\x01k\x14\x01o\x02\
+ss\x01p\x17\x01kw\x01r\x04\x0fidempotency-key\x15\x0cretry-policy\x16\x07headers\
+\x18\x0dtimeout-nanos\x19\x04\0\x12invocation-options\x03\0\x1a\x01p\x10\x01r\x02\
+\x0dfunction-names\x0ffunction-params\x1c\x04\0\x10batch-invocation\x03\0\x1d\x01\
+r\x02\x08parallel\x7f\x13stop-on-first-error\x7f\x04\0\x0dbatch-options\x03\0\x1f\
+\x04\0\x08wasm-rpc\x03\x01\x04\0\x14future-invoke-result\x03\x01\x04\0\x1afuture\
+-batch-invoke-result\x03\x01\x04\0\x14invoke-result-stream\x03\x01\x01i!\x01@\x01\
+\x08location\x05\0%\x04\0\x15[constructor]wasm-rpc\x01&\x01h!\x01j\x01\x10\x01\x12\
+\x01@\x03\x04self'\x0dfunction-names\x0ffunction-params\x1c\0(\x04\0![method]was\
+m-rpc.invoke-and-await\x01)\x01j\0\x01\x12\x01@\x03\x04self'\x0dfunction-names\x0f\
+function-params\x1c\0*\x04\0\x17[method]wasm-rpc.invoke\x01+\x01i\"\x01@\x03\x04\
+self'\x0dfunction-names\x0ffunction-params\x1c\0,\x04\0'[method]wasm-rpc.async-i\
+nvoke-and-await\x01-\x01@\x04\x04self'\x0dfunction-names\x0ffunction-params\x1c\x0d\
+timeout-nanosw\0(\x04\0.[method]wasm-rpc.invoke-and-await-with-timeout\x01.\x01@\
+\x04\x04self'\x0dfunction-names\x0ffunction-params\x1c\x0dtimeout-nanosw\0,\x04\0\
+4[method]wasm-rpc.async-invoke-and-await-with-timeout\x01/\x01@\x04\x04self'\x0d\
+function-names\x0ffunction-params\x1c\x07options\x1b\0(\x04\0.[method]wasm-rpc.i\
+nvoke-and-await-with-options\x010\x01@\x04\x04self'\x0dfunction-names\x0ffunctio\
+n-params\x1c\x07options\x1b\0*\x04\0$[method]wasm-rpc.invoke-with-options\x011\x01\
+@\x04\x04self'\x0dfunction-names\x0ffunction-params\x1c\x07options\x1b\0,\x04\04\
+[method]wasm-rpc.async-invoke-and-await-with-options\x012\x01p\x1e\x01p(\x01@\x03\
+\x04self'\x0binvocations3\x07options\x20\04\x04\0\x1d[method]wasm-rpc.invoke-bat\
+ch\x015\x01i#\x01@\x03\x04self'\x0binvocations3\x07options\x20\06\x04\0#[method]\
+wasm-rpc.async-invoke-batch\x017\x01i$\x01@\x03\x04self'\x0dfunction-names\x0ffu\
+nction-params\x1c\08\x04\0\x1e[method]wasm-rpc.stream-invoke\x019\x01h\"\x01i\x01\
+\x01@\x01\x04self:\0;\x04\0&[method]future-invoke-result.subscribe\x01<\x01k(\x01\
+@\x01\x04self:\0=\x04\0\x20[method]future-invoke-result.get\x01>\x01@\x01\x04sel\
+f:\x01\0\x04\0#[method]future-invoke-result.cancel\x01?\x01h#\x01@\x01\x04self\xc0\
+\0\0;\x04\0,[method]future-batch-invoke-result.subscribe\x01A\x01k4\x01@\x01\x04\
+self\xc0\0\0\xc2\0\x04\0&[method]future-batch-invoke-result.get\x01C\x01@\x01\x04\
+self\xc0\0\x01\0\x04\0)[method]future-batch-invoke-result.cancel\x01D\x01h$\x01@\
+\x01\x04self\xc5\0\0;\x04\0&[method]invoke-result-stream.subscribe\x01F\x01@\x01\
+\x04self\xc5\0\0=\x04\0%[method]invoke-result-stream.get-next\x01G\x03\x01\x15go\
+lem:rpc/types@0.1.0\x05\x02\x04\x01\x19golem:rpc/wit-value@0.1.0\x04\0\x0b\x0f\x01\
+\0\x09wit-value\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\
+\x070.208.1\x10wit-bindgen-rust\x060.25.0";
 
 #[inline(never)]
 #[doc(hidden)]
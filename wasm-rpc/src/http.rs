@@ -0,0 +1,857 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Invocations over HTTP.
+//!
+//! An invocation is a `POST` request with one of the following bodies, selected by its
+//! `Content-Type`:
+//!
+//! - `application/x-protobuf`: a protobuf `InvocationRequest` message, answered by a protobuf
+//!   `WitValue` message
+//! - `application/json`: an object with `target`, `function_name` and `params` fields, where the
+//!   parameters use the typed JSON format of `TypeAnnotatedValue`, answered by a typed JSON value
+//!
+//! The idempotency key of an invocation is part of the body, while its headers are sent as HTTP
+//! headers.
+//!
+//! Successful invocations are answered with `200 OK` and the result tuple. Failed invocations
+//! are answered with a plain text message and a status code depending on the `RpcError`:
+//! `400 Bad Request` for `protocol-error`, `403 Forbidden` for `denied`, `404 Not Found` for
+//! `not-found`, `500 Internal Server Error` for `remote-internal-error` and `504 Gateway Timeout`
+//! for `timeout`.
+//!
+//! A batch of invocations is sent as a single request with the `x-wasm-rpc-batch` header, and a
+//! protobuf `BatchInvocationRequest` message or a JSON object with `target`, `invocations`,
+//! `parallel` and `stop_on_first_error` fields as its body. It is answered with a protobuf
+//! `BatchInvocationResponse` message or a JSON array of `{"ok": value}` and `{"err": error}`
+//! objects, while the status code only reflects errors of the batch as a whole.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use golem_wasm_ast::analysis::{AnalysedExport, AnalysedType, TypeTuple};
+use poem::http::{header, HeaderName, HeaderValue, Method, StatusCode};
+use poem::listener::{Acceptor, Listener, TcpListener};
+use poem::{Endpoint, Request, Response, Server};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::local::LocalWasmRpcHost;
+use crate::protobuf;
+use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
+use crate::text::find_function;
+use crate::transport::RpcTransport;
+use crate::{
+    BatchInvocation, BatchInvocationRequest, BatchOptions, InvocationOptions, InvocationRequest,
+    RpcError, TypeAnnotatedValueConstructors, Uri, Value, WitValue,
+};
+
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+const JSON_CONTENT_TYPE: &str = "application/json";
+/// Marks the requests carrying a batch of invocations
+const BATCH_HEADER: &str = "x-wasm-rpc-batch";
+
+/// The body format of the invocations sent by `HttpTransport`
+#[derive(Debug, Clone)]
+pub enum HttpEncoding {
+    /// Protobuf `InvocationRequest` and `WitValue` messages
+    Protobuf,
+    /// Typed JSON values. The types of the parameters are looked up in the exports of the
+    /// target component. Resource handles are not supported.
+    Json { exports: Vec<AnalysedExport> },
+}
+
+/// The JSON body of an invocation
+#[derive(Serialize, Deserialize)]
+struct JsonInvocationRequest {
+    target: Uri,
+    function_name: String,
+    params: Vec<TypeAnnotatedValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+}
+
+/// The JSON body of a batch of invocations
+#[derive(Serialize, Deserialize)]
+struct JsonBatchInvocationRequest {
+    target: Uri,
+    invocations: Vec<JsonBatchInvocation>,
+    parallel: bool,
+    stop_on_first_error: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonBatchInvocation {
+    function_name: String,
+    params: Vec<TypeAnnotatedValue>,
+}
+
+/// The JSON result of an invocation of a batch
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum JsonInvocationResult {
+    Ok(TypeAnnotatedValue),
+    Err(RpcError),
+}
+
+/// An `RpcTransport` sending invocations as HTTP requests
+pub struct HttpTransport {
+    client: reqwest::Client,
+    endpoint: Box<dyn Fn(&Uri) -> String + Send + Sync>,
+    encoding: HttpEncoding,
+}
+
+impl HttpTransport {
+    /// Sends every invocation to the same URL, the target worker being identified by the body
+    /// of the request.
+    pub fn new(url: impl Into<String>, encoding: HttpEncoding) -> Self {
+        let url = url.into();
+        Self::with_endpoint(move |_| url.clone(), encoding)
+    }
+
+    /// Sends each invocation to the URL returned by `endpoint` for its target.
+    pub fn with_endpoint(
+        endpoint: impl Fn(&Uri) -> String + Send + Sync + 'static,
+        encoding: HttpEncoding,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: Box::new(endpoint),
+            encoding,
+        }
+    }
+
+    fn encode_request(
+        &self,
+        uri: &Uri,
+        function_name: &str,
+        params: Vec<WitValue>,
+        idempotency_key: Option<String>,
+    ) -> Result<(&'static str, Vec<u8>), RpcError> {
+        match &self.encoding {
+            HttpEncoding::Protobuf => {
+                let request = InvocationRequest {
+                    target: uri.clone(),
+                    function_name: function_name.to_string(),
+                    params,
+                    idempotency_key,
+                };
+                let request = protobuf::InvocationRequest::from(request);
+                Ok((PROTOBUF_CONTENT_TYPE, request.encode_to_vec()))
+            }
+            HttpEncoding::Json { exports } => {
+                let request = JsonInvocationRequest {
+                    target: uri.clone(),
+                    function_name: function_name.to_string(),
+                    params: typed_params(exports, function_name, params)?,
+                    idempotency_key,
+                };
+                let body = serde_json::to_vec(&request)
+                    .map_err(|err| RpcError::ProtocolError(err.to_string()))?;
+                Ok((JSON_CONTENT_TYPE, body))
+            }
+        }
+    }
+
+    fn encode_batch_request(
+        &self,
+        request: BatchInvocationRequest,
+    ) -> Result<(&'static str, Vec<u8>), RpcError> {
+        match &self.encoding {
+            HttpEncoding::Protobuf => {
+                let request = protobuf::BatchInvocationRequest::from(request);
+                Ok((PROTOBUF_CONTENT_TYPE, request.encode_to_vec()))
+            }
+            HttpEncoding::Json { exports } => {
+                let request = JsonBatchInvocationRequest {
+                    target: request.target,
+                    invocations: request
+                        .invocations
+                        .into_iter()
+                        .map(|invocation| {
+                            Ok(JsonBatchInvocation {
+                                params: typed_params(
+                                    exports,
+                                    &invocation.function_name,
+                                    invocation.function_params,
+                                )?,
+                                function_name: invocation.function_name,
+                            })
+                        })
+                        .collect::<Result<_, RpcError>>()?,
+                    parallel: request.options.parallel,
+                    stop_on_first_error: request.options.stop_on_first_error,
+                    idempotency_key: request.idempotency_key,
+                };
+                let body = serde_json::to_vec(&request)
+                    .map_err(|err| RpcError::ProtocolError(err.to_string()))?;
+                Ok((JSON_CONTENT_TYPE, body))
+            }
+        }
+    }
+
+    fn decode_response(&self, body: &[u8]) -> Result<WitValue, RpcError> {
+        match &self.encoding {
+            HttpEncoding::Protobuf => protobuf::WitValue::decode(body)
+                .map_err(|err| RpcError::ProtocolError(format!("Invalid response: {err}")))?
+                .try_into()
+                .map_err(RpcError::ProtocolError),
+            HttpEncoding::Json { .. } => serde_json::from_slice::<TypeAnnotatedValue>(body)
+                .map_err(|err| RpcError::ProtocolError(format!("Invalid response: {err}")))?
+                .try_into()
+                .map_err(RpcError::ProtocolError),
+        }
+    }
+
+    fn decode_batch_response(&self, body: &[u8]) -> Result<Vec<InvokeResult>, RpcError> {
+        match &self.encoding {
+            HttpEncoding::Protobuf => protobuf::BatchInvocationResponse::decode(body)
+                .map_err(|err| RpcError::ProtocolError(format!("Invalid response: {err}")))?
+                .try_into()
+                .map_err(RpcError::ProtocolError),
+            HttpEncoding::Json { .. } => serde_json::from_slice::<Vec<JsonInvocationResult>>(body)
+                .map_err(|err| RpcError::ProtocolError(format!("Invalid response: {err}")))?
+                .into_iter()
+                .map(|result| match result {
+                    JsonInvocationResult::Ok(value) => {
+                        Ok(Ok(value.try_into().map_err(RpcError::ProtocolError)?))
+                    }
+                    JsonInvocationResult::Err(error) => Ok(Err(error)),
+                })
+                .collect(),
+        }
+    }
+
+    /// Sends a request with the headers of `options`, returning the body of the response if it
+    /// was successful
+    async fn send(
+        &self,
+        uri: &Uri,
+        content_type: &str,
+        body: Vec<u8>,
+        options: &InvocationOptions,
+        batch: bool,
+    ) -> Result<Vec<u8>, RpcError> {
+        let url = (self.endpoint)(uri);
+        let mut request = self
+            .client
+            .post(&url)
+            .header(header::CONTENT_TYPE, content_type);
+        if batch {
+            request = request.header(BATCH_HEADER, "true");
+        }
+        for (name, value) in &options.headers {
+            let name = HeaderName::try_from(name)
+                .map_err(|err| RpcError::ProtocolError(format!("Invalid header {name}: {err}")))?;
+            let value = HeaderValue::try_from(value).map_err(|err| {
+                RpcError::ProtocolError(format!("Invalid value of header {name}: {err}"))
+            })?;
+            request = request.header(name, value);
+        }
+        let response = request.body(body).send().await.map_err(|err| {
+            RpcError::RemoteInternalError(format!("Failed to send invocation to {url}: {err}"))
+        })?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(|err| {
+            RpcError::RemoteInternalError(format!("Failed to receive response from {url}: {err}"))
+        })?;
+        if status.is_success() {
+            Ok(body.to_vec())
+        } else {
+            Err(error_from_status(
+                status,
+                String::from_utf8_lossy(&body).to_string(),
+            ))
+        }
+    }
+
+    async fn send_batch(
+        &self,
+        uri: Uri,
+        invocations: Vec<BatchInvocation>,
+        options: &InvocationOptions,
+        batch_options: BatchOptions,
+    ) -> Result<Vec<InvokeResult>, RpcError> {
+        let (content_type, body) = self.encode_batch_request(BatchInvocationRequest {
+            target: uri.clone(),
+            invocations,
+            options: batch_options,
+            idempotency_key: options.idempotency_key.clone(),
+        })?;
+        let body = self.send(&uri, content_type, body, options, true).await?;
+        self.decode_batch_response(&body)
+    }
+}
+
+type InvokeResult = Result<WitValue, RpcError>;
+
+/// Converts the parameters of a function to typed values using the types of its parameters
+fn typed_params(
+    exports: &[AnalysedExport],
+    function_name: &str,
+    params: Vec<WitValue>,
+) -> Result<Vec<TypeAnnotatedValue>, RpcError> {
+    let (_, function) = find_function(function_name, exports).map_err(RpcError::ProtocolError)?;
+    if function.parameters.len() != params.len() {
+        return Err(RpcError::ProtocolError(format!(
+            "Unexpected number of parameters for {function_name} (got {}, expected: {})",
+            params.len(),
+            function.parameters.len()
+        )));
+    }
+    params
+        .into_iter()
+        .zip(&function.parameters)
+        .map(|(param, parameter)| {
+            TypeAnnotatedValue::create(&Value::from(param), &parameter.typ).map_err(|errors| {
+                RpcError::ProtocolError(format!(
+                    "Invalid value for parameter {}: {}",
+                    parameter.name,
+                    errors.join(", ")
+                ))
+            })
+        })
+        .collect()
+}
+
+/// The results of a batch failing as a whole, which is a single error if the batch stops at the
+/// first one, or the same error for every invocation otherwise
+fn failed_batch(error: RpcError, count: usize, batch_options: BatchOptions) -> Vec<InvokeResult> {
+    if batch_options.stop_on_first_error {
+        vec![Err(error)]
+    } else {
+        vec![Err(error); count]
+    }
+}
+
+#[async_trait]
+impl RpcTransport for HttpTransport {
+    async fn invoke(
+        &self,
+        uri: &Uri,
+        function_name: &str,
+        params: Vec<WitValue>,
+    ) -> Result<WitValue, RpcError> {
+        self.invoke_with_options(uri, function_name, params, &InvocationOptions::default())
+            .await
+    }
+
+    async fn invoke_with_options(
+        &self,
+        uri: &Uri,
+        function_name: &str,
+        params: Vec<WitValue>,
+        options: &InvocationOptions,
+    ) -> Result<WitValue, RpcError> {
+        let (content_type, body) =
+            self.encode_request(uri, function_name, params, options.idempotency_key.clone())?;
+        let body = self.send(uri, content_type, body, options, false).await?;
+        self.decode_response(&body)
+    }
+
+    /// Sends the batch as a single request, bounded by the timeout of `options`
+    async fn invoke_batch(
+        self: Arc<Self>,
+        uri: Uri,
+        invocations: Vec<BatchInvocation>,
+        options: InvocationOptions,
+        batch_options: BatchOptions,
+    ) -> Vec<Result<WitValue, RpcError>> {
+        let count = invocations.len();
+        let batch = self.send_batch(uri, invocations, &options, batch_options);
+        let result = match options.timeout_nanos {
+            Some(timeout_nanos) => {
+                let timeout = Duration::from_nanos(timeout_nanos);
+                tokio::time::timeout(timeout, batch)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(RpcError::Timeout(format!(
+                            "Batch invocation did not finish in {timeout:?}"
+                        )))
+                    })
+            }
+            None => batch.await,
+        };
+        result.unwrap_or_else(|error| failed_batch(error, count, batch_options))
+    }
+}
+
+fn error_from_status(status: StatusCode, message: String) -> RpcError {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+            RpcError::ProtocolError(message)
+        }
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => RpcError::Denied(message),
+        StatusCode::NOT_FOUND => RpcError::NotFound(message),
+        StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => RpcError::Timeout(message),
+        _ => RpcError::RemoteInternalError(message),
+    }
+}
+
+fn status_of_error(error: &RpcError) -> StatusCode {
+    match error {
+        RpcError::ProtocolError(_) => StatusCode::BAD_REQUEST,
+        RpcError::Denied(_) => StatusCode::FORBIDDEN,
+        RpcError::NotFound(_) => StatusCode::NOT_FOUND,
+        RpcError::RemoteInternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        RpcError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+    }
+}
+
+fn error_response(error: RpcError) -> Response {
+    let status = status_of_error(&error);
+    let message = match error {
+        RpcError::ProtocolError(message)
+        | RpcError::Denied(message)
+        | RpcError::NotFound(message)
+        | RpcError::RemoteInternalError(message)
+        | RpcError::Timeout(message) => message,
+    };
+    Response::builder().status(status).body(message)
+}
+
+/// Serves the invocations sent by `HttpTransport` to the component instances of a
+/// `LocalWasmRpcHost`.
+///
+/// Requests are accepted on any path. Idempotency keys and headers are not interpreted.
+pub struct HttpRpcServer<T> {
+    host: LocalWasmRpcHost<T>,
+}
+
+impl<T: Send + 'static> HttpRpcServer<T> {
+    pub fn new(host: LocalWasmRpcHost<T>) -> Self {
+        Self { host }
+    }
+
+    /// Binds to `addr`, returning the bound address and the future serving the requests.
+    pub async fn bind(
+        self,
+        addr: &str,
+    ) -> std::io::Result<(SocketAddr, impl Future<Output = std::io::Result<()>> + Send)> {
+        let acceptor = TcpListener::bind(addr.to_string()).into_acceptor().await?;
+        let local_addr = acceptor
+            .local_addr()
+            .iter()
+            .find_map(|addr| addr.as_socket_addr().cloned())
+            .ok_or_else(|| std::io::Error::other("Listener is not bound to a socket address"))?;
+        Ok((local_addr, Server::new_with_acceptor(acceptor).run(self)))
+    }
+
+    async fn invoke_protobuf(&self, body: &[u8]) -> Result<Response, RpcError> {
+        let request: InvocationRequest = protobuf::InvocationRequest::decode(body)
+            .map_err(|err| RpcError::ProtocolError(format!("Invalid invocation request: {err}")))?
+            .try_into()
+            .map_err(RpcError::ProtocolError)?;
+        let result = self
+            .host
+            .invoke(&request.target, &request.function_name, request.params)
+            .await?;
+        Ok(Response::builder()
+            .content_type(PROTOBUF_CONTENT_TYPE)
+            .body(protobuf::WitValue::from(result).encode_to_vec()))
+    }
+
+    async fn invoke_json(&self, body: &[u8]) -> Result<Response, RpcError> {
+        let request: JsonInvocationRequest = serde_json::from_slice(body)
+            .map_err(|err| RpcError::ProtocolError(format!("Invalid invocation request: {err}")))?;
+        let params = untyped_params(request.params)?;
+        let result_types = self
+            .host
+            .result_types(&request.target, &request.function_name)
+            .await?;
+        let result = self
+            .host
+            .invoke(&request.target, &request.function_name, params)
+            .await?;
+        let result = typed_result(result, result_types)?;
+        let body =
+            serde_json::to_vec(&result).map_err(|err| RpcError::ProtocolError(err.to_string()))?;
+        Ok(Response::builder()
+            .content_type(JSON_CONTENT_TYPE)
+            .body(body))
+    }
+
+    async fn invoke_batch(
+        &self,
+        request: BatchInvocationRequest,
+    ) -> Result<Vec<InvokeResult>, RpcError> {
+        Ok(Arc::new(self.host.clone())
+            .invoke_batch(
+                request.target,
+                request.invocations,
+                InvocationOptions::default(),
+                request.options,
+            )
+            .await)
+    }
+
+    async fn invoke_batch_protobuf(&self, body: &[u8]) -> Result<Response, RpcError> {
+        let request: BatchInvocationRequest = protobuf::BatchInvocationRequest::decode(body)
+            .map_err(|err| RpcError::ProtocolError(format!("Invalid batch request: {err}")))?
+            .try_into()
+            .map_err(RpcError::ProtocolError)?;
+        let results = self.invoke_batch(request).await?;
+        Ok(Response::builder()
+            .content_type(PROTOBUF_CONTENT_TYPE)
+            .body(protobuf::BatchInvocationResponse::from(results).encode_to_vec()))
+    }
+
+    async fn invoke_batch_json(&self, body: &[u8]) -> Result<Response, RpcError> {
+        let request: JsonBatchInvocationRequest = serde_json::from_slice(body)
+            .map_err(|err| RpcError::ProtocolError(format!("Invalid batch request: {err}")))?;
+        let target = request.target;
+        let mut function_names = Vec::new();
+        let mut invocations = Vec::new();
+        for invocation in request.invocations {
+            function_names.push(invocation.function_name.clone());
+            invocations.push(BatchInvocation {
+                function_name: invocation.function_name,
+                function_params: untyped_params(invocation.params)?,
+            });
+        }
+        let results = self
+            .invoke_batch(BatchInvocationRequest {
+                target: target.clone(),
+                invocations,
+                options: BatchOptions {
+                    parallel: request.parallel,
+                    stop_on_first_error: request.stop_on_first_error,
+                },
+                idempotency_key: request.idempotency_key,
+            })
+            .await?;
+
+        let mut json_results = Vec::with_capacity(results.len());
+        for (result, function_name) in results.into_iter().zip(function_names) {
+            let result = match result {
+                Ok(value) => match self.host.result_types(&target, &function_name).await {
+                    Ok(result_types) => typed_result(value, result_types),
+                    Err(error) => Err(error),
+                },
+                Err(error) => Err(error),
+            };
+            json_results.push(match result {
+                Ok(value) => JsonInvocationResult::Ok(value),
+                Err(error) => JsonInvocationResult::Err(error),
+            });
+        }
+        let body = serde_json::to_vec(&json_results)
+            .map_err(|err| RpcError::ProtocolError(err.to_string()))?;
+        Ok(Response::builder()
+            .content_type(JSON_CONTENT_TYPE)
+            .body(body))
+    }
+}
+
+fn untyped_params(params: Vec<TypeAnnotatedValue>) -> Result<Vec<WitValue>, RpcError> {
+    params
+        .into_iter()
+        .map(WitValue::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(RpcError::ProtocolError)
+}
+
+/// Converts the result tuple of a function to a typed value
+fn typed_result(
+    result: WitValue,
+    result_types: Vec<AnalysedType>,
+) -> Result<TypeAnnotatedValue, RpcError> {
+    let typ = AnalysedType::Tuple(TypeTuple {
+        items: result_types,
+    });
+    TypeAnnotatedValue::create(&Value::from(result), &typ)
+        .map_err(|errors| RpcError::ProtocolError(errors.join(", ")))
+}
+
+impl<T: Send + 'static> Endpoint for HttpRpcServer<T> {
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> poem::Result<Response> {
+        if req.method() != Method::POST {
+            return Ok(Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .finish());
+        }
+        let is_json = req
+            .content_type()
+            .is_some_and(|content_type| content_type.starts_with(JSON_CONTENT_TYPE));
+        let is_batch = req.headers().contains_key(BATCH_HEADER);
+        let body = req.take_body().into_bytes().await?;
+        let result = match (is_batch, is_json) {
+            (false, true) => self.invoke_json(&body).await,
+            (false, false) => self.invoke_protobuf(&body).await,
+            (true, true) => self.invoke_batch_json(&body).await,
+            (true, false) => self.invoke_batch_protobuf(&body).await,
+        };
+        Ok(result.unwrap_or_else(error_response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedInstance, AnalysedType, TypeU32,
+    };
+    use poem::http::StatusCode;
+    use std::sync::Arc;
+
+    use crate::http::{
+        error_from_status, status_of_error, HttpEncoding, HttpRpcServer, HttpTransport,
+    };
+    use crate::local::tests::{calculator_host, calculator_uri};
+    use crate::transport::RpcTransport;
+    use crate::{BatchInvocation, BatchOptions, InvocationOptions, RpcError, Uri, Value};
+
+    fn calculator_exports() -> Vec<AnalysedExport> {
+        let u32 = || AnalysedType::U32(TypeU32);
+        let function = |name: &str, parameters: Vec<&str>| AnalysedFunction {
+            name: name.to_string(),
+            parameters: parameters
+                .into_iter()
+                .map(|name| AnalysedFunctionParameter {
+                    name: name.to_string(),
+                    typ: u32(),
+                })
+                .collect(),
+            results: vec![AnalysedFunctionResult {
+                name: None,
+                typ: u32(),
+            }],
+        };
+        vec![AnalysedExport::Instance(AnalysedInstance {
+            name: "golem:it/api".to_string(),
+            // `missing` is known to the clients, but not exported by the component
+            functions: vec![
+                function("add", vec!["x", "y"]),
+                function("fail", vec![]),
+                function("missing", vec![]),
+            ],
+        })]
+    }
+
+    /// Starts a server for the calculator component, returning its URL
+    async fn calculator_server() -> String {
+        let server = HttpRpcServer::new(calculator_host().await);
+        let (addr, run) = server.bind("127.0.0.1:0").await.unwrap();
+        tokio::spawn(run);
+        format!("http://{addr}/invoke")
+    }
+
+    async fn invoke(
+        transport: &HttpTransport,
+        uri: &Uri,
+        function_name: &str,
+        params: Vec<Value>,
+    ) -> Result<Value, String> {
+        transport
+            .invoke(
+                uri,
+                function_name,
+                params.into_iter().map(|param| param.into()).collect(),
+            )
+            .await
+            .map(Value::from)
+            .map_err(|err| format!("{err:?}"))
+    }
+
+    #[tokio::test]
+    async fn invoke_over_http() {
+        let url = calculator_server().await;
+        let encodings = vec![
+            HttpEncoding::Protobuf,
+            HttpEncoding::Json {
+                exports: calculator_exports(),
+            },
+        ];
+        for encoding in encodings {
+            let transport = HttpTransport::new(url.clone(), encoding);
+            assert_eq!(
+                invoke(
+                    &transport,
+                    &calculator_uri(),
+                    "golem:it/api.{add}",
+                    vec![Value::U32(40), Value::U32(2)]
+                )
+                .await,
+                Ok(Value::Tuple(vec![Value::U32(42)]))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn invocation_errors_over_http() {
+        let url = calculator_server().await;
+        let transport = HttpTransport::new(url.clone(), HttpEncoding::Protobuf);
+
+        let unknown = Uri {
+            value: "urn:worker:calculator/2".to_string(),
+        };
+        assert!(matches!(
+            transport.invoke(&unknown, "add", vec![]).await,
+            Err(RpcError::NotFound(_))
+        ));
+        assert!(matches!(
+            transport
+                .invoke(&calculator_uri(), "golem:it/api.{sub}", vec![])
+                .await,
+            Err(RpcError::NotFound(_))
+        ));
+        assert!(matches!(
+            transport
+                .invoke(&calculator_uri(), "golem:it/api.{fail}", vec![])
+                .await,
+            Err(RpcError::RemoteInternalError(_))
+        ));
+        assert!(matches!(
+            transport
+                .invoke(
+                    &calculator_uri(),
+                    "golem:it/api.{add}",
+                    vec![Value::Bool(true).into(), Value::U32(2).into()]
+                )
+                .await,
+            Err(RpcError::ProtocolError(_))
+        ));
+
+        let transport = HttpTransport::new(
+            url,
+            HttpEncoding::Json {
+                exports: calculator_exports(),
+            },
+        );
+        assert!(matches!(
+            transport
+                .invoke(
+                    &calculator_uri(),
+                    "golem:it/api.{add}",
+                    vec![Value::String("1".to_string()).into(), Value::U32(2).into()]
+                )
+                .await,
+            Err(RpcError::ProtocolError(_))
+        ));
+        assert!(matches!(
+            transport
+                .invoke(&calculator_uri(), "golem:it/api.{fail}", vec![])
+                .await,
+            Err(RpcError::RemoteInternalError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn invoke_batch_over_http() {
+        let url = calculator_server().await;
+        let invocation = |function_name: &str, params: Vec<Value>| BatchInvocation {
+            function_name: function_name.to_string(),
+            function_params: params.into_iter().map(|param| param.into()).collect(),
+        };
+        let encodings = vec![
+            HttpEncoding::Protobuf,
+            HttpEncoding::Json {
+                exports: calculator_exports(),
+            },
+        ];
+        for encoding in encodings {
+            let transport = Arc::new(HttpTransport::new(url.clone(), encoding));
+            for stop_on_first_error in [false, true] {
+                let results = transport
+                    .clone()
+                    .invoke_batch(
+                        calculator_uri(),
+                        vec![
+                            invocation("golem:it/api.{add}", vec![Value::U32(1), Value::U32(2)]),
+                            invocation("golem:it/api.{missing}", vec![]),
+                            invocation("golem:it/api.{add}", vec![Value::U32(3), Value::U32(4)]),
+                        ],
+                        InvocationOptions::default(),
+                        BatchOptions {
+                            parallel: false,
+                            stop_on_first_error,
+                        },
+                    )
+                    .await;
+                let results = results
+                    .into_iter()
+                    .map(|result| result.map(Value::from))
+                    .collect::<Vec<_>>();
+                assert_eq!(results.len(), if stop_on_first_error { 2 } else { 3 });
+                assert_eq!(
+                    results[0].as_ref().unwrap(),
+                    &Value::Tuple(vec![Value::U32(3)])
+                );
+                assert!(matches!(results[1], Err(RpcError::NotFound(_))));
+                if !stop_on_first_error {
+                    assert_eq!(
+                        results[2].as_ref().unwrap(),
+                        &Value::Tuple(vec![Value::U32(7)])
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn endpoint_derived_from_uri() {
+        let url = calculator_server().await;
+        let transport = HttpTransport::with_endpoint(
+            move |uri| format!("{url}/{}", uri.value.replace(':', "%3A")),
+            HttpEncoding::Protobuf,
+        );
+        assert_eq!(
+            invoke(
+                &transport,
+                &calculator_uri(),
+                "add",
+                vec![Value::U32(1), Value::U32(2)]
+            )
+            .await,
+            Ok(Value::Tuple(vec![Value::U32(3)]))
+        );
+    }
+
+    #[test]
+    fn errors_round_trip_through_status_codes() {
+        let errors = vec![
+            RpcError::ProtocolError("invalid".to_string()),
+            RpcError::Denied("denied".to_string()),
+            RpcError::NotFound("missing".to_string()),
+            RpcError::RemoteInternalError("failed".to_string()),
+            RpcError::Timeout("timeout".to_string()),
+        ];
+        for error in errors {
+            let message = match &error {
+                RpcError::ProtocolError(message)
+                | RpcError::Denied(message)
+                | RpcError::NotFound(message)
+                | RpcError::RemoteInternalError(message)
+                | RpcError::Timeout(message) => message.clone(),
+            };
+            let round_tripped = error_from_status(status_of_error(&error), message);
+            assert_eq!(format!("{round_tripped:?}"), format!("{error:?}"));
+        }
+        assert!(matches!(
+            error_from_status(StatusCode::UNAUTHORIZED, String::new()),
+            RpcError::Denied(_)
+        ));
+        assert!(matches!(
+            error_from_status(StatusCode::BAD_GATEWAY, String::new()),
+            RpcError::RemoteInternalError(_)
+        ));
+    }
+}
@@ -12,235 +12,475 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::str::FromStr;
 
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
 use golem_wasm_ast::analysis::{
-    AnalysedResourceId, AnalysedResourceMode, AnalysedType, NameOptionTypePair, NameTypePair,
-    TypeEnum, TypeFlags, TypeHandle, TypeList, TypeOption, TypeRecord, TypeResult, TypeTuple,
-    TypeVariant,
+    AnalysedFunctionParameter, AnalysedFunctionResult, AnalysedResourceId, AnalysedResourceMode,
+    AnalysedType, NameOptionTypePair, NameTypePair, TypeEnum, TypeFlags, TypeHandle, TypeList,
+    TypeOption, TypeRecord, TypeResult, TypeTuple, TypeVariant,
 };
 use serde_json::{Number, Value as JsonValue};
 
-use crate::json::TypeAnnotatedValueJsonExtensions;
+use crate::json::named::named_parameters_typed;
+use crate::json::{
+    JsonOptions, NonFiniteFloats, TypeAnnotatedValueJsonExtensions, VariantTagging, INFINITY, NAN,
+    NEG_INFINITY,
+};
 use crate::protobuf;
 use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
 use crate::protobuf::typed_result::ResultValue;
+use crate::{TypeAnnotatedValueConstructors, Value};
 
-// pub fn function_parameters(
-//     value: &JsonValue,
-//     expected_parameters: &[AnalysedFunctionParameter],
-// ) -> Result<Vec<Value>, Vec<String>> {
-//     let typed_values = function_parameters_typed(value, expected_parameters)?;
-//
-//     let mut errors = vec![];
-//     let mut values = vec![];
-//
-//     for typed_value in typed_values {
-//         match Value::try_from(typed_value) {
-//             Ok(value) => {
-//                 values.push(value);
-//             }
-//             Err(err) => {
-//                 errors.push(err);
-//             }
-//         }
-//     }
-//
-//     if errors.is_empty() {
-//         Ok(values)
-//     } else {
-//         Err(errors)
-//     }
-// }
-//
-// pub fn function_parameters_typed(
-//     value: &JsonValue,
-//     expected_parameters: &[AnalysedFunctionParameter],
-// ) -> Result<Vec<TypeAnnotatedValue>, Vec<String>> {
-//     let parameters = value
-//         .as_array()
-//         .ok_or(vec!["Expecting an array for fn_params".to_string()])?;
-//
-//     let mut results = vec![];
-//     let mut errors = vec![];
-//
-//     if parameters.len() == expected_parameters.len() {
-//         for (json, fp) in parameters.iter().zip(expected_parameters.iter()) {
-//             match parse_json_with_type(json, &fp.typ) {
-//                 Ok(result) => results.push(result),
-//                 Err(err) => errors.extend(err),
-//             }
-//         }
-//
-//         if errors.is_empty() {
-//             Ok(results)
-//         } else {
-//             Err(errors)
-//         }
-//     } else {
-//         Err(vec![format!(
-//             "Unexpected number of parameters (got {}, expected: {})",
-//             parameters.len(),
-//             expected_parameters.len()
-//         )])
-//     }
-// }
-//
-// pub fn function_result(
-//     values: Vec<Value>,
-//     expected_types: &[AnalysedFunctionResult],
-// ) -> Result<JsonValue, Vec<String>> {
-//     function_result_typed(values, expected_types).map(|result| result.to_json_value())
-// }
-//
+/// Parses the JSON representation of a function's parameters into values.
+///
+/// See `function_parameters_typed` for the accepted formats.
+pub fn function_parameters(
+    value: &JsonValue,
+    expected_parameters: &[AnalysedFunctionParameter],
+) -> Result<Vec<Value>, Vec<String>> {
+    to_values(function_parameters_typed(value, expected_parameters)?)
+}
 
-impl TypeAnnotatedValueJsonExtensions for TypeAnnotatedValue {
-    fn parse_with_type(json_val: &JsonValue, typ: &AnalysedType) -> Result<Self, Vec<String>> {
-        match typ {
-            AnalysedType::Bool(_) => get_bool(json_val),
-            AnalysedType::S8(_) => get_s8(json_val),
-            AnalysedType::U8(_) => get_u8(json_val),
-            AnalysedType::S16(_) => get_s16(json_val),
-            AnalysedType::U16(_) => get_u16(json_val),
-            AnalysedType::S32(_) => get_s32(json_val),
-            AnalysedType::U32(_) => get_u32(json_val),
-            AnalysedType::S64(_) => get_s64(json_val),
-            AnalysedType::U64(_) => get_u64(json_val),
-            AnalysedType::F64(_) => get_f64(json_val),
-            AnalysedType::F32(_) => get_f32(json_val),
-            AnalysedType::Chr(_) => get_char(json_val),
-            AnalysedType::Str(_) => get_string(json_val),
-            AnalysedType::Enum(TypeEnum { cases }) => get_enum(json_val, cases),
-            AnalysedType::Flags(TypeFlags { names }) => get_flag(json_val, names),
-            AnalysedType::List(TypeList { inner }) => get_list(json_val, inner),
-            AnalysedType::Option(TypeOption { inner }) => get_option(json_val, inner),
-            AnalysedType::Result(TypeResult { ok, err }) => get_result(json_val, ok, err),
-            AnalysedType::Record(TypeRecord { fields }) => get_record(json_val, fields),
-            AnalysedType::Variant(TypeVariant { cases }) => get_variant(json_val, cases),
-            AnalysedType::Tuple(TypeTuple { items }) => get_tuple(json_val, items),
-            AnalysedType::Handle(TypeHandle { resource_id, mode }) => {
-                get_handle(json_val, resource_id.clone(), mode.clone())
+/// Parses the JSON representation of a function's parameters into typed values.
+///
+/// The parameters are either given as an array in declaration order, or as an object keyed by
+/// parameter name (see `function_parameters_named_typed`). All invalid parameters are reported,
+/// not just the first one.
+pub fn function_parameters_typed(
+    value: &JsonValue,
+    expected_parameters: &[AnalysedFunctionParameter],
+) -> Result<Vec<TypeAnnotatedValue>, Vec<String>> {
+    match value {
+        JsonValue::Array(parameters) => {
+            if parameters.len() != expected_parameters.len() {
+                return Err(vec![format!(
+                    "Unexpected number of parameters (got {}, expected: {})",
+                    parameters.len(),
+                    expected_parameters.len()
+                )]);
             }
+            parse_parameters(
+                expected_parameters
+                    .iter()
+                    .zip(parameters.iter().map(Cow::Borrowed)),
+                vec![],
+            )
         }
+        JsonValue::Object(parameters) => named_parameters_typed(parameters, expected_parameters),
+        _ => Err(vec![format!(
+            "Expected an array or an object for function parameters, found {}",
+            type_description(value)
+        )]),
     }
+}
 
-    fn to_json_value(&self) -> JsonValue {
-        match self {
-            TypeAnnotatedValue::Bool(bool) => JsonValue::Bool(*bool),
-            TypeAnnotatedValue::Flags(protobuf::TypedFlags { typ: _, values }) => JsonValue::Array(
-                values
-                    .iter()
-                    .map(|x| JsonValue::String(x.clone()))
-                    .collect(),
+/// Parses each parameter with its expected type, collecting all errors in addition to the
+/// already existing ones.
+pub(super) fn parse_parameters<'a>(
+    parameters: impl IntoIterator<Item = (&'a AnalysedFunctionParameter, Cow<'a, JsonValue>)>,
+    mut errors: Vec<String>,
+) -> Result<Vec<TypeAnnotatedValue>, Vec<String>> {
+    let mut results = vec![];
+
+    for (fp, json) in parameters {
+        match TypeAnnotatedValue::parse_with_type(&json, &fp.typ) {
+            Ok(result) => results.push(result),
+            Err(err) => errors.extend(
+                err.into_iter()
+                    .map(|err| format!("Invalid value for parameter {}: {}", fp.name, err)),
             ),
-            TypeAnnotatedValue::S8(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::U8(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::S16(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::U16(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::S32(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::U32(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::S64(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::U64(value) => JsonValue::Number(Number::from(*value)),
-            TypeAnnotatedValue::F32(value) => {
-                JsonValue::Number(Number::from_f64(*value as f64).unwrap())
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(results)
+    } else {
+        Err(errors)
+    }
+}
+
+pub(super) fn to_values(typed_values: Vec<TypeAnnotatedValue>) -> Result<Vec<Value>, Vec<String>> {
+    let mut errors = vec![];
+    let mut values = vec![];
+
+    for typed_value in typed_values {
+        match Value::try_from(typed_value) {
+            Ok(value) => {
+                values.push(value);
+            }
+            Err(err) => {
+                errors.push(err);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Converts a function's result values to their JSON representation.
+///
+/// See `function_result_typed` for the shape of the result.
+pub fn function_result(
+    values: Vec<Value>,
+    expected_types: &[AnalysedFunctionResult],
+) -> Result<JsonValue, Vec<String>> {
+    function_result_typed(values, expected_types)
+        .and_then(|result| result.to_json_value().map_err(|err| vec![err]))
+}
+
+/// Converts a function's result values to a single typed value.
+///
+/// A single unnamed result is returned as is, otherwise the results are collected into a record
+/// keyed by the result names, or by their index for unnamed results.
+pub fn function_result_typed(
+    values: Vec<Value>,
+    expected_types: &[AnalysedFunctionResult],
+) -> Result<TypeAnnotatedValue, Vec<String>> {
+    if values.len() != expected_types.len() {
+        return Err(vec![format!(
+            "Unexpected number of result values (got {}, expected: {})",
+            values.len(),
+            expected_types.len()
+        )]);
+    }
+
+    match expected_types {
+        [AnalysedFunctionResult { name: None, typ }] => TypeAnnotatedValue::create(&values[0], typ),
+        _ => {
+            let fields = expected_types
+                .iter()
+                .enumerate()
+                .map(|(idx, result)| NameTypePair {
+                    name: result.name.clone().unwrap_or_else(|| idx.to_string()),
+                    typ: result.typ.clone(),
+                })
+                .collect();
+            TypeAnnotatedValue::create(
+                &Value::Record(values),
+                &AnalysedType::Record(TypeRecord { fields }),
+            )
+        }
+    }
+}
+
+impl TypeAnnotatedValueJsonExtensions for TypeAnnotatedValue {
+    fn parse_with_type_and_options(
+        json_val: &JsonValue,
+        typ: &AnalysedType,
+        options: &JsonOptions,
+    ) -> Result<Self, Vec<String>> {
+        parse_at(json_val, typ, options, Path::Root)
+    }
+
+    fn to_json_value_with_options(&self, options: &JsonOptions) -> Result<JsonValue, String> {
+        match self {
+            TypeAnnotatedValue::Bool(bool) => Ok(JsonValue::Bool(*bool)),
+            TypeAnnotatedValue::Flags(protobuf::TypedFlags { typ: _, values }) => {
+                Ok(JsonValue::Array(
+                    values
+                        .iter()
+                        .map(|x| JsonValue::String(x.clone()))
+                        .collect(),
+                ))
             }
-            TypeAnnotatedValue::F64(value) => JsonValue::Number(Number::from_f64(*value).unwrap()),
-            TypeAnnotatedValue::Char(value) => JsonValue::Number(Number::from(*value as u32)),
-            TypeAnnotatedValue::Str(value) => JsonValue::String(value.clone()),
+            TypeAnnotatedValue::S8(value) => Ok(JsonValue::Number(Number::from(*value))),
+            TypeAnnotatedValue::U8(value) => Ok(JsonValue::Number(Number::from(*value))),
+            TypeAnnotatedValue::S16(value) => Ok(JsonValue::Number(Number::from(*value))),
+            TypeAnnotatedValue::U16(value) => Ok(JsonValue::Number(Number::from(*value))),
+            TypeAnnotatedValue::S32(value) => Ok(JsonValue::Number(Number::from(*value))),
+            TypeAnnotatedValue::U32(value) => Ok(JsonValue::Number(Number::from(*value))),
+            TypeAnnotatedValue::S64(value) => {
+                if options.int64_as_string {
+                    Ok(JsonValue::String(value.to_string()))
+                } else {
+                    Ok(JsonValue::Number(Number::from(*value)))
+                }
+            }
+            TypeAnnotatedValue::U64(value) => {
+                if options.int64_as_string {
+                    Ok(JsonValue::String(value.to_string()))
+                } else {
+                    Ok(JsonValue::Number(Number::from(*value)))
+                }
+            }
+            TypeAnnotatedValue::F32(value) => float_to_json(*value as f64, options),
+            TypeAnnotatedValue::F64(value) => float_to_json(*value, options),
+            TypeAnnotatedValue::Char(value) => {
+                match char::from_u32(*value as u32).filter(|_| options.char_as_string) {
+                    Some(char) => Ok(JsonValue::String(char.to_string())),
+                    None => Ok(JsonValue::Number(Number::from(*value as u32))),
+                }
+            }
+            TypeAnnotatedValue::Str(value) => Ok(JsonValue::String(value.clone())),
             TypeAnnotatedValue::Enum(protobuf::TypedEnum { typ: _, value }) => {
-                JsonValue::String(value.clone())
+                Ok(JsonValue::String(value.clone()))
             }
             TypeAnnotatedValue::Option(option) => match &option.value {
-                Some(value) => value.clone().type_annotated_value.unwrap().to_json_value(),
-                None => JsonValue::Null,
+                Some(value) => {
+                    let inner = inner_to_json(value, "Option", options)?;
+                    if options.disambiguate_nested_options && is_option_type(&option.typ) {
+                        let mut map = serde_json::Map::new();
+                        map.insert("some".to_string(), inner);
+                        Ok(JsonValue::Object(map))
+                    } else {
+                        Ok(inner)
+                    }
+                }
+                None => Ok(JsonValue::Null),
             },
             TypeAnnotatedValue::Tuple(protobuf::TypedTuple { typ: _, value }) => {
-                let values: Vec<serde_json::Value> = value
+                let values = value
                     .iter()
-                    .map(|v| v.type_annotated_value.clone().unwrap().to_json_value())
-                    .collect();
-                JsonValue::Array(values)
+                    .map(|v| inner_to_json(v, "Tuple", options))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(JsonValue::Array(values))
             }
             TypeAnnotatedValue::List(protobuf::TypedList { typ: _, values }) => {
-                let values: Vec<serde_json::Value> = values
+                let values = values
                     .iter()
-                    .map(|v| v.type_annotated_value.clone().unwrap().to_json_value())
-                    .collect();
-                JsonValue::Array(values)
+                    .map(|v| inner_to_json(v, "List", options))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(JsonValue::Array(values))
             }
 
             TypeAnnotatedValue::Record(protobuf::TypedRecord { typ: _, value }) => {
                 let mut map = serde_json::Map::new();
                 for name_value in value {
+                    let value = name_value
+                        .value
+                        .as_ref()
+                        .ok_or_else(|| "Missing value in Record".to_string())?;
                     map.insert(
                         name_value.name.clone(),
-                        name_value
-                            .value
-                            .clone()
-                            .unwrap()
-                            .type_annotated_value
-                            .unwrap()
-                            .to_json_value(),
+                        inner_to_json(value, "Record", options)?,
                     );
                 }
-                JsonValue::Object(map)
+                Ok(JsonValue::Object(map))
             }
 
             TypeAnnotatedValue::Variant(variant) => {
-                let mut map = serde_json::Map::new();
-                map.insert(
-                    variant.case_name.clone(),
-                    variant
-                        .case_value
-                        .as_ref()
-                        .map(|x| {
-                            let value = x.clone().deref().type_annotated_value.clone().unwrap();
-                            value.to_json_value()
-                        })
-                        .unwrap_or(JsonValue::Null),
-                );
-                JsonValue::Object(map)
-            }
+                let case_value = variant
+                    .case_value
+                    .as_ref()
+                    .map(|x| inner_to_json(x, "Variant", options))
+                    .transpose()?;
 
-            TypeAnnotatedValue::Result(result0) => {
                 let mut map = serde_json::Map::new();
-
-                let result_value = result0.result_value.clone().unwrap();
-
-                match result_value {
-                    ResultValue::OkValue(value) => {
+                match &options.variant_tagging {
+                    VariantTagging::External => {
                         map.insert(
-                            "ok".to_string(),
-                            value
-                                .type_annotated_value
-                                .map_or(JsonValue::Null, |v| v.to_json_value()),
+                            variant.case_name.clone(),
+                            case_value.unwrap_or(JsonValue::Null),
                         );
                     }
-                    ResultValue::ErrorValue(value) => {
-                        map.insert(
-                            "err".to_string(),
-                            value
-                                .type_annotated_value
-                                .map_or(JsonValue::Null, |v| v.to_json_value()),
-                        );
+                    VariantTagging::Adjacent { tag, content } => {
+                        map.insert(tag.clone(), JsonValue::String(variant.case_name.clone()));
+                        if let Some(case_value) = case_value {
+                            map.insert(content.clone(), case_value);
+                        }
                     }
                 }
+                Ok(JsonValue::Object(map))
+            }
 
-                JsonValue::Object(map)
+            TypeAnnotatedValue::Result(result0) => {
+                let mut map = serde_json::Map::new();
+
+                let result_value = result0
+                    .result_value
+                    .as_ref()
+                    .ok_or_else(|| "Missing value in Result".to_string())?;
+
+                let (key, value) = match result_value {
+                    ResultValue::OkValue(value) => ("ok", value),
+                    ResultValue::ErrorValue(value) => ("err", value),
+                };
+                let value = match &value.type_annotated_value {
+                    Some(v) => v.to_json_value_with_options(options)?,
+                    None => JsonValue::Null,
+                };
+                map.insert(key.to_string(), value);
+
+                Ok(JsonValue::Object(map))
             }
 
             TypeAnnotatedValue::Handle(protobuf::TypedHandle {
                 typ: _,
                 uri,
                 resource_id,
-            }) => JsonValue::String(format!("{}/{}", uri, resource_id)),
+            }) => Ok(JsonValue::String(format!("{}/{}", uri, resource_id))),
         }
     }
 }
 
+fn inner_to_json(
+    value: &protobuf::TypeAnnotatedValue,
+    context: &str,
+    options: &JsonOptions,
+) -> Result<JsonValue, String> {
+    value
+        .type_annotated_value
+        .as_ref()
+        .ok_or_else(|| format!("Missing type_annotated_value in {}", context))?
+        .to_json_value_with_options(options)
+}
+
+fn float_to_json(value: f64, options: &JsonOptions) -> Result<JsonValue, String> {
+    match Number::from_f64(value) {
+        Some(number) => Ok(JsonValue::Number(number)),
+        None => match options.non_finite_floats {
+            NonFiniteFloats::String => Ok(JsonValue::String(non_finite_float_name(value))),
+            NonFiniteFloats::Null => Ok(JsonValue::Null),
+            NonFiniteFloats::Error => Err(format!(
+                "The value {} cannot be represented as a JSON number",
+                value
+            )),
+        },
+    }
+}
+
+pub(super) fn non_finite_float_name(value: f64) -> String {
+    if value.is_nan() {
+        NAN.to_string()
+    } else if value.is_sign_positive() {
+        INFINITY.to_string()
+    } else {
+        NEG_INFINITY.to_string()
+    }
+}
+
+pub(super) fn is_option_type(typ: &Option<protobuf::Type>) -> bool {
+    matches!(
+        typ.as_ref().and_then(|typ| typ.r#type.as_ref()),
+        Some(protobuf::r#type::Type::Option(_))
+    )
+}
+
+/// The location of a value within the parsed JSON document, used in error messages
+#[derive(Clone, Copy)]
+enum Path<'a> {
+    Root,
+    Field(&'a Path<'a>, &'a str),
+    Index(&'a Path<'a>, usize),
+}
+
+impl Path<'_> {
+    fn field<'a>(&'a self, name: &'a str) -> Path<'a> {
+        Path::Field(self, name)
+    }
+
+    fn index(&self, idx: usize) -> Path<'_> {
+        Path::Index(self, idx)
+    }
+
+    /// Prefixes errors with the path
+    fn errors(&self, errors: Vec<String>) -> Vec<String> {
+        errors
+            .into_iter()
+            .map(|err| format!("{}: {}", self, err))
+            .collect()
+    }
+
+    fn error(&self, error: String) -> Vec<String> {
+        self.errors(vec![error])
+    }
+}
+
+impl Display for Path<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Path::Root => write!(f, "$"),
+            Path::Field(parent, name) => write!(f, "{}.{}", parent, name),
+            Path::Index(parent, idx) => write!(f, "{}[{}]", parent, idx),
+        }
+    }
+}
+
+fn parse_at(
+    json_val: &JsonValue,
+    typ: &AnalysedType,
+    options: &JsonOptions,
+    path: Path,
+) -> Result<TypeAnnotatedValue, Vec<String>> {
+    let number_from_string;
+    let json_val = match json_val {
+        JsonValue::String(str) if options.numbers_as_strings && is_number_type(typ) => {
+            match Number::from_str(str.trim()) {
+                Ok(number) => {
+                    number_from_string = JsonValue::Number(number);
+                    &number_from_string
+                }
+                // might be a non-finite float or an int64_as_string value
+                Err(_) => json_val,
+            }
+        }
+        _ => json_val,
+    };
+
+    let result = match typ {
+        AnalysedType::Bool(_) => get_bool(json_val),
+        AnalysedType::S8(_) => get_s8(json_val),
+        AnalysedType::U8(_) => get_u8(json_val),
+        AnalysedType::S16(_) => get_s16(json_val),
+        AnalysedType::U16(_) => get_u16(json_val),
+        AnalysedType::S32(_) => get_s32(json_val),
+        AnalysedType::U32(_) => get_u32(json_val),
+        AnalysedType::S64(_) => get_s64(json_val, options),
+        AnalysedType::U64(_) => get_u64(json_val, options),
+        AnalysedType::F64(_) => get_f64(json_val, options),
+        AnalysedType::F32(_) => get_f32(json_val, options),
+        AnalysedType::Chr(_) => get_char(json_val, options),
+        AnalysedType::Str(_) => get_string(json_val),
+        AnalysedType::Enum(TypeEnum { cases }) => get_enum(json_val, cases, options),
+        AnalysedType::Flags(TypeFlags { names }) => get_flag(json_val, names, options),
+        AnalysedType::Handle(TypeHandle { resource_id, mode }) => {
+            get_handle(json_val, resource_id.clone(), mode.clone())
+        }
+        // composite values report errors with the path of the offending nested value
+        AnalysedType::List(TypeList { inner }) => return get_list(json_val, inner, options, path),
+        AnalysedType::Option(TypeOption { inner }) => {
+            return get_option(json_val, inner, options, path)
+        }
+        AnalysedType::Result(TypeResult { ok, err }) => {
+            return get_result(json_val, ok, err, options, path)
+        }
+        AnalysedType::Record(TypeRecord { fields }) => {
+            return get_record(json_val, fields, options, path)
+        }
+        AnalysedType::Variant(TypeVariant { cases }) => {
+            return get_variant(json_val, cases, options, path)
+        }
+        AnalysedType::Tuple(TypeTuple { items }) => {
+            return get_tuple(json_val, items, options, path)
+        }
+    };
+    result.map_err(|errors| path.errors(errors))
+}
+
+fn is_number_type(typ: &AnalysedType) -> bool {
+    matches!(
+        typ,
+        AnalysedType::S8(_)
+            | AnalysedType::U8(_)
+            | AnalysedType::S16(_)
+            | AnalysedType::U16(_)
+            | AnalysedType::S32(_)
+            | AnalysedType::U32(_)
+            | AnalysedType::S64(_)
+            | AnalysedType::U64(_)
+            | AnalysedType::F32(_)
+            | AnalysedType::F64(_)
+    )
+}
+
 fn get_bool(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
     match json {
         JsonValue::Bool(bool_val) => Ok(TypeAnnotatedValue::Bool(*bool_val)),
@@ -308,7 +548,13 @@ fn get_u32(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
     .map(|num| TypeAnnotatedValue::U32(num.to_u32().expect("Failed to convert BigDecimal to u32")))
 }
 
-fn get_s64(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_s64(json: &JsonValue, options: &JsonOptions) -> Result<TypeAnnotatedValue, Vec<String>> {
+    if let (true, Some(str)) = (options.int64_as_string, json.as_str()) {
+        return i64::from_str(str)
+            .map(TypeAnnotatedValue::S64)
+            .map_err(|err| vec![format!("Cannot convert {} to s64: {}", str, err)]);
+    }
+
     ensure_range(
         json,
         BigDecimal::from_i64(i64::MIN).expect("Failed to convert i64::MIN to BigDecimal"),
@@ -317,7 +563,11 @@ fn get_s64(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
     .map(|num| TypeAnnotatedValue::S64(num.to_i64().expect("Failed to convert BigDecimal to i64")))
 }
 
-fn get_f32(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_f32(json: &JsonValue, options: &JsonOptions) -> Result<TypeAnnotatedValue, Vec<String>> {
+    if let Some(value) = get_non_finite_float(json, options) {
+        return Ok(TypeAnnotatedValue::F32(value as f32));
+    }
+
     ensure_range(
         json,
         BigDecimal::from_f32(f32::MIN).expect("Failed to convert f32::MIN to BigDecimal"),
@@ -326,7 +576,11 @@ fn get_f32(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
     .map(|num| TypeAnnotatedValue::F32(num.to_f32().expect("Failed to convert BigDecimal to f32")))
 }
 
-fn get_f64(json_val: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_f64(json_val: &JsonValue, options: &JsonOptions) -> Result<TypeAnnotatedValue, Vec<String>> {
+    if let Some(value) = get_non_finite_float(json_val, options) {
+        return Ok(TypeAnnotatedValue::F64(value));
+    }
+
     let num = get_big_decimal(json_val)?;
     let value = TypeAnnotatedValue::F64(
         num.to_string()
@@ -336,6 +590,16 @@ fn get_f64(json_val: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
     Ok(value)
 }
 
+fn get_non_finite_float(json: &JsonValue, options: &JsonOptions) -> Option<f64> {
+    match json {
+        JsonValue::String(str) if str == NAN => Some(f64::NAN),
+        JsonValue::String(str) if str == INFINITY => Some(f64::INFINITY),
+        JsonValue::String(str) if str == NEG_INFINITY => Some(f64::NEG_INFINITY),
+        JsonValue::Null if options.non_finite_floats == NonFiniteFloats::Null => Some(f64::NAN),
+        _ => None,
+    }
+}
+
 fn get_string(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
     if let Some(str_value) = json.as_str() {
         // If the JSON value is a string, return it
@@ -350,7 +614,18 @@ fn get_string(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
     }
 }
 
-fn get_char(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_char(json: &JsonValue, options: &JsonOptions) -> Result<TypeAnnotatedValue, Vec<String>> {
+    if let (true, Some(str)) = (options.char_as_string, json.as_str()) {
+        let mut chars = str.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(char), None) => Ok(TypeAnnotatedValue::Char(char as i32)),
+            _ => Err(vec![format!(
+                "Expected a string with a single character, but found {:?}",
+                str
+            )]),
+        };
+    }
+
     if let Some(num_u64) = json.as_u64() {
         if num_u64 > u32::MAX as u64 {
             Err(vec![format!(
@@ -373,41 +648,33 @@ fn get_char(json: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
 fn get_tuple(
     input_json: &JsonValue,
     types: &[AnalysedType],
+    options: &JsonOptions,
+    path: Path,
 ) -> Result<TypeAnnotatedValue, Vec<String>> {
-    let json_array = input_json.as_array().ok_or(vec![format!(
+    let json_array = input_json.as_array().ok_or(path.error(format!(
         "Input {} is not an array representing tuple",
         input_json
-    )])?;
+    )))?;
 
     if json_array.len() != types.len() {
-        return Err(vec![format!(
+        return Err(path.error(format!(
             "The length of types in template is not equal to the length of tuple (array) in  {}",
             input_json,
-        )]);
+        )));
     }
 
     let mut errors: Vec<String> = vec![];
     let mut vals: Vec<TypeAnnotatedValue> = vec![];
 
-    for (json, tpe) in json_array.iter().zip(types.iter()) {
-        match TypeAnnotatedValue::parse_with_type(json, tpe) {
+    for (idx, (json, tpe)) in json_array.iter().zip(types.iter()).enumerate() {
+        match parse_at(json, tpe, options, path.index(idx)) {
             Ok(result) => vals.push(result),
             Err(errs) => errors.extend(errs),
         }
     }
 
-    let tuple = protobuf::TypedTuple {
-        typ: types.iter().map(|t| t.into()).collect(),
-        value: vals
-            .iter()
-            .map(|v| protobuf::TypeAnnotatedValue {
-                type_annotated_value: Some(v.clone()),
-            })
-            .collect(),
-    };
-
     if errors.is_empty() {
-        Ok(TypeAnnotatedValue::Tuple(tuple))
+        Ok(typed_tuple(types, vals))
     } else {
         Err(errors)
     }
@@ -416,215 +683,212 @@ fn get_tuple(
 fn get_option(
     input_json: &JsonValue,
     tpe: &AnalysedType,
+    options: &JsonOptions,
+    path: Path,
 ) -> Result<TypeAnnotatedValue, Vec<String>> {
-    match input_json.as_null() {
-        Some(_) => {
-            let option = protobuf::TypedOption {
-                typ: Some(tpe.into()),
-                value: None,
-            };
+    let nested = options.disambiguate_nested_options && matches!(tpe, AnalysedType::Option(_));
+
+    let inner_json = match input_json {
+        JsonValue::Null => None,
+        _ if nested => Some(
+            input_json
+                .as_object()
+                .filter(|obj| obj.len() == 1)
+                .and_then(|obj| obj.get("some"))
+                .ok_or(path.error(format!(
+                    "Input {} is not null or a {{\"some\": ...}} object representing a nested option",
+                    input_json
+                )))?,
+        ),
+        _ => Some(input_json),
+    };
 
-            Ok(TypeAnnotatedValue::Option(Box::new(option)))
+    match inner_json {
+        None => Ok(typed_option(tpe, None)),
+        Some(inner_json) => {
+            parse_at(inner_json, tpe, options, path).map(|result| typed_option(tpe, Some(result)))
         }
-
-        None => TypeAnnotatedValue::parse_with_type(input_json, tpe).map(|result| {
-            let option = protobuf::TypedOption {
-                typ: Some(tpe.into()),
-                value: Some(Box::new(protobuf::TypeAnnotatedValue {
-                    type_annotated_value: Some(result),
-                })),
-            };
-
-            TypeAnnotatedValue::Option(Box::new(option))
-        }),
     }
 }
 
-fn get_list(input_json: &JsonValue, tpe: &AnalysedType) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_list(
+    input_json: &JsonValue,
+    tpe: &AnalysedType,
+    options: &JsonOptions,
+    path: Path,
+) -> Result<TypeAnnotatedValue, Vec<String>> {
     let json_array = input_json
         .as_array()
-        .ok_or(vec![format!("Input {} is not an array", input_json)])?;
+        .ok_or(path.error(format!("Input {} is not an array", input_json)))?;
 
     let mut errors: Vec<String> = vec![];
     let mut vals: Vec<TypeAnnotatedValue> = vec![];
 
-    for json in json_array {
-        match TypeAnnotatedValue::parse_with_type(json, tpe) {
+    for (idx, json) in json_array.iter().enumerate() {
+        match parse_at(json, tpe, options, path.index(idx)) {
             Ok(result) => vals.push(result),
             Err(errs) => errors.extend(errs),
         }
     }
 
-    let list = protobuf::TypedList {
-        typ: Some(tpe.into()),
-        values: vals
-            .iter()
-            .map(|v| protobuf::TypeAnnotatedValue {
-                type_annotated_value: Some(v.clone()),
-            })
-            .collect(),
-    };
-
     if errors.is_empty() {
-        Ok(TypeAnnotatedValue::List(list))
+        Ok(typed_list(tpe, vals))
     } else {
         Err(errors)
     }
 }
 
-fn get_enum(input_json: &JsonValue, names: &[String]) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_enum(
+    input_json: &JsonValue,
+    names: &[String],
+    options: &JsonOptions,
+) -> Result<TypeAnnotatedValue, Vec<String>> {
     let input_enum_value = input_json
         .as_str()
         .ok_or(vec![format!("Input {} is not string", input_json)])?;
 
-    let enum_value = protobuf::TypedEnum {
-        typ: names.to_vec(),
-        value: input_enum_value.to_string(),
-    };
-    if names.contains(&input_enum_value.to_string()) {
-        Ok(TypeAnnotatedValue::Enum(enum_value))
-    } else {
-        Err(vec![format!(
+    match find_name(names, input_enum_value, options) {
+        Some(name) => Ok(TypeAnnotatedValue::Enum(protobuf::TypedEnum {
+            typ: names.to_vec(),
+            value: name.clone(),
+        })),
+        None => Err(vec![format!(
             "Invalid input {}. Valid values are {}",
             input_enum_value,
             names.join(",")
-        )])
+        )]),
     }
 }
 
+fn find_name<'a>(names: &'a [String], name: &str, options: &JsonOptions) -> Option<&'a String> {
+    names.iter().find(|candidate| {
+        if options.case_insensitive_names {
+            candidate.eq_ignore_ascii_case(name)
+        } else {
+            *candidate == name
+        }
+    })
+}
+
 #[allow(clippy::type_complexity)]
 fn get_result(
     input_json: &JsonValue,
     ok_type: &Option<Box<AnalysedType>>,
     err_type: &Option<Box<AnalysedType>>,
+    options: &JsonOptions,
+    path: Path,
 ) -> Result<TypeAnnotatedValue, Vec<String>> {
     fn validate(
         typ: &Option<Box<AnalysedType>>,
         input_json: &JsonValue,
-    ) -> Result<Option<Box<TypeAnnotatedValue>>, Vec<String>> {
+        options: &JsonOptions,
+        path: Path,
+    ) -> Result<Option<TypeAnnotatedValue>, Vec<String>> {
         if let Some(typ) = typ {
-            TypeAnnotatedValue::parse_with_type(input_json, typ).map(|v| Some(Box::new(v)))
+            parse_at(input_json, typ, options, path).map(Some)
         } else if input_json.is_null() {
             Ok(None)
         } else {
-            Err(vec![
-                "The type of ok is absent, but some JSON value was provided".to_string(),
-            ])
+            Err(path
+                .error("The type of ok is absent, but some JSON value was provided".to_string()))
+        }
+    }
+
+    if options.deny_unknown_fields {
+        if let Some(obj) = input_json.as_object() {
+            check_known_fields(obj, &["ok", "err"], path)?;
         }
     }
 
+    if input_json.get("ok").is_some() && input_json.get("err").is_some() {
+        return Err(path.error("Result has both ok value and err value".to_string()));
+    }
+
     match input_json.get("ok") {
         Some(value) => {
-            let value = validate(ok_type, value)?;
-
-            let result_value = value.map(|value| {
-                ResultValue::OkValue(Box::new(protobuf::TypeAnnotatedValue {
-                    type_annotated_value: Some(value.deref().clone()),
-                }))
-            });
-
-            let typed_result = protobuf::TypedResult {
-                ok: ok_type.clone().map(|x| x.deref().into()),
-                error: err_type.clone().map(|x| x.deref().into()),
-                result_value,
-            };
-
-            Ok(TypeAnnotatedValue::Result(Box::new(typed_result)))
+            let value = validate(ok_type, value, options, path.field("ok"))?;
+            Ok(typed_result(ok_type, err_type, Ok(value)))
         }
         None => match input_json.get("err") {
             Some(value) => {
-                let value = validate(err_type, value)?;
-
-                let result_value = value.map(|value| {
-                    ResultValue::ErrorValue(Box::new(protobuf::TypeAnnotatedValue {
-                        type_annotated_value: Some(value.deref().clone()),
-                    }))
-                });
-
-                let typed_result = protobuf::TypedResult {
-                    ok: ok_type.clone().map(|x| x.deref().into()),
-                    error: err_type.clone().map(|x| x.deref().into()),
-                    result_value,
-                };
-
-                Ok(TypeAnnotatedValue::Result(Box::new(typed_result)))
+                let value = validate(err_type, value, options, path.field("err"))?;
+                Ok(typed_result(ok_type, err_type, Err(value)))
             }
-            None => Err(vec![
-                "Failed to retrieve either ok value or err value".to_string()
-            ]),
+            None => Err(path.error("Failed to retrieve either ok value or err value".to_string())),
         },
     }
 }
 
+fn check_known_fields(
+    obj: &serde_json::Map<String, JsonValue>,
+    known: &[&str],
+    path: Path,
+) -> Result<(), Vec<String>> {
+    let errors: Vec<String> = obj
+        .keys()
+        .filter(|key| !known.contains(&key.as_str()))
+        .flat_map(|key| path.field(key).error("unknown field".to_string()))
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 fn get_record(
     input_json: &JsonValue,
     name_type_pairs: &[NameTypePair],
+    options: &JsonOptions,
+    path: Path,
 ) -> Result<TypeAnnotatedValue, Vec<String>> {
-    let json_map = input_json.as_object().ok_or(vec![format!(
-        "The input {} is not a json object",
-        input_json
-    )])?;
+    let json_map = input_json
+        .as_object()
+        .ok_or(path.error(format!("The input {} is not a json object", input_json)))?;
 
     let mut errors: Vec<String> = vec![];
     let mut vals: Vec<(String, TypeAnnotatedValue)> = vec![];
 
+    if options.deny_unknown_fields {
+        let known: Vec<&str> = name_type_pairs
+            .iter()
+            .map(|pair| pair.name.as_str())
+            .collect();
+        if let Err(errs) = check_known_fields(json_map, &known, path) {
+            errors.extend(errs);
+        }
+    }
+
     for NameTypePair { name, typ } in name_type_pairs {
         if let Some(json_value) = json_map.get(name) {
-            match TypeAnnotatedValue::parse_with_type(json_value, typ) {
+            match parse_at(json_value, typ, options, path.field(name)) {
                 Ok(result) => vals.push((name.clone(), result)),
-                Err(value_errors) => errors.extend(
-                    value_errors
-                        .iter()
-                        .map(|err| format!("Invalid value for the key {}. Error: {}", name, err))
-                        .collect::<Vec<_>>(),
-                ),
+                Err(value_errors) => errors.extend(value_errors),
             }
         } else {
             match typ {
-                AnalysedType::Option(_) => {
-                    let option = protobuf::TypedOption {
-                        typ: Some(typ.into()),
-                        value: None,
-                    };
-
-                    vals.push((name.clone(), TypeAnnotatedValue::Option(Box::new(option))))
+                AnalysedType::Option(TypeOption { inner })
+                    if options.missing_option_fields_as_none =>
+                {
+                    vals.push((name.clone(), typed_option(inner, None)))
                 }
-                _ => errors.push(format!("Key '{}' not found in json_map", name)),
+                _ => errors.extend(path.field(name).error("missing field".to_string())),
             }
         }
     }
 
     if errors.is_empty() {
-        let name_type_pairs = name_type_pairs
-            .iter()
-            .map(|pair| protobuf::NameTypePair {
-                name: pair.name.clone(),
-                typ: Some((&pair.typ).into()),
-            })
-            .collect::<Vec<_>>();
-
-        let name_value_pairs = vals
-            .iter()
-            .map(|(name, value)| protobuf::NameValuePair {
-                name: name.clone(),
-                value: Some(protobuf::TypeAnnotatedValue {
-                    type_annotated_value: Some(value.clone()),
-                }),
-            })
-            .collect::<Vec<_>>();
-
-        let record = protobuf::TypedRecord {
-            typ: name_type_pairs,
-            value: name_value_pairs,
-        };
-
-        Ok(TypeAnnotatedValue::Record(record))
+        Ok(typed_record(name_type_pairs, vals))
     } else {
         Err(errors)
     }
 }
 
-fn get_flag(input_json: &JsonValue, names: &[String]) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_flag(
+    input_json: &JsonValue,
+    names: &[String],
+    options: &JsonOptions,
+) -> Result<TypeAnnotatedValue, Vec<String>> {
     let json_array = input_json
         .as_array()
         .ok_or(vec![format!("Input {} is not an array", input_json)])?;
@@ -643,14 +907,13 @@ fn get_flag(input_json: &JsonValue, names: &[String]) -> Result<TypeAnnotatedVal
                 json
             )])?;
 
-        if names.contains(&flag) {
-            vals.push(flag);
-        } else {
-            errors.push(format!(
+        match find_name(names, &flag, options) {
+            Some(name) => vals.push(name.clone()),
+            None => errors.push(format!(
                 "Invalid input {}. Valid values are {}",
                 flag,
                 names.join(",")
-            ));
+            )),
         }
     }
 
@@ -668,70 +931,80 @@ fn get_flag(input_json: &JsonValue, names: &[String]) -> Result<TypeAnnotatedVal
 fn get_variant(
     input_json: &JsonValue,
     types: &[NameOptionTypePair],
+    options: &JsonOptions,
+    path: Path,
 ) -> Result<TypeAnnotatedValue, Vec<String>> {
-    let mut possible_mapping_indexed: HashMap<&String, &Option<AnalysedType>> = HashMap::new();
-
-    for NameOptionTypePair {
-        name,
-        typ: optional_type,
-    } in types.iter()
-    {
-        possible_mapping_indexed.insert(name, optional_type);
-    }
-
     let json_obj = input_json
         .as_object()
-        .ok_or(vec![format!("Input {} is not an object", input_json)])?;
-
-    let (key, json) = if json_obj.is_empty() {
-        Err(vec!["Zero variants in in the input".to_string()])
-    } else {
-        Ok(json_obj.iter().next().unwrap())
+        .ok_or(path.error(format!("Input {} is not an object", input_json)))?;
+
+    let (key, json) = match &options.variant_tagging {
+        // keys not naming a case are unknown fields
+        VariantTagging::External => {
+            let mut case_keys = json_obj
+                .iter()
+                .filter(|(key, _)| find_case(types, key, options).is_some());
+            match (case_keys.next(), case_keys.next()) {
+                (Some(entry), None) => {
+                    if options.deny_unknown_fields {
+                        check_known_fields(json_obj, &[entry.0], path)?;
+                    }
+                    Ok(entry)
+                }
+                (Some(_), Some(_)) => {
+                    Err(path.error("More than one variant case in the input".to_string()))
+                }
+                (None, _) => match json_obj.keys().next() {
+                    Some(key) => Err(path.error(format!("Unknown key {key} in the variant"))),
+                    None => Err(path.error("Zero variants in in the input".to_string())),
+                },
+            }
+        }
+        VariantTagging::Adjacent { tag, content } => {
+            if options.deny_unknown_fields {
+                check_known_fields(json_obj, &[tag, content], path)?;
+            }
+            match json_obj.get(tag) {
+                Some(JsonValue::String(case_name)) => {
+                    Ok((case_name, json_obj.get(content).unwrap_or(&JsonValue::Null)))
+                }
+                _ => Err(path.error(format!(
+                    "Input {} has no string field {} holding the variant case name",
+                    input_json, tag
+                ))),
+            }
+        }
     }?;
 
-    match possible_mapping_indexed.get(key) {
-        Some(Some(tpe)) => {
-            let result = TypeAnnotatedValue::parse_with_type(json, tpe)?;
-            let variant = protobuf::TypedVariant {
-                typ: Some(protobuf::TypeVariant {
-                    cases: types
-                        .iter()
-                        .map(|pair| protobuf::NameOptionTypePair {
-                            name: pair.name.clone(),
-                            typ: pair.typ.as_ref().map(|t| t.into()),
-                        })
-                        .collect(),
-                }),
-                case_name: key.clone(),
-                case_value: Some(Box::new(protobuf::TypeAnnotatedValue {
-                    type_annotated_value: Some(result),
-                })),
-            };
+    let case = find_case(types, key, options).map(|case| (&case.name, &case.typ));
 
-            Ok(TypeAnnotatedValue::Variant(Box::new(variant)))
+    match case {
+        Some((name, Some(tpe))) => {
+            let result = parse_at(json, tpe, options, path.field(name))?;
+            Ok(typed_variant(types, name.clone(), Some(result)))
         }
-        Some(None) if json.is_null() => {
-            let variant = protobuf::TypedVariant {
-                typ: Some(protobuf::TypeVariant {
-                    cases: types
-                        .iter()
-                        .map(|pair| protobuf::NameOptionTypePair {
-                            name: pair.name.clone(),
-                            typ: pair.typ.as_ref().map(|t| t.into()),
-                        })
-                        .collect(),
-                }),
-                case_name: key.clone(),
-                case_value: None,
-            };
-
-            Ok(TypeAnnotatedValue::Variant(Box::new(variant)))
+        Some((name, None)) if json.is_null() => Ok(typed_variant(types, name.clone(), None)),
+        Some((name, None)) => {
+            Err(path.error(format!("Unit variant {name} has non-null JSON value")))
         }
-        Some(None) => Err(vec![format!("Unit variant {key} has non-null JSON value")]),
-        None => Err(vec![format!("Unknown key {key} in the variant")]),
+        None => Err(path.error(format!("Unknown key {key} in the variant"))),
     }
 }
 
+pub(super) fn find_case<'a>(
+    cases: &'a [NameOptionTypePair],
+    case_name: &str,
+    options: &JsonOptions,
+) -> Option<&'a NameOptionTypePair> {
+    cases.iter().find(|case| {
+        if options.case_insensitive_names {
+            case.name.eq_ignore_ascii_case(case_name)
+        } else {
+            case.name == case_name
+        }
+    })
+}
+
 fn get_handle(
     value: &JsonValue,
     id: AnalysedResourceId,
@@ -777,6 +1050,106 @@ fn get_handle(
     }
 }
 
+pub(super) fn typed_tuple(
+    types: &[AnalysedType],
+    values: Vec<TypeAnnotatedValue>,
+) -> TypeAnnotatedValue {
+    TypeAnnotatedValue::Tuple(protobuf::TypedTuple {
+        typ: types.iter().map(|t| t.into()).collect(),
+        value: values.into_iter().map(wrap).collect(),
+    })
+}
+
+pub(super) fn typed_list(
+    tpe: &AnalysedType,
+    values: Vec<TypeAnnotatedValue>,
+) -> TypeAnnotatedValue {
+    TypeAnnotatedValue::List(protobuf::TypedList {
+        typ: Some(tpe.into()),
+        values: values.into_iter().map(wrap).collect(),
+    })
+}
+
+pub(super) fn typed_option(
+    tpe: &AnalysedType,
+    value: Option<TypeAnnotatedValue>,
+) -> TypeAnnotatedValue {
+    TypeAnnotatedValue::Option(Box::new(protobuf::TypedOption {
+        typ: Some(tpe.into()),
+        value: value.map(|value| Box::new(wrap(value))),
+    }))
+}
+
+pub(super) fn typed_result(
+    ok_type: &Option<Box<AnalysedType>>,
+    err_type: &Option<Box<AnalysedType>>,
+    value: Result<Option<TypeAnnotatedValue>, Option<TypeAnnotatedValue>>,
+) -> TypeAnnotatedValue {
+    // unit cases are represented by a missing inner value, like in `TypeAnnotatedValue::create`
+    let result_value = match value {
+        Ok(value) => ResultValue::OkValue(Box::new(protobuf::TypeAnnotatedValue {
+            type_annotated_value: value,
+        })),
+        Err(value) => ResultValue::ErrorValue(Box::new(protobuf::TypeAnnotatedValue {
+            type_annotated_value: value,
+        })),
+    };
+
+    TypeAnnotatedValue::Result(Box::new(protobuf::TypedResult {
+        ok: ok_type.as_ref().map(|x| x.deref().into()),
+        error: err_type.as_ref().map(|x| x.deref().into()),
+        result_value: Some(result_value),
+    }))
+}
+
+pub(super) fn typed_record(
+    fields: &[NameTypePair],
+    values: Vec<(String, TypeAnnotatedValue)>,
+) -> TypeAnnotatedValue {
+    TypeAnnotatedValue::Record(protobuf::TypedRecord {
+        typ: fields
+            .iter()
+            .map(|pair| protobuf::NameTypePair {
+                name: pair.name.clone(),
+                typ: Some((&pair.typ).into()),
+            })
+            .collect(),
+        value: values
+            .into_iter()
+            .map(|(name, value)| protobuf::NameValuePair {
+                name,
+                value: Some(wrap(value)),
+            })
+            .collect(),
+    })
+}
+
+pub(super) fn typed_variant(
+    cases: &[NameOptionTypePair],
+    case_name: String,
+    value: Option<TypeAnnotatedValue>,
+) -> TypeAnnotatedValue {
+    TypeAnnotatedValue::Variant(Box::new(protobuf::TypedVariant {
+        typ: Some(protobuf::TypeVariant {
+            cases: cases
+                .iter()
+                .map(|pair| protobuf::NameOptionTypePair {
+                    name: pair.name.clone(),
+                    typ: pair.typ.as_ref().map(|t| t.into()),
+                })
+                .collect(),
+        }),
+        case_name,
+        case_value: value.map(|value| Box::new(wrap(value))),
+    }))
+}
+
+fn wrap(value: TypeAnnotatedValue) -> protobuf::TypeAnnotatedValue {
+    protobuf::TypeAnnotatedValue {
+        type_annotated_value: Some(value),
+    }
+}
+
 fn type_description(value: &JsonValue) -> &'static str {
     match value {
         JsonValue::Null => "Null",
@@ -823,8 +1196,11 @@ fn get_big_decimal(value: &JsonValue) -> Result<BigDecimal, Vec<String>> {
     }
 }
 
-fn get_u64(value: &JsonValue) -> Result<TypeAnnotatedValue, Vec<String>> {
+fn get_u64(value: &JsonValue, options: &JsonOptions) -> Result<TypeAnnotatedValue, Vec<String>> {
     match value {
+        JsonValue::String(str) if options.int64_as_string => u64::from_str(str)
+            .map(TypeAnnotatedValue::U64)
+            .map_err(|err| vec![format!("Cannot convert {} to u64: {}", str, err)]),
         JsonValue::Number(num) => {
             if let Some(u64) = num.as_u64() {
                 Ok(TypeAnnotatedValue::U64(u64))
@@ -847,14 +1223,18 @@ mod tests {
     use std::collections::HashSet;
 
     use golem_wasm_ast::analysis::{
-        AnalysedType, NameOptionTypePair, NameTypePair, TypeBool, TypeChr, TypeEnum, TypeF32,
-        TypeF64, TypeFlags, TypeList, TypeOption, TypeRecord, TypeResult, TypeS16, TypeS32,
-        TypeS64, TypeS8, TypeStr, TypeTuple, TypeU16, TypeU32, TypeU64, TypeU8, TypeVariant,
+        AnalysedFunctionParameter, AnalysedFunctionResult, AnalysedType, NameOptionTypePair,
+        NameTypePair, TypeBool, TypeChr, TypeEnum, TypeF32, TypeF64, TypeFlags, TypeList,
+        TypeOption, TypeRecord, TypeResult, TypeS16, TypeS32, TypeS64, TypeS8, TypeStr, TypeTuple,
+        TypeU16, TypeU32, TypeU64, TypeU8, TypeVariant,
     };
     use proptest::prelude::*;
     use serde_json::{Number, Value as JsonValue};
 
-    use crate::json::TypeAnnotatedValueJsonExtensions;
+    use crate::json::{
+        function_parameters, function_result, JsonOptions, NonFiniteFloats,
+        TypeAnnotatedValueJsonExtensions, VariantTagging,
+    };
     use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
     use crate::{TypeAnnotatedValueConstructors, Value};
 
@@ -862,7 +1242,8 @@ mod tests {
         val: Value,
         expected_type: &AnalysedType,
     ) -> Result<JsonValue, Vec<String>> {
-        TypeAnnotatedValue::create(&val, expected_type).map(|result| result.to_json_value())
+        TypeAnnotatedValue::create(&val, expected_type)
+            .and_then(|result| result.to_json_value().map_err(|err| vec![err]))
     }
 
     fn validate_function_parameter(
@@ -1327,4 +1708,391 @@ mod tests {
             ]))
         );
     }
+
+    fn round_trip_with_options(
+        value: Value,
+        typ: &AnalysedType,
+        options: &JsonOptions,
+    ) -> (JsonValue, Value) {
+        let json = TypeAnnotatedValue::create(&value, typ)
+            .unwrap()
+            .to_json_value_with_options(options)
+            .unwrap();
+        let parsed = TypeAnnotatedValue::parse_with_type_and_options(&json, typ, options).unwrap();
+        (json, Value::try_from(parsed).unwrap())
+    }
+
+    #[test]
+    fn char_as_string() {
+        let options = JsonOptions {
+            char_as_string: true,
+            ..JsonOptions::default()
+        };
+        let (json, value) =
+            round_trip_with_options(Value::Char('x'), &AnalysedType::Chr(TypeChr), &options);
+        assert_eq!(json, JsonValue::String("x".to_string()));
+        assert_eq!(value, Value::Char('x'));
+    }
+
+    #[test]
+    fn int64_as_string() {
+        let options = JsonOptions {
+            int64_as_string: true,
+            ..JsonOptions::default()
+        };
+        let (json, value) =
+            round_trip_with_options(Value::U64(u64::MAX), &AnalysedType::U64(TypeU64), &options);
+        assert_eq!(json, JsonValue::String(u64::MAX.to_string()));
+        assert_eq!(value, Value::U64(u64::MAX));
+
+        let (json, value) =
+            round_trip_with_options(Value::S64(i64::MIN), &AnalysedType::S64(TypeS64), &options);
+        assert_eq!(json, JsonValue::String(i64::MIN.to_string()));
+        assert_eq!(value, Value::S64(i64::MIN));
+    }
+
+    #[test]
+    fn non_finite_floats_round_trip() {
+        for value in [f64::INFINITY, f64::NEG_INFINITY] {
+            let (json, result) = round_trip_with_options(
+                Value::F64(value),
+                &AnalysedType::F64(TypeF64),
+                &JsonOptions::default(),
+            );
+            assert_eq!(
+                json,
+                JsonValue::String(value.to_string().replace("inf", "Infinity"))
+            );
+            assert_eq!(result, Value::F64(value));
+        }
+
+        let json = TypeAnnotatedValue::F32(f32::NAN).to_json_value().unwrap();
+        assert_eq!(json, JsonValue::String("NaN".to_string()));
+        let parsed = TypeAnnotatedValue::parse_with_type(&json, &AnalysedType::F32(TypeF32));
+        assert!(matches!(parsed, Ok(TypeAnnotatedValue::F32(value)) if value.is_nan()));
+    }
+
+    #[test]
+    fn non_finite_floats_as_null() {
+        let options = JsonOptions {
+            non_finite_floats: NonFiniteFloats::Null,
+            ..JsonOptions::default()
+        };
+        let json = TypeAnnotatedValue::F64(f64::INFINITY)
+            .to_json_value_with_options(&options)
+            .unwrap();
+        assert_eq!(json, JsonValue::Null);
+
+        let parsed = TypeAnnotatedValue::parse_with_type_and_options(
+            &json,
+            &AnalysedType::F64(TypeF64),
+            &options,
+        );
+        assert!(matches!(parsed, Ok(TypeAnnotatedValue::F64(value)) if value.is_nan()));
+
+        // within an option, `null` is `none`
+        let typ = AnalysedType::Option(TypeOption {
+            inner: Box::new(AnalysedType::F64(TypeF64)),
+        });
+        let json =
+            TypeAnnotatedValue::create(&Value::Option(Some(Box::new(Value::F64(f64::NAN)))), &typ)
+                .unwrap()
+                .to_json_value_with_options(&options)
+                .unwrap();
+        assert_eq!(json, JsonValue::Null);
+        let parsed = TypeAnnotatedValue::parse_with_type_and_options(&json, &typ, &options)
+            .map(|value| Value::try_from(value).unwrap());
+        assert_eq!(parsed, Ok(Value::Option(None)));
+    }
+
+    #[test]
+    fn non_finite_floats_rejected() {
+        let options = JsonOptions {
+            non_finite_floats: NonFiniteFloats::Error,
+            ..JsonOptions::default()
+        };
+        let result = TypeAnnotatedValue::F64(f64::NAN).to_json_value_with_options(&options);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn serializing_nan_does_not_panic() {
+        let tav = TypeAnnotatedValue::F64(f64::NAN);
+        let json = serde_json::to_value(&tav).unwrap();
+        assert_eq!(json["value"], JsonValue::String("NaN".to_string()));
+    }
+
+    #[test]
+    fn disambiguated_nested_options() {
+        let options = JsonOptions {
+            disambiguate_nested_options: true,
+            ..JsonOptions::default()
+        };
+        let typ = AnalysedType::Option(TypeOption {
+            inner: Box::new(AnalysedType::Option(TypeOption {
+                inner: Box::new(AnalysedType::Str(TypeStr)),
+            })),
+        });
+
+        let some_none = Value::Option(Some(Box::new(Value::Option(None))));
+        let (json, value) = round_trip_with_options(some_none.clone(), &typ, &options);
+        assert_eq!(json, serde_json::json!({ "some": null }));
+        assert_eq!(value, some_none);
+
+        let none = Value::Option(None);
+        let (json, value) = round_trip_with_options(none.clone(), &typ, &options);
+        assert_eq!(json, JsonValue::Null);
+        assert_eq!(value, none);
+    }
+
+    #[test]
+    fn adjacently_tagged_variants() {
+        let options = JsonOptions {
+            variant_tagging: VariantTagging::adjacent(),
+            ..JsonOptions::default()
+        };
+        let typ = AnalysedType::Variant(TypeVariant {
+            cases: vec![
+                NameOptionTypePair {
+                    name: "empty".to_string(),
+                    typ: None,
+                },
+                NameOptionTypePair {
+                    name: "text".to_string(),
+                    typ: Some(AnalysedType::Str(TypeStr)),
+                },
+            ],
+        });
+
+        let text = Value::Variant {
+            case_idx: 1,
+            case_value: Some(Box::new(Value::String("hello".to_string()))),
+        };
+        let (json, value) = round_trip_with_options(text.clone(), &typ, &options);
+        assert_eq!(json, serde_json::json!({ "tag": "text", "value": "hello" }));
+        assert_eq!(value, text);
+
+        let empty = Value::Variant {
+            case_idx: 0,
+            case_value: None,
+        };
+        let (json, value) = round_trip_with_options(empty.clone(), &typ, &options);
+        assert_eq!(json, serde_json::json!({ "tag": "empty" }));
+        assert_eq!(value, empty);
+    }
+
+    fn add_item_parameters() -> Vec<AnalysedFunctionParameter> {
+        vec![
+            AnalysedFunctionParameter {
+                name: "product-id".to_string(),
+                typ: AnalysedType::Str(TypeStr),
+            },
+            AnalysedFunctionParameter {
+                name: "quantity".to_string(),
+                typ: AnalysedType::U32(TypeU32),
+            },
+        ]
+    }
+
+    #[test]
+    fn function_parameters_from_array() {
+        let result = function_parameters(&serde_json::json!(["G1000", 2]), &add_item_parameters());
+        assert_eq!(
+            result,
+            Ok(vec![Value::String("G1000".to_string()), Value::U32(2)])
+        );
+    }
+
+    #[test]
+    fn function_parameters_from_object() {
+        let result = function_parameters(
+            &serde_json::json!({ "quantity": 2, "product-id": "G1000" }),
+            &add_item_parameters(),
+        );
+        assert_eq!(
+            result,
+            Ok(vec![Value::String("G1000".to_string()), Value::U32(2)])
+        );
+    }
+
+    #[test]
+    fn function_parameters_errors_are_aggregated() {
+        let result = function_parameters(&serde_json::json!([1, -2]), &add_item_parameters());
+        let errors = result.unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Invalid value for parameter product-id: "));
+        assert!(errors[1].starts_with("Invalid value for parameter quantity: "));
+
+        let result = function_parameters(&serde_json::json!({}), &add_item_parameters());
+        assert_eq!(
+            result,
+            Err(vec![
+                "Missing parameter product-id".to_string(),
+                "Missing parameter quantity".to_string()
+            ])
+        );
+
+        let result = function_parameters(&serde_json::json!(["G1000"]), &add_item_parameters());
+        assert_eq!(
+            result,
+            Err(vec![
+                "Unexpected number of parameters (got 1, expected: 2)".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn function_result_json() {
+        let single = vec![AnalysedFunctionResult {
+            name: None,
+            typ: AnalysedType::U32(TypeU32),
+        }];
+        assert_eq!(
+            function_result(vec![Value::U32(1)], &single),
+            Ok(serde_json::json!(1))
+        );
+
+        let named = vec![
+            AnalysedFunctionResult {
+                name: Some("total".to_string()),
+                typ: AnalysedType::U32(TypeU32),
+            },
+            AnalysedFunctionResult {
+                name: Some("currency".to_string()),
+                typ: AnalysedType::Str(TypeStr),
+            },
+        ];
+        assert_eq!(
+            function_result(
+                vec![Value::U32(10), Value::String("EUR".to_string())],
+                &named
+            ),
+            Ok(serde_json::json!({ "total": 10, "currency": "EUR" }))
+        );
+
+        assert!(function_result(vec![], &single).is_err());
+    }
+
+    fn order_type() -> AnalysedType {
+        AnalysedType::Record(TypeRecord {
+            fields: vec![
+                NameTypePair {
+                    name: "items".to_string(),
+                    typ: AnalysedType::List(TypeList {
+                        inner: Box::new(AnalysedType::Record(TypeRecord {
+                            fields: vec![
+                                NameTypePair {
+                                    name: "quantity".to_string(),
+                                    typ: AnalysedType::U32(TypeU32),
+                                },
+                                NameTypePair {
+                                    name: "size".to_string(),
+                                    typ: AnalysedType::Enum(TypeEnum {
+                                        cases: vec!["small".to_string(), "large".to_string()],
+                                    }),
+                                },
+                            ],
+                        })),
+                    }),
+                },
+                NameTypePair {
+                    name: "note".to_string(),
+                    typ: AnalysedType::Option(TypeOption {
+                        inner: Box::new(AnalysedType::Str(TypeStr)),
+                    }),
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn errors_include_path() {
+        let json = serde_json::json!({
+            "items": [
+                { "quantity": 1, "size": "small" },
+                { "quantity": -1, "size": "huge" }
+            ]
+        });
+        let errors = TypeAnnotatedValue::parse_with_type(&json, &order_type()).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(
+            errors[0].starts_with("$.items[1].quantity: "),
+            "{}",
+            errors[0]
+        );
+        assert!(errors[1].starts_with("$.items[1].size: "), "{}", errors[1]);
+    }
+
+    #[test]
+    fn strict_mode() {
+        let options = JsonOptions::strict();
+        let json = serde_json::json!({ "items": [], "note": null });
+        assert!(
+            TypeAnnotatedValue::parse_with_type_and_options(&json, &order_type(), &options).is_ok()
+        );
+
+        let json = serde_json::json!({
+            "items": [{ "quantity": 1, "size": "small", "color": "red" }],
+            "total": 1
+        });
+        let errors =
+            TypeAnnotatedValue::parse_with_type_and_options(&json, &order_type(), &options)
+                .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "$.total: unknown field".to_string(),
+                "$.items[0].color: unknown field".to_string(),
+                "$.note: missing field".to_string(),
+            ]
+        );
+
+        // the default options ignore unknown fields
+        assert!(TypeAnnotatedValue::parse_with_type(&json, &order_type()).is_ok());
+    }
+
+    #[test]
+    fn lenient_mode() {
+        let options = JsonOptions::lenient();
+        let json = serde_json::json!({
+            "items": [{ "quantity": "2", "size": "LARGE", "color": "red" }]
+        });
+        let value = TypeAnnotatedValue::parse_with_type_and_options(&json, &order_type(), &options)
+            .unwrap();
+        assert_eq!(
+            Value::try_from(value).unwrap(),
+            Value::Record(vec![
+                Value::List(vec![Value::Record(vec![Value::U32(2), Value::Enum(1)])]),
+                Value::Option(None),
+            ])
+        );
+
+        let flags = AnalysedType::Flags(TypeFlags {
+            names: vec!["read".to_string(), "write".to_string()],
+        });
+        let value = TypeAnnotatedValue::parse_with_type_and_options(
+            &serde_json::json!(["Write"]),
+            &flags,
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            Value::try_from(value).unwrap(),
+            Value::Flags(vec![false, true])
+        );
+
+        let value = TypeAnnotatedValue::parse_with_type_and_options(
+            &serde_json::json!(" 1.5 "),
+            &AnalysedType::F64(TypeF64),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(value, TypeAnnotatedValue::F64(1.5));
+
+        assert!(TypeAnnotatedValue::parse_with_type(
+            &serde_json::json!("2"),
+            &AnalysedType::U32(TypeU32)
+        )
+        .is_err());
+    }
 }
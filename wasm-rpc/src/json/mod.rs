@@ -13,22 +13,165 @@
 // limitations under the License.
 
 mod r#impl;
+mod named;
+mod schema;
+mod stream;
 
 use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
 use golem_wasm_ast::analysis::AnalysedType;
 use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value as JsonValue;
 
+pub use named::{function_parameters_named, function_parameters_named_typed, NamedArguments};
+pub use r#impl::{
+    function_parameters, function_parameters_typed, function_result, function_result_typed,
+};
+pub use schema::{json_schema, HANDLE_FORMAT};
+pub use stream::{from_reader, to_writer, wit_value_from_reader, TypedJson, TypedJsonSeed};
+
+/// Options controlling how typed values are mapped to and from untyped JSON values.
+///
+/// The default options correspond to the representation used by `to_json_value` and
+/// `parse_with_type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonOptions {
+    /// Represent `char` values as single-character strings instead of their code point.
+    pub char_as_string: bool,
+    /// Represent `u64` and `s64` values as decimal strings, as JSON numbers outside of the
+    /// +/- 2^53 range lose precision in JavaScript.
+    pub int64_as_string: bool,
+    /// How to represent NaN and infinite `f32` and `f64` values, which JSON numbers cannot hold.
+    pub non_finite_floats: NonFiniteFloats,
+    /// Represent `some(x)` as `{"some": x}` when the option's inner type is also an option, so
+    /// `some(none)` and `none` can be told apart.
+    pub disambiguate_nested_options: bool,
+    /// How to represent variant values.
+    pub variant_tagging: VariantTagging,
+    /// Reject record fields, and keys next to result and variant cases, that are not part of
+    /// the type.
+    pub deny_unknown_fields: bool,
+    /// Parse missing `option` record fields as `none`.
+    pub missing_option_fields_as_none: bool,
+    /// Match enum cases, flag names and variant cases case-insensitively.
+    pub case_insensitive_names: bool,
+    /// Accept numbers encoded as strings, such as `"42"` or `"1.5"`, when parsing.
+    pub numbers_as_strings: bool,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            char_as_string: false,
+            int64_as_string: false,
+            non_finite_floats: NonFiniteFloats::String,
+            disambiguate_nested_options: false,
+            variant_tagging: VariantTagging::External,
+            deny_unknown_fields: false,
+            missing_option_fields_as_none: true,
+            case_insensitive_names: false,
+            numbers_as_strings: false,
+        }
+    }
+}
+
+impl JsonOptions {
+    /// Options that only accept input exactly matching the type: unknown fields are rejected
+    /// and every record field has to be present.
+    pub fn strict() -> Self {
+        Self {
+            deny_unknown_fields: true,
+            missing_option_fields_as_none: false,
+            ..Self::default()
+        }
+    }
+
+    /// Options that accept input from less precise producers: unknown fields are ignored,
+    /// missing option fields are `none`, names are matched case-insensitively and numbers may be
+    /// encoded as strings.
+    pub fn lenient() -> Self {
+        Self {
+            deny_unknown_fields: false,
+            missing_option_fields_as_none: true,
+            case_insensitive_names: true,
+            numbers_as_strings: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// The JSON string representing a NaN float value
+pub const NAN: &str = "NaN";
+/// The JSON string representing a positive infinite float value
+pub const INFINITY: &str = "Infinity";
+/// The JSON string representing a negative infinite float value
+pub const NEG_INFINITY: &str = "-Infinity";
+
+/// Representation of non-finite floating point values
+///
+/// Regardless of this setting, the `"NaN"`, `"Infinity"` and `"-Infinity"` strings are always
+/// accepted as `f32` and `f64` values when parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonFiniteFloats {
+    /// NaN and infinite values are represented by the `"NaN"`, `"Infinity"` and `"-Infinity"`
+    /// strings. This is lossless, except for the payload and sign of NaN values.
+    String,
+    /// NaN and infinite values are represented by `null`, which is parsed back as NaN.
+    ///
+    /// Within an `option<f32>` or `option<f64>`, `null` is parsed as `none`, so a `some` holding
+    /// a non-finite value does not round-trip. Use `String` where such values have to be kept.
+    Null,
+    /// Converting NaN and infinite values to JSON fails.
+    Error,
+}
+
+/// Representation of variant values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariantTagging {
+    /// A single-key object where the key is the case name: `{"case": value}`
+    External,
+    /// An object with the case name and the case value in separate fields:
+    /// `{"tag": "case", "value": value}`. The value field is omitted for unit cases.
+    Adjacent { tag: String, content: String },
+}
+
+impl VariantTagging {
+    /// Adjacent tagging with `tag` and `value` as field names.
+    pub fn adjacent() -> Self {
+        VariantTagging::Adjacent {
+            tag: "tag".to_string(),
+            content: "value".to_string(),
+        }
+    }
+}
+
 pub trait TypeAnnotatedValueJsonExtensions: Sized {
     /// Parses a JSON value representation (with no type information) into a typed value based
     /// on the given type information.
-    fn parse_with_type(json_val: &JsonValue, typ: &AnalysedType) -> Result<Self, Vec<String>>;
+    fn parse_with_type(json_val: &JsonValue, typ: &AnalysedType) -> Result<Self, Vec<String>> {
+        Self::parse_with_type_and_options(json_val, typ, &JsonOptions::default())
+    }
+
+    /// Parses a JSON value representation (with no type information) into a typed value based
+    /// on the given type information, using the given mapping options.
+    fn parse_with_type_and_options(
+        json_val: &JsonValue,
+        typ: &AnalysedType,
+        options: &JsonOptions,
+    ) -> Result<Self, Vec<String>>;
 
     /// Converts a `TypeAnnotatedValue` to a JSON value representation with no type information.
     ///
+    /// Fails if the value is malformed, for example if a list item has no value.
+    ///
     /// Use `TypeAnnotatedValue`'s `Serialize` instance with `serde_json` to get a self-describing
     /// representation that contains both the type information and the value.
-    fn to_json_value(&self) -> JsonValue;
+    fn to_json_value(&self) -> Result<JsonValue, String> {
+        self.to_json_value_with_options(&JsonOptions::default())
+    }
+
+    /// Converts a `TypeAnnotatedValue` to a JSON value representation with no type information,
+    /// using the given mapping options.
+    fn to_json_value_with_options(&self, options: &JsonOptions) -> Result<JsonValue, String>;
 }
 
 /// An internal representation of a TypeAnnotatedValue that can be serialized to JSON.
@@ -44,7 +187,7 @@ impl Serialize for TypeAnnotatedValue {
         S: Serializer,
     {
         let typ: AnalysedType = self.try_into().map_err(serde::ser::Error::custom)?;
-        let value = self.to_json_value();
+        let value = self.to_json_value().map_err(serde::ser::Error::custom)?;
         let json = TypeAnnotatedValueJson { typ, value };
         json.serialize(serializer)
     }
@@ -0,0 +1,216 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::Formatter;
+
+use golem_wasm_ast::analysis::{AnalysedFunction, AnalysedFunctionParameter, AnalysedType};
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::json::r#impl::{parse_parameters, to_values};
+use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
+use crate::Value;
+
+/// Function arguments keyed by parameter name, such as `{"x": 1, "metadata": {...}}`.
+///
+/// Unlike `serde_json::Map`, deserializing this type keeps every key of the input object in
+/// order, so duplicate parameter names can be reported.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NamedArguments(pub Vec<(String, JsonValue)>);
+
+impl NamedArguments {
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &JsonValue)> {
+        self.0.iter().map(|(name, value)| (name, value))
+    }
+}
+
+impl From<Map<String, JsonValue>> for NamedArguments {
+    fn from(value: Map<String, JsonValue>) -> Self {
+        NamedArguments(value.into_iter().collect())
+    }
+}
+
+impl<'de> Deserialize<'de> for NamedArguments {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NamedArgumentsVisitor;
+
+        impl<'de> Visitor<'de> for NamedArgumentsVisitor {
+            type Value = NamedArguments;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("an object of arguments keyed by parameter name")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut arguments = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((name, value)) = map.next_entry::<String, JsonValue>()? {
+                    arguments.push((name, value));
+                }
+                Ok(NamedArguments(arguments))
+            }
+        }
+
+        deserializer.deserialize_map(NamedArgumentsVisitor)
+    }
+}
+
+/// Parses named function arguments into values, based on the function's signature.
+///
+/// See `function_parameters_named_typed` for details.
+pub fn function_parameters_named(
+    arguments: &NamedArguments,
+    function: &AnalysedFunction,
+) -> Result<Vec<Value>, Vec<String>> {
+    to_values(function_parameters_named_typed(arguments, function)?)
+}
+
+/// Parses named function arguments into typed values, in the order of the function's parameters.
+///
+/// Missing, unknown and duplicate parameter names are all reported together with the invalid
+/// parameter values. Absent `option` parameters are `none`.
+pub fn function_parameters_named_typed(
+    arguments: &NamedArguments,
+    function: &AnalysedFunction,
+) -> Result<Vec<TypeAnnotatedValue>, Vec<String>> {
+    named_parameters_typed(arguments.iter(), &function.parameters)
+}
+
+pub(super) fn named_parameters_typed<'a>(
+    arguments: impl IntoIterator<Item = (&'a String, &'a JsonValue)>,
+    expected_parameters: &'a [AnalysedFunctionParameter],
+) -> Result<Vec<TypeAnnotatedValue>, Vec<String>> {
+    let mut errors = vec![];
+    let mut found: Vec<Option<&JsonValue>> = vec![None; expected_parameters.len()];
+    let mut duplicates = HashSet::new();
+
+    for (name, value) in arguments {
+        match expected_parameters.iter().position(|fp| &fp.name == name) {
+            Some(idx) => {
+                if found[idx].is_some() {
+                    if duplicates.insert(idx) {
+                        errors.push(format!("Duplicate parameter {name}"));
+                    }
+                } else {
+                    found[idx] = Some(value);
+                }
+            }
+            None => errors.push(format!("Unknown parameter {name}")),
+        }
+    }
+
+    let mut parameters = vec![];
+    for (fp, value) in expected_parameters.iter().zip(found) {
+        match value {
+            Some(value) => parameters.push((fp, Cow::Borrowed(value))),
+            None if matches!(fp.typ, AnalysedType::Option(_)) => {
+                parameters.push((fp, Cow::Owned(JsonValue::Null)))
+            }
+            None => errors.push(format!("Missing parameter {}", fp.name)),
+        }
+    }
+
+    parse_parameters(parameters, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use golem_wasm_ast::analysis::{
+        AnalysedFunction, AnalysedFunctionParameter, AnalysedType, TypeOption, TypeStr, TypeU32,
+    };
+
+    use crate::json::{function_parameters_named, NamedArguments};
+    use crate::Value;
+
+    fn function() -> AnalysedFunction {
+        AnalysedFunction {
+            name: "add-item".to_string(),
+            parameters: vec![
+                AnalysedFunctionParameter {
+                    name: "product-id".to_string(),
+                    typ: AnalysedType::Str(TypeStr),
+                },
+                AnalysedFunctionParameter {
+                    name: "quantity".to_string(),
+                    typ: AnalysedType::U32(TypeU32),
+                },
+                AnalysedFunctionParameter {
+                    name: "note".to_string(),
+                    typ: AnalysedType::Option(TypeOption {
+                        inner: Box::new(AnalysedType::Str(TypeStr)),
+                    }),
+                },
+            ],
+            results: vec![],
+        }
+    }
+
+    fn parse(json: &str) -> Result<Vec<Value>, Vec<String>> {
+        let arguments: NamedArguments = serde_json::from_str(json).unwrap();
+        function_parameters_named(&arguments, &function())
+    }
+
+    #[test]
+    fn named_arguments_in_any_order() {
+        assert_eq!(
+            parse(r#"{"note": "gift", "quantity": 2, "product-id": "G1000"}"#),
+            Ok(vec![
+                Value::String("G1000".to_string()),
+                Value::U32(2),
+                Value::Option(Some(Box::new(Value::String("gift".to_string()))))
+            ])
+        );
+    }
+
+    #[test]
+    fn absent_option_is_none() {
+        assert_eq!(
+            parse(r#"{"product-id": "G1000", "quantity": 2}"#),
+            Ok(vec![
+                Value::String("G1000".to_string()),
+                Value::U32(2),
+                Value::Option(None)
+            ])
+        );
+    }
+
+    #[test]
+    fn all_name_errors_are_reported() {
+        assert_eq!(
+            parse(r#"{"quantity": 2, "quantity": 3, "price": 10}"#),
+            Err(vec![
+                "Duplicate parameter quantity".to_string(),
+                "Unknown parameter price".to_string(),
+                "Missing parameter product-id".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_reported_with_name_errors() {
+        let errors =
+            parse(r#"{"product-id": "G1000", "quantity": "two", "price": 10}"#).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0], "Unknown parameter price");
+        assert!(errors[1].starts_with("Invalid value for parameter quantity: "));
+    }
+}
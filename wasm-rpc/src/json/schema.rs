@@ -0,0 +1,399 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_wasm_ast::analysis::{
+    AnalysedType, NameOptionTypePair, NameTypePair, TypeEnum, TypeFlags, TypeList, TypeOption,
+    TypeRecord, TypeResult, TypeTuple, TypeVariant,
+};
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::json::{INFINITY, NAN, NEG_INFINITY};
+
+/// The `format` used in the generated schema for resource handles, which are represented
+/// by a `worker-url/resource-id` string.
+pub const HANDLE_FORMAT: &str = "uri/resource-id";
+
+/// Generates a JSON Schema (draft 2020-12) describing the untyped JSON representation of values
+/// of the given type, as produced by `to_json_value` and accepted by `parse_with_type`.
+///
+/// The schema follows the default `JsonOptions`: unknown object keys are allowed and option
+/// fields of records may be missing.
+pub fn json_schema(typ: &AnalysedType) -> JsonValue {
+    match typ {
+        AnalysedType::Bool(_) => json!({ "type": "boolean" }),
+        AnalysedType::S8(_) => integer_schema(i8::MIN as i64, i8::MAX as u64),
+        AnalysedType::U8(_) => integer_schema(0, u8::MAX as u64),
+        AnalysedType::S16(_) => integer_schema(i16::MIN as i64, i16::MAX as u64),
+        AnalysedType::U16(_) => integer_schema(0, u16::MAX as u64),
+        AnalysedType::S32(_) => integer_schema(i32::MIN as i64, i32::MAX as u64),
+        AnalysedType::U32(_) => integer_schema(0, u32::MAX as u64),
+        AnalysedType::S64(_) => integer_schema(i64::MIN, i64::MAX as u64),
+        AnalysedType::U64(_) => integer_schema(0, u64::MAX),
+        AnalysedType::F32(_) => float_schema(json!({
+            "type": "number",
+            "minimum": f32::MIN as f64,
+            "maximum": f32::MAX as f64
+        })),
+        AnalysedType::F64(_) => float_schema(json!({ "type": "number" })),
+        // chars are represented by their code point
+        AnalysedType::Chr(_) => integer_schema(0, u32::MAX as u64),
+        AnalysedType::Str(_) => json!({ "type": "string" }),
+        AnalysedType::Enum(TypeEnum { cases }) => json!({
+            "type": "string",
+            "enum": cases
+        }),
+        AnalysedType::Flags(TypeFlags { names }) => json!({
+            "type": "array",
+            "items": {
+                "type": "string",
+                "enum": names
+            }
+        }),
+        AnalysedType::List(TypeList { inner }) => json!({
+            "type": "array",
+            "items": json_schema(inner)
+        }),
+        AnalysedType::Tuple(TypeTuple { items }) => json!({
+            "type": "array",
+            "prefixItems": items.iter().map(json_schema).collect::<Vec<_>>(),
+            "items": false,
+            "minItems": items.len(),
+            "maxItems": items.len()
+        }),
+        // `anyOf` instead of `oneOf` because for nested options `null` matches both branches
+        AnalysedType::Option(TypeOption { inner }) => json!({
+            "anyOf": [
+                { "type": "null" },
+                json_schema(inner)
+            ]
+        }),
+        AnalysedType::Result(TypeResult { ok, err }) => exclusive_keys_schema(&[
+            ("ok", optional_schema(ok.as_deref())),
+            ("err", optional_schema(err.as_deref())),
+        ]),
+        AnalysedType::Record(TypeRecord { fields }) => record_schema(fields),
+        AnalysedType::Variant(TypeVariant { cases }) => variant_schema(cases),
+        AnalysedType::Handle(_) => json!({
+            "type": "string",
+            "format": HANDLE_FORMAT,
+            "pattern": "^.*/[0-9]+$"
+        }),
+    }
+}
+
+fn integer_schema(min: i64, max: u64) -> JsonValue {
+    json!({
+        "type": "integer",
+        "minimum": min,
+        "maximum": max
+    })
+}
+
+// non-finite floats are encoded as strings by default
+fn float_schema(number_schema: JsonValue) -> JsonValue {
+    json!({
+        "anyOf": [
+            number_schema,
+            { "type": "string", "enum": [NAN, INFINITY, NEG_INFINITY] }
+        ]
+    })
+}
+
+fn optional_schema(typ: Option<&AnalysedType>) -> JsonValue {
+    match typ {
+        Some(typ) => json_schema(typ),
+        None => json!({ "type": "null" }),
+    }
+}
+
+/// Objects with exactly one of the given keys, and any number of other keys which are ignored
+/// when parsing with the default options
+fn exclusive_keys_schema(keys: &[(&str, JsonValue)]) -> JsonValue {
+    let branches: Vec<JsonValue> = keys
+        .iter()
+        .map(|(key, value_schema)| {
+            let mut properties = Map::new();
+            properties.insert(key.to_string(), value_schema.clone());
+
+            let mut schema = Map::new();
+            schema.insert("type".to_string(), json!("object"));
+            schema.insert("properties".to_string(), JsonValue::Object(properties));
+            schema.insert("required".to_string(), json!([key]));
+
+            let others: Vec<JsonValue> = keys
+                .iter()
+                .filter(|(other, _)| other != key)
+                .map(|(other, _)| json!({ "required": [other] }))
+                .collect();
+            if !others.is_empty() {
+                schema.insert("not".to_string(), json!({ "anyOf": others }));
+            }
+            JsonValue::Object(schema)
+        })
+        .collect();
+
+    json!({ "anyOf": branches })
+}
+
+fn record_schema(fields: &[NameTypePair]) -> JsonValue {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for NameTypePair { name, typ } in fields {
+        properties.insert(name.clone(), json_schema(typ));
+        // missing option fields are parsed as `none`
+        if !matches!(typ, AnalysedType::Option(_)) {
+            required.push(JsonValue::String(name.clone()));
+        }
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required
+    })
+}
+
+fn variant_schema(cases: &[NameOptionTypePair]) -> JsonValue {
+    let cases: Vec<(&str, JsonValue)> = cases
+        .iter()
+        .map(|NameOptionTypePair { name, typ }| (name.as_str(), optional_schema(typ.as_ref())))
+        .collect();
+    exclusive_keys_schema(&cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use golem_wasm_ast::analysis::{
+        AnalysedResourceId, AnalysedResourceMode, AnalysedType, NameOptionTypePair, NameTypePair,
+        TypeEnum, TypeFlags, TypeHandle, TypeOption, TypeRecord, TypeResult, TypeStr, TypeTuple,
+        TypeU32, TypeU8, TypeVariant,
+    };
+    use serde_json::{json, Value as JsonValue};
+
+    use crate::json::{json_schema, TypeAnnotatedValueJsonExtensions};
+    use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
+
+    /// Checks that the schema of `typ` accepts exactly the inputs `parse_with_type` accepts
+    fn assert_schema_matches_parser(typ: &AnalysedType, inputs: Vec<JsonValue>) {
+        let schema = json_schema(typ);
+        for input in inputs {
+            let parsed = TypeAnnotatedValue::parse_with_type(&input, typ);
+            assert_eq!(
+                jsonschema::draft202012::is_valid(&schema, &input),
+                parsed.is_ok(),
+                "{input} (parsed: {parsed:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(
+            json_schema(&AnalysedType::U8(TypeU8)),
+            json!({ "type": "integer", "minimum": 0, "maximum": 255 })
+        );
+        assert_eq!(
+            json_schema(&AnalysedType::Str(TypeStr)),
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn enum_and_flags() {
+        assert_eq!(
+            json_schema(&AnalysedType::Enum(TypeEnum {
+                cases: vec!["a".to_string(), "b".to_string()]
+            })),
+            json!({ "type": "string", "enum": ["a", "b"] })
+        );
+        assert_eq!(
+            json_schema(&AnalysedType::Flags(TypeFlags {
+                names: vec!["x".to_string(), "y".to_string()]
+            })),
+            json!({ "type": "array", "items": { "type": "string", "enum": ["x", "y"] } })
+        );
+    }
+
+    #[test]
+    fn tuple() {
+        assert_eq!(
+            json_schema(&AnalysedType::Tuple(TypeTuple {
+                items: vec![AnalysedType::U32(TypeU32), AnalysedType::Str(TypeStr)]
+            })),
+            json!({
+                "type": "array",
+                "prefixItems": [
+                    { "type": "integer", "minimum": 0, "maximum": 4294967295u32 },
+                    { "type": "string" }
+                ],
+                "items": false,
+                "minItems": 2,
+                "maxItems": 2
+            })
+        );
+    }
+
+    #[test]
+    fn record_with_optional_field() {
+        assert_eq!(
+            json_schema(&AnalysedType::Record(TypeRecord {
+                fields: vec![
+                    NameTypePair {
+                        name: "x".to_string(),
+                        typ: AnalysedType::Str(TypeStr),
+                    },
+                    NameTypePair {
+                        name: "y".to_string(),
+                        typ: AnalysedType::Option(TypeOption {
+                            inner: Box::new(AnalysedType::Str(TypeStr)),
+                        }),
+                    },
+                ]
+            })),
+            json!({
+                "type": "object",
+                "properties": {
+                    "x": { "type": "string" },
+                    "y": { "anyOf": [{ "type": "null" }, { "type": "string" }] }
+                },
+                "required": ["x"]
+            })
+        );
+    }
+
+    #[test]
+    fn result_with_unit_err() {
+        assert_eq!(
+            json_schema(&AnalysedType::Result(TypeResult {
+                ok: Some(Box::new(AnalysedType::Str(TypeStr))),
+                err: None,
+            })),
+            json!({
+                "anyOf": [
+                    {
+                        "type": "object",
+                        "properties": { "ok": { "type": "string" } },
+                        "required": ["ok"],
+                        "not": { "anyOf": [{ "required": ["err"] }] }
+                    },
+                    {
+                        "type": "object",
+                        "properties": { "err": { "type": "null" } },
+                        "required": ["err"],
+                        "not": { "anyOf": [{ "required": ["ok"] }] }
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn variant() {
+        assert_eq!(
+            json_schema(&AnalysedType::Variant(TypeVariant {
+                cases: vec![
+                    NameOptionTypePair {
+                        name: "empty".to_string(),
+                        typ: None,
+                    },
+                    NameOptionTypePair {
+                        name: "text".to_string(),
+                        typ: Some(AnalysedType::Str(TypeStr)),
+                    },
+                ]
+            })),
+            json!({
+                "anyOf": [
+                    {
+                        "type": "object",
+                        "properties": { "empty": { "type": "null" } },
+                        "required": ["empty"],
+                        "not": { "anyOf": [{ "required": ["text"] }] }
+                    },
+                    {
+                        "type": "object",
+                        "properties": { "text": { "type": "string" } },
+                        "required": ["text"],
+                        "not": { "anyOf": [{ "required": ["empty"] }] }
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn schema_rejects_what_the_parser_rejects() {
+        let result = AnalysedType::Result(TypeResult {
+            ok: Some(Box::new(AnalysedType::U32(TypeU32))),
+            err: Some(Box::new(AnalysedType::Str(TypeStr))),
+        });
+        assert_schema_matches_parser(
+            &result,
+            vec![
+                json!({ "ok": 1 }),
+                json!({ "err": "failed" }),
+                json!({ "ok": 1, "other": true }),
+                json!({ "ok": 1, "err": "failed" }),
+                json!({ "ok": "1" }),
+                json!({}),
+            ],
+        );
+
+        let variant = AnalysedType::Variant(TypeVariant {
+            cases: vec![
+                NameOptionTypePair {
+                    name: "a".to_string(),
+                    typ: Some(AnalysedType::U32(TypeU32)),
+                },
+                NameOptionTypePair {
+                    name: "b".to_string(),
+                    typ: Some(AnalysedType::U32(TypeU32)),
+                },
+                NameOptionTypePair {
+                    name: "c".to_string(),
+                    typ: None,
+                },
+            ],
+        });
+        assert_schema_matches_parser(
+            &variant,
+            vec![
+                json!({ "a": 1 }),
+                json!({ "c": null }),
+                json!({ "b": 2, "other": true }),
+                json!({ "a": 1, "b": 2 }),
+                json!({ "a": 1, "c": null }),
+                json!({ "c": 1 }),
+                json!({ "other": 1 }),
+                json!({}),
+            ],
+        );
+    }
+
+    #[test]
+    fn handle() {
+        assert_eq!(
+            json_schema(&AnalysedType::Handle(TypeHandle {
+                resource_id: AnalysedResourceId(1),
+                mode: AnalysedResourceMode::Owned,
+            })),
+            json!({
+                "type": "string",
+                "format": "uri/resource-id",
+                "pattern": "^.*/[0-9]+$"
+            })
+        );
+    }
+}
@@ -0,0 +1,1239 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streaming conversion between JSON and typed values.
+//!
+//! Composite values (lists, tuples, records, options, results and variants) are deserialized
+//! directly from the input, driven by the expected `AnalysedType`, without materializing a
+//! `serde_json::Value` tree. Only individual primitive values are read into a `serde_json::Value`,
+//! to share the validation logic with `parse_with_type_and_options`. The accepted and produced
+//! JSON is the same as the one of `TypeAnnotatedValueJsonExtensions`, but parsing stops at the
+//! first error instead of collecting all of them, and errors are located by line and column
+//! instead of by the path of the offending value.
+//!
+//! `wit_value_from_reader` builds the `WitValue` nodes directly, so only a single representation
+//! of a large input is held in memory.
+
+use std::cell::RefCell;
+use std::fmt::Formatter;
+use std::io::{Read, Write};
+
+use golem_wasm_ast::analysis::{
+    AnalysedType, NameOptionTypePair, NameTypePair, TypeList, TypeOption, TypeRecord, TypeResult,
+    TypeTuple, TypeVariant,
+};
+use serde::de::{DeserializeSeed, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Error as _, SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+
+use crate::json::r#impl::{
+    find_case, is_option_type, non_finite_float_name, typed_list, typed_option, typed_record,
+    typed_result, typed_tuple, typed_variant,
+};
+use crate::json::{JsonOptions, NonFiniteFloats, TypeAnnotatedValueJsonExtensions, VariantTagging};
+use crate::protobuf;
+use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
+use crate::protobuf::typed_result::ResultValue;
+use crate::{NodeIndex, WitNode, WitValue};
+
+/// Parses a JSON document from a reader into a typed value of the given type.
+///
+/// The reader is read byte by byte, so files and sockets should be wrapped in a `BufReader`.
+pub fn from_reader<R: Read>(
+    reader: R,
+    typ: &AnalysedType,
+    options: &JsonOptions,
+) -> serde_json::Result<TypeAnnotatedValue> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let value = TypedJsonSeed::new(typ, options).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Parses a JSON document from a reader into a `WitValue` of the given type, without building
+/// an intermediate `TypeAnnotatedValue`.
+///
+/// The reader is read byte by byte, so files and sockets should be wrapped in a `BufReader`.
+pub fn wit_value_from_reader<R: Read>(
+    reader: R,
+    typ: &AnalysedType,
+    options: &JsonOptions,
+) -> serde_json::Result<WitValue> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let nodes = RefCell::new(WitNodes::default());
+    Seed::new(typ, options, &nodes).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(nodes.into_inner().finish())
+}
+
+/// Writes the JSON representation of a typed value to a writer.
+pub fn to_writer<W: Write>(
+    writer: W,
+    value: &TypeAnnotatedValue,
+    options: &JsonOptions,
+) -> serde_json::Result<()> {
+    serde_json::to_writer(writer, &TypedJson::new(value, options))
+}
+
+/// A `DeserializeSeed` parsing the JSON representation of a value of the given type into a
+/// `TypeAnnotatedValue`.
+#[derive(Debug, Clone, Copy)]
+pub struct TypedJsonSeed<'a> {
+    typ: &'a AnalysedType,
+    options: &'a JsonOptions,
+}
+
+impl<'a> TypedJsonSeed<'a> {
+    pub fn new(typ: &'a AnalysedType, options: &'a JsonOptions) -> Self {
+        Self { typ, options }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for TypedJsonSeed<'_> {
+    type Value = TypeAnnotatedValue;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values = RefCell::new(TypeAnnotatedValues);
+        Seed::new(self.typ, self.options, &values).deserialize(deserializer)
+    }
+}
+
+/// Builds the values read by the visitors, children before their parents.
+trait ValueBuilder {
+    type Value: Clone;
+
+    /// Adds a value parsed by `parse_with_type_and_options`, used for primitives and for buffered
+    /// variant contents
+    fn parsed(&mut self, value: TypeAnnotatedValue) -> Result<Self::Value, String>;
+
+    fn list(&mut self, inner: &AnalysedType, values: Vec<Self::Value>) -> Self::Value;
+
+    fn tuple(&mut self, items: &[AnalysedType], values: Vec<Self::Value>) -> Self::Value;
+
+    fn option(&mut self, inner: &AnalysedType, value: Option<Self::Value>) -> Self::Value;
+
+    fn record(
+        &mut self,
+        fields: &[NameTypePair],
+        values: Vec<(String, Self::Value)>,
+    ) -> Self::Value;
+
+    fn result(
+        &mut self,
+        ok: &Option<Box<AnalysedType>>,
+        err: &Option<Box<AnalysedType>>,
+        value: Result<Option<Self::Value>, Option<Self::Value>>,
+    ) -> Self::Value;
+
+    fn variant(
+        &mut self,
+        cases: &[NameOptionTypePair],
+        case_name: &str,
+        value: Option<Self::Value>,
+    ) -> Self::Value;
+}
+
+struct TypeAnnotatedValues;
+
+impl ValueBuilder for TypeAnnotatedValues {
+    type Value = TypeAnnotatedValue;
+
+    fn parsed(&mut self, value: TypeAnnotatedValue) -> Result<Self::Value, String> {
+        Ok(value)
+    }
+
+    fn list(&mut self, inner: &AnalysedType, values: Vec<Self::Value>) -> Self::Value {
+        typed_list(inner, values)
+    }
+
+    fn tuple(&mut self, items: &[AnalysedType], values: Vec<Self::Value>) -> Self::Value {
+        typed_tuple(items, values)
+    }
+
+    fn option(&mut self, inner: &AnalysedType, value: Option<Self::Value>) -> Self::Value {
+        typed_option(inner, value)
+    }
+
+    fn record(
+        &mut self,
+        fields: &[NameTypePair],
+        values: Vec<(String, Self::Value)>,
+    ) -> Self::Value {
+        typed_record(fields, values)
+    }
+
+    fn result(
+        &mut self,
+        ok: &Option<Box<AnalysedType>>,
+        err: &Option<Box<AnalysedType>>,
+        value: Result<Option<Self::Value>, Option<Self::Value>>,
+    ) -> Self::Value {
+        typed_result(ok, err, value)
+    }
+
+    fn variant(
+        &mut self,
+        cases: &[NameOptionTypePair],
+        case_name: &str,
+        value: Option<Self::Value>,
+    ) -> Self::Value {
+        typed_variant(cases, case_name.to_string(), value)
+    }
+}
+
+/// The nodes of a `WitValue`, added children first. The root, added last, is moved to the front
+/// by `finish`.
+#[derive(Default)]
+struct WitNodes {
+    nodes: Vec<WitNode>,
+}
+
+impl WitNodes {
+    fn add(&mut self, node: WitNode) -> NodeIndex {
+        self.nodes.push(node);
+        self.nodes.len() as NodeIndex - 1
+    }
+
+    fn finish(mut self) -> WitValue {
+        let last = self.nodes.len() as NodeIndex - 1;
+        self.nodes.reverse();
+        let nodes = self
+            .nodes
+            .into_iter()
+            .map(|node| map_node_indices(node, |idx| last - idx))
+            .collect();
+        WitValue { nodes }
+    }
+}
+
+impl ValueBuilder for WitNodes {
+    type Value = NodeIndex;
+
+    fn parsed(&mut self, value: TypeAnnotatedValue) -> Result<Self::Value, String> {
+        let value = WitValue::try_from(value)?;
+        // the root of the parsed value has to be added last
+        let last = self.nodes.len() as NodeIndex + value.nodes.len() as NodeIndex - 1;
+        for node in value.nodes.into_iter().rev() {
+            self.add(map_node_indices(node, |idx| last - idx));
+        }
+        Ok(last)
+    }
+
+    fn list(&mut self, _inner: &AnalysedType, values: Vec<Self::Value>) -> Self::Value {
+        self.add(WitNode::ListValue(values))
+    }
+
+    fn tuple(&mut self, _items: &[AnalysedType], values: Vec<Self::Value>) -> Self::Value {
+        self.add(WitNode::TupleValue(values))
+    }
+
+    fn option(&mut self, _inner: &AnalysedType, value: Option<Self::Value>) -> Self::Value {
+        self.add(WitNode::OptionValue(value))
+    }
+
+    fn record(
+        &mut self,
+        _fields: &[NameTypePair],
+        values: Vec<(String, Self::Value)>,
+    ) -> Self::Value {
+        self.add(WitNode::RecordValue(
+            values.into_iter().map(|(_, value)| value).collect(),
+        ))
+    }
+
+    fn result(
+        &mut self,
+        _ok: &Option<Box<AnalysedType>>,
+        _err: &Option<Box<AnalysedType>>,
+        value: Result<Option<Self::Value>, Option<Self::Value>>,
+    ) -> Self::Value {
+        self.add(WitNode::ResultValue(value))
+    }
+
+    fn variant(
+        &mut self,
+        cases: &[NameOptionTypePair],
+        case_name: &str,
+        value: Option<Self::Value>,
+    ) -> Self::Value {
+        // the visitors only pass names of existing cases
+        let case_idx = cases
+            .iter()
+            .position(|case| case.name == case_name)
+            .unwrap_or_default();
+        self.add(WitNode::VariantValue((case_idx as u32, value)))
+    }
+}
+
+fn map_node_indices(node: WitNode, f: impl Fn(NodeIndex) -> NodeIndex) -> WitNode {
+    let map_all = |indices: Vec<NodeIndex>| indices.into_iter().map(&f).collect();
+    match node {
+        WitNode::RecordValue(indices) => WitNode::RecordValue(map_all(indices)),
+        WitNode::TupleValue(indices) => WitNode::TupleValue(map_all(indices)),
+        WitNode::ListValue(indices) => WitNode::ListValue(map_all(indices)),
+        WitNode::VariantValue((case_idx, value)) => {
+            WitNode::VariantValue((case_idx, value.map(&f)))
+        }
+        WitNode::OptionValue(value) => WitNode::OptionValue(value.map(&f)),
+        WitNode::ResultValue(Ok(value)) => WitNode::ResultValue(Ok(value.map(&f))),
+        WitNode::ResultValue(Err(value)) => WitNode::ResultValue(Err(value.map(&f))),
+        node => node,
+    }
+}
+
+/// The `DeserializeSeed` behind `TypedJsonSeed` and `wit_value_from_reader`, passing the parsed
+/// values to a `ValueBuilder`
+struct Seed<'a, B> {
+    typ: &'a AnalysedType,
+    options: &'a JsonOptions,
+    builder: &'a RefCell<B>,
+}
+
+impl<B> Clone for Seed<'_, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B> Copy for Seed<'_, B> {}
+
+impl<'a, B: ValueBuilder> Seed<'a, B> {
+    fn new(typ: &'a AnalysedType, options: &'a JsonOptions, builder: &'a RefCell<B>) -> Self {
+        Self {
+            typ,
+            options,
+            builder,
+        }
+    }
+
+    fn with_type(self, typ: &'a AnalysedType) -> Self {
+        Self { typ, ..self }
+    }
+}
+
+impl<'de, B: ValueBuilder> DeserializeSeed<'de> for Seed<'_, B> {
+    type Value = B::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.typ {
+            AnalysedType::List(TypeList { inner }) => deserializer.deserialize_seq(ListVisitor {
+                seed: self.with_type(inner),
+            }),
+            AnalysedType::Tuple(TypeTuple { items }) => {
+                deserializer.deserialize_seq(TupleVisitor { seed: self, items })
+            }
+            AnalysedType::Option(TypeOption { inner }) => {
+                deserializer.deserialize_option(OptionVisitor {
+                    seed: self.with_type(inner),
+                })
+            }
+            AnalysedType::Record(TypeRecord { fields }) => {
+                deserializer.deserialize_map(RecordVisitor { seed: self, fields })
+            }
+            AnalysedType::Result(TypeResult { ok, err }) => {
+                deserializer.deserialize_map(ResultVisitor {
+                    seed: self,
+                    ok,
+                    err,
+                })
+            }
+            AnalysedType::Variant(TypeVariant { cases }) => {
+                deserializer.deserialize_map(VariantVisitor { seed: self, cases })
+            }
+            _ => {
+                let json = JsonValue::deserialize(deserializer)?;
+                let value =
+                    TypeAnnotatedValue::parse_with_type_and_options(&json, self.typ, self.options)
+                        .map_err(|errors| D::Error::custom(errors.join(", ")))?;
+                self.builder
+                    .borrow_mut()
+                    .parsed(value)
+                    .map_err(D::Error::custom)
+            }
+        }
+    }
+}
+
+struct ListVisitor<'a, B> {
+    seed: Seed<'a, B>,
+}
+
+impl<'de, B: ValueBuilder> Visitor<'de> for ListVisitor<'_, B> {
+    type Value = B::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an array representing a list")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element_seed(self.seed)? {
+            values.push(value);
+        }
+        Ok(self.seed.builder.borrow_mut().list(self.seed.typ, values))
+    }
+}
+
+struct TupleVisitor<'a, B> {
+    seed: Seed<'a, B>,
+    items: &'a [AnalysedType],
+}
+
+impl<'de, B: ValueBuilder> Visitor<'de> for TupleVisitor<'_, B> {
+    type Value = B::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "an array of {} items representing a tuple",
+            self.items.len()
+        )
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(self.items.len());
+        for (idx, item) in self.items.iter().enumerate() {
+            match seq.next_element_seed(self.seed.with_type(item))? {
+                Some(value) => values.push(value),
+                None => return Err(A::Error::invalid_length(idx, &self)),
+            }
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(A::Error::invalid_length(self.items.len() + 1, &self));
+        }
+        Ok(self.seed.builder.borrow_mut().tuple(self.items, values))
+    }
+}
+
+struct OptionVisitor<'a, B> {
+    seed: Seed<'a, B>,
+}
+
+impl<'de, B: ValueBuilder> Visitor<'de> for OptionVisitor<'_, B> {
+    type Value = B::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("null or a value representing an option")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(self.seed.builder.borrow_mut().option(self.seed.typ, None))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nested = self.seed.options.disambiguate_nested_options
+            && matches!(self.seed.typ, AnalysedType::Option(_));
+        let value = if nested {
+            deserializer.deserialize_map(SomeVisitor { seed: self.seed })?
+        } else {
+            self.seed.deserialize(deserializer)?
+        };
+        Ok(self
+            .seed
+            .builder
+            .borrow_mut()
+            .option(self.seed.typ, Some(value)))
+    }
+}
+
+/// Visits the `{"some": ...}` object representing a nested option.
+struct SomeVisitor<'a, B> {
+    seed: Seed<'a, B>,
+}
+
+impl<'de, B: ValueBuilder> Visitor<'de> for SomeVisitor<'_, B> {
+    type Value = B::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a {\"some\": ...} object representing a nested option")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match map.next_key::<String>()? {
+            Some(key) if key == "some" => {
+                let value = map.next_value_seed(self.seed)?;
+                match map.next_key::<String>()? {
+                    None => Ok(value),
+                    Some(key) => Err(A::Error::unknown_field(&key, &["some"])),
+                }
+            }
+            Some(key) => Err(A::Error::unknown_field(&key, &["some"])),
+            None => Err(A::Error::missing_field("some")),
+        }
+    }
+}
+
+struct RecordVisitor<'a, B> {
+    seed: Seed<'a, B>,
+    fields: &'a [NameTypePair],
+}
+
+impl<'de, B: ValueBuilder> Visitor<'de> for RecordVisitor<'_, B> {
+    type Value = B::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an object representing a record")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut values: Vec<Option<B::Value>> = vec![None; self.fields.len()];
+
+        while let Some(key) = map.next_key::<String>()? {
+            match self.fields.iter().position(|field| field.name == key) {
+                Some(idx) => {
+                    values[idx] =
+                        Some(map.next_value_seed(self.seed.with_type(&self.fields[idx].typ))?);
+                }
+                None => skip_unknown_field(&mut map, &key, self.seed.options)?,
+            }
+        }
+
+        let mut result = Vec::with_capacity(self.fields.len());
+        for (NameTypePair { name, typ }, value) in self.fields.iter().zip(values) {
+            match (value, typ) {
+                (Some(value), _) => result.push((name.clone(), value)),
+                (None, AnalysedType::Option(TypeOption { inner }))
+                    if self.seed.options.missing_option_fields_as_none =>
+                {
+                    let none = self.seed.builder.borrow_mut().option(inner, None);
+                    result.push((name.clone(), none))
+                }
+                (None, _) => {
+                    return Err(A::Error::custom(format!(
+                        "Key '{}' not found in json_map",
+                        name
+                    )))
+                }
+            }
+        }
+
+        Ok(self.seed.builder.borrow_mut().record(self.fields, result))
+    }
+}
+
+struct ResultVisitor<'a, B> {
+    seed: Seed<'a, B>,
+    ok: &'a Option<Box<AnalysedType>>,
+    err: &'a Option<Box<AnalysedType>>,
+}
+
+impl<B: ValueBuilder> ResultVisitor<'_, B> {
+    fn case_value<'de, A>(
+        &self,
+        map: &mut A,
+        key: &str,
+        typ: &Option<Box<AnalysedType>>,
+    ) -> Result<Option<B::Value>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match typ {
+            Some(typ) => Ok(Some(map.next_value_seed(self.seed.with_type(typ))?)),
+            None => match map.next_value::<Option<IgnoredAny>>()? {
+                None => Ok(None),
+                Some(_) => Err(A::Error::custom(format!(
+                    "The type of {} is absent, but some JSON value was provided",
+                    key
+                ))),
+            },
+        }
+    }
+}
+
+impl<'de, B: ValueBuilder> Visitor<'de> for ResultVisitor<'_, B> {
+    type Value = B::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an {\"ok\": ...} or {\"err\": ...} object representing a result")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut result = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "ok" | "err" if result.is_some() => {
+                    return Err(A::Error::custom(
+                        "Result has both ok value and err value".to_string(),
+                    ))
+                }
+                "ok" => result = Some(Ok(self.case_value(&mut map, &key, self.ok)?)),
+                "err" => result = Some(Err(self.case_value(&mut map, &key, self.err)?)),
+                _ => skip_unknown_field(&mut map, &key, self.seed.options)?,
+            }
+        }
+
+        match result {
+            Some(result) => Ok(self
+                .seed
+                .builder
+                .borrow_mut()
+                .result(self.ok, self.err, result)),
+            None => Err(A::Error::custom(
+                "Failed to retrieve either ok value or err value".to_string(),
+            )),
+        }
+    }
+}
+
+struct VariantVisitor<'a, B> {
+    seed: Seed<'a, B>,
+    cases: &'a [NameOptionTypePair],
+}
+
+impl<B: ValueBuilder> VariantVisitor<'_, B> {
+    fn case<E: serde::de::Error>(&self, case_name: &str) -> Result<&NameOptionTypePair, E> {
+        find_case(self.cases, case_name, self.seed.options)
+            .ok_or_else(|| E::custom(format!("Unknown key {case_name} in the variant")))
+    }
+
+    fn case_value<'de, A>(
+        &self,
+        map: &mut A,
+        case: &NameOptionTypePair,
+    ) -> Result<Option<B::Value>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match &case.typ {
+            Some(typ) => Ok(Some(map.next_value_seed(self.seed.with_type(typ))?)),
+            None => match map.next_value::<Option<IgnoredAny>>()? {
+                None => Ok(None),
+                Some(_) => Err(A::Error::custom(format!(
+                    "Unit variant {} has non-null JSON value",
+                    case.name
+                ))),
+            },
+        }
+    }
+
+    fn buffered_case_value<E: serde::de::Error>(
+        &self,
+        case: &NameOptionTypePair,
+        json: JsonValue,
+    ) -> Result<Option<B::Value>, E> {
+        match &case.typ {
+            Some(typ) => {
+                let value =
+                    TypeAnnotatedValue::parse_with_type_and_options(&json, typ, self.seed.options)
+                        .map_err(|errors| E::custom(errors.join(", ")))?;
+                self.seed
+                    .builder
+                    .borrow_mut()
+                    .parsed(value)
+                    .map(Some)
+                    .map_err(E::custom)
+            }
+            None if json.is_null() => Ok(None),
+            None => Err(E::custom(format!(
+                "Unit variant {} has non-null JSON value",
+                case.name
+            ))),
+        }
+    }
+
+    fn variant(&self, case: &NameOptionTypePair, value: Option<B::Value>) -> B::Value {
+        self.seed
+            .builder
+            .borrow_mut()
+            .variant(self.cases, &case.name, value)
+    }
+}
+
+impl<'de, B: ValueBuilder> Visitor<'de> for VariantVisitor<'_, B> {
+    type Value = B::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an object representing a variant")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match &self.seed.options.variant_tagging {
+            VariantTagging::External => {
+                // keys not naming a case are unknown fields, the same as in `get_variant`
+                let mut found = None;
+                let mut unknown_key = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match find_case(self.cases, &key, self.seed.options) {
+                        Some(_) if found.is_some() => {
+                            return Err(A::Error::custom(
+                                "More than one variant case in the input".to_string(),
+                            ))
+                        }
+                        Some(case) => found = Some((case, self.case_value(&mut map, case)?)),
+                        None => {
+                            skip_unknown_field(&mut map, &key, self.seed.options)?;
+                            unknown_key.get_or_insert(key);
+                        }
+                    }
+                }
+                match (found, unknown_key) {
+                    (Some((case, value)), _) => Ok(self.variant(case, value)),
+                    (None, Some(key)) => Err(A::Error::custom(format!(
+                        "Unknown key {key} in the variant"
+                    ))),
+                    (None, None) => Err(A::Error::custom("Zero variants in the input".to_string())),
+                }
+            }
+            VariantTagging::Adjacent { tag, content } => {
+                let mut case: Option<&NameOptionTypePair> = None;
+                let mut value: Option<Option<B::Value>> = None;
+                // the content can only be streamed if the tag precedes it
+                let mut buffered: Option<JsonValue> = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    if &key == tag {
+                        case = Some(self.case::<A::Error>(&map.next_value::<String>()?)?);
+                    } else if &key == content {
+                        match case {
+                            Some(case) => value = Some(self.case_value(&mut map, case)?),
+                            None => buffered = Some(map.next_value()?),
+                        }
+                    } else {
+                        skip_unknown_field(&mut map, &key, self.seed.options)?;
+                    }
+                }
+
+                let case = case.ok_or_else(|| {
+                    A::Error::custom(format!(
+                        "Input has no string field {} holding the variant case name",
+                        tag
+                    ))
+                })?;
+                let value = match (value, buffered) {
+                    (Some(value), _) => value,
+                    (None, Some(json)) => self.buffered_case_value(case, json)?,
+                    (None, None) => self.buffered_case_value(case, JsonValue::Null)?,
+                };
+                Ok(self.variant(case, value))
+            }
+        }
+    }
+}
+
+fn skip_unknown_field<'de, A>(map: &mut A, key: &str, options: &JsonOptions) -> Result<(), A::Error>
+where
+    A: MapAccess<'de>,
+{
+    if options.deny_unknown_fields {
+        Err(A::Error::custom(format!("Unknown field {}", key)))
+    } else {
+        map.next_value::<IgnoredAny>().map(|_| ())
+    }
+}
+
+/// A `Serialize` implementation writing the JSON representation of a typed value, the same as
+/// `to_json_value_with_options`, without building an intermediate `serde_json::Value`.
+#[derive(Debug, Clone, Copy)]
+pub struct TypedJson<'a> {
+    value: &'a TypeAnnotatedValue,
+    options: &'a JsonOptions,
+}
+
+impl<'a> TypedJson<'a> {
+    pub fn new(value: &'a TypeAnnotatedValue, options: &'a JsonOptions) -> Self {
+        Self { value, options }
+    }
+
+    fn inner<E: serde::ser::Error>(
+        self,
+        value: &'a protobuf::TypeAnnotatedValue,
+        context: &str,
+    ) -> Result<Self, E> {
+        let value = value
+            .type_annotated_value
+            .as_ref()
+            .ok_or_else(|| E::custom(format!("Missing type_annotated_value in {}", context)))?;
+        Ok(Self {
+            value,
+            options: self.options,
+        })
+    }
+
+    fn serialize_float<S: Serializer>(&self, value: f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_finite() {
+            serializer.serialize_f64(value)
+        } else {
+            match self.options.non_finite_floats {
+                NonFiniteFloats::String => serializer.serialize_str(&non_finite_float_name(value)),
+                NonFiniteFloats::Null => serializer.serialize_unit(),
+                NonFiniteFloats::Error => Err(S::Error::custom(format!(
+                    "The value {} cannot be represented as a JSON number",
+                    value
+                ))),
+            }
+        }
+    }
+}
+
+impl Serialize for TypedJson<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let options = self.options;
+        match self.value {
+            TypeAnnotatedValue::Bool(value) => serializer.serialize_bool(*value),
+            TypeAnnotatedValue::S8(value)
+            | TypeAnnotatedValue::S16(value)
+            | TypeAnnotatedValue::S32(value) => serializer.serialize_i32(*value),
+            TypeAnnotatedValue::U8(value)
+            | TypeAnnotatedValue::U16(value)
+            | TypeAnnotatedValue::U32(value) => serializer.serialize_u32(*value),
+            TypeAnnotatedValue::S64(value) => {
+                if options.int64_as_string {
+                    serializer.serialize_str(&value.to_string())
+                } else {
+                    serializer.serialize_i64(*value)
+                }
+            }
+            TypeAnnotatedValue::U64(value) => {
+                if options.int64_as_string {
+                    serializer.serialize_str(&value.to_string())
+                } else {
+                    serializer.serialize_u64(*value)
+                }
+            }
+            TypeAnnotatedValue::F32(value) => self.serialize_float(*value as f64, serializer),
+            TypeAnnotatedValue::F64(value) => self.serialize_float(*value, serializer),
+            TypeAnnotatedValue::Char(value) => {
+                match char::from_u32(*value as u32).filter(|_| options.char_as_string) {
+                    Some(char) => serializer.serialize_char(char),
+                    None => serializer.serialize_u32(*value as u32),
+                }
+            }
+            TypeAnnotatedValue::Str(value) => serializer.serialize_str(value),
+            TypeAnnotatedValue::Enum(protobuf::TypedEnum { typ: _, value }) => {
+                serializer.serialize_str(value)
+            }
+            TypeAnnotatedValue::Flags(protobuf::TypedFlags { typ: _, values }) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            TypeAnnotatedValue::Option(option) => match &option.value {
+                Some(value) => {
+                    let inner = self.inner(value, "Option")?;
+                    if options.disambiguate_nested_options && is_option_type(&option.typ) {
+                        let mut map = serializer.serialize_map(Some(1))?;
+                        map.serialize_entry("some", &inner)?;
+                        map.end()
+                    } else {
+                        inner.serialize(serializer)
+                    }
+                }
+                None => serializer.serialize_none(),
+            },
+            TypeAnnotatedValue::Tuple(protobuf::TypedTuple { typ: _, value }) => {
+                let mut seq = serializer.serialize_seq(Some(value.len()))?;
+                for value in value {
+                    seq.serialize_element(&self.inner(value, "Tuple")?)?;
+                }
+                seq.end()
+            }
+            TypeAnnotatedValue::List(protobuf::TypedList { typ: _, values }) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(&self.inner(value, "List")?)?;
+                }
+                seq.end()
+            }
+            TypeAnnotatedValue::Record(protobuf::TypedRecord { typ: _, value }) => {
+                let mut map = serializer.serialize_map(Some(value.len()))?;
+                for name_value in value {
+                    let value = name_value
+                        .value
+                        .as_ref()
+                        .ok_or_else(|| S::Error::custom("Missing value in Record"))?;
+                    map.serialize_entry(&name_value.name, &self.inner(value, "Record")?)?;
+                }
+                map.end()
+            }
+            TypeAnnotatedValue::Variant(variant) => {
+                let case_value = variant
+                    .case_value
+                    .as_ref()
+                    .map(|value| self.inner(value, "Variant"))
+                    .transpose()?;
+
+                match &options.variant_tagging {
+                    VariantTagging::External => {
+                        let mut map = serializer.serialize_map(Some(1))?;
+                        map.serialize_entry(&variant.case_name, &case_value)?;
+                        map.end()
+                    }
+                    VariantTagging::Adjacent { tag, content } => {
+                        let len = if case_value.is_some() { 2 } else { 1 };
+                        let mut map = serializer.serialize_map(Some(len))?;
+                        map.serialize_entry(tag, &variant.case_name)?;
+                        if let Some(case_value) = case_value {
+                            map.serialize_entry(content, &case_value)?;
+                        }
+                        map.end()
+                    }
+                }
+            }
+            TypeAnnotatedValue::Result(result) => {
+                let result_value = result
+                    .result_value
+                    .as_ref()
+                    .ok_or_else(|| S::Error::custom("Missing value in Result"))?;
+                let (key, value) = match result_value {
+                    ResultValue::OkValue(value) => ("ok", value),
+                    ResultValue::ErrorValue(value) => ("err", value),
+                };
+                let value = value.type_annotated_value.as_ref().map(|value| TypedJson {
+                    value,
+                    options: self.options,
+                });
+
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(key, &value)?;
+                map.end()
+            }
+            TypeAnnotatedValue::Handle(protobuf::TypedHandle {
+                typ: _,
+                uri,
+                resource_id,
+            }) => serializer.serialize_str(&format!("{}/{}", uri, resource_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use golem_wasm_ast::analysis::{
+        AnalysedType, NameOptionTypePair, NameTypePair, TypeF64, TypeList, TypeOption, TypeRecord,
+        TypeResult, TypeStr, TypeTuple, TypeU32, TypeU64, TypeVariant,
+    };
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    use crate::json::{
+        from_reader, to_writer, wit_value_from_reader, JsonOptions,
+        TypeAnnotatedValueJsonExtensions, VariantTagging,
+    };
+    use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
+    use crate::Value;
+
+    fn item_type() -> AnalysedType {
+        AnalysedType::Record(TypeRecord {
+            fields: vec![
+                NameTypePair {
+                    name: "id".to_string(),
+                    typ: AnalysedType::U64(TypeU64),
+                },
+                NameTypePair {
+                    name: "tags".to_string(),
+                    typ: AnalysedType::List(TypeList {
+                        inner: Box::new(AnalysedType::Str(TypeStr)),
+                    }),
+                },
+                NameTypePair {
+                    name: "price".to_string(),
+                    typ: AnalysedType::Option(TypeOption {
+                        inner: Box::new(AnalysedType::F64(TypeF64)),
+                    }),
+                },
+                NameTypePair {
+                    name: "status".to_string(),
+                    typ: AnalysedType::Variant(TypeVariant {
+                        cases: vec![
+                            NameOptionTypePair {
+                                name: "draft".to_string(),
+                                typ: None,
+                            },
+                            NameOptionTypePair {
+                                name: "published".to_string(),
+                                typ: Some(AnalysedType::Tuple(TypeTuple {
+                                    items: vec![
+                                        AnalysedType::U32(TypeU32),
+                                        AnalysedType::Str(TypeStr),
+                                    ],
+                                })),
+                            },
+                        ],
+                    }),
+                },
+                NameTypePair {
+                    name: "checked".to_string(),
+                    typ: AnalysedType::Result(TypeResult {
+                        ok: None,
+                        err: Some(Box::new(AnalysedType::Str(TypeStr))),
+                    }),
+                },
+            ],
+        })
+    }
+
+    fn items_type() -> AnalysedType {
+        AnalysedType::List(TypeList {
+            inner: Box::new(item_type()),
+        })
+    }
+
+    fn items_json() -> serde_json::Value {
+        json!([
+            {
+                "id": 1,
+                "tags": ["a", "b"],
+                "price": 9.5,
+                "status": { "published": [2024, "v1"] },
+                "checked": { "ok": null }
+            },
+            {
+                "status": { "draft": null },
+                "unknown": { "ignored": [1, 2, 3] },
+                "tags": [],
+                "checked": { "err": "too long" },
+                "id": 2
+            }
+        ])
+    }
+
+    #[test]
+    fn streaming_parse_matches_parse_with_type() {
+        let json = items_json();
+        let options = JsonOptions::default();
+        let expected = TypeAnnotatedValue::parse_with_type(&json, &items_type()).unwrap();
+        let streamed = from_reader(json.to_string().as_bytes(), &items_type(), &options).unwrap();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn streaming_write_matches_to_json_value() {
+        let options = JsonOptions::default();
+        let value = TypeAnnotatedValue::parse_with_type(&items_json(), &items_type()).unwrap();
+
+        let mut bytes = Vec::new();
+        to_writer(&mut bytes, &value, &options).unwrap();
+        let written: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(written, value.to_json_value().unwrap());
+    }
+
+    #[test]
+    fn streaming_parse_into_wit_value() {
+        let typ = AnalysedType::List(TypeList {
+            inner: Box::new(AnalysedType::U32(TypeU32)),
+        });
+        let wit_value =
+            wit_value_from_reader("[1, 2, 3]".as_bytes(), &typ, &JsonOptions::default()).unwrap();
+        assert_eq!(
+            Value::from(wit_value),
+            Value::List(vec![Value::U32(1), Value::U32(2), Value::U32(3)])
+        );
+    }
+
+    #[test]
+    fn streaming_parse_of_composite_wit_value() {
+        let json = items_json().to_string();
+        let options = JsonOptions::default();
+        let expected = from_reader(json.as_bytes(), &items_type(), &options).unwrap();
+        let wit_value = wit_value_from_reader(json.as_bytes(), &items_type(), &options).unwrap();
+        assert_eq!(Value::from(wit_value), Value::try_from(expected).unwrap());
+
+        // adjacently tagged contents preceding the tag are buffered
+        let options = JsonOptions {
+            variant_tagging: VariantTagging::adjacent(),
+            ..JsonOptions::default()
+        };
+        let json = r#"[{"id": 3, "tags": ["x"], "status": {"value": [1, "y"], "tag": "published"}, "checked": {"ok": null}}]"#;
+        let expected = from_reader(json.as_bytes(), &items_type(), &options).unwrap();
+        let wit_value = wit_value_from_reader(json.as_bytes(), &items_type(), &options).unwrap();
+        assert_eq!(Value::from(wit_value), Value::try_from(expected).unwrap());
+    }
+
+    #[test]
+    fn streaming_accepts_the_same_input_as_parse_with_type() {
+        let typ = item_type();
+        for (json, options) in [
+            (
+                r#"{"id": 1, "tags": [], "status": {"draft": null}, "checked": {"ok": null, "err": "e"}}"#,
+                JsonOptions::default(),
+            ),
+            (
+                r#"{"id": 1, "tags": [], "status": {"draft": null, "note": 1}, "checked": {"ok": null}}"#,
+                JsonOptions::default(),
+            ),
+            (
+                r#"{"id": 1, "tags": [], "status": {"note": 1, "draft": null}, "checked": {"ok": null}}"#,
+                JsonOptions::strict(),
+            ),
+            (
+                r#"{"id": 1, "tags": [], "status": {"draft": null, "published": [1, "x"]}, "checked": {"ok": null}}"#,
+                JsonOptions::default(),
+            ),
+            (
+                r#"{"id": 1, "tags": [], "status": {"note": 1}, "checked": {"ok": null}}"#,
+                JsonOptions::default(),
+            ),
+        ] {
+            let tree = TypeAnnotatedValue::parse_with_type_and_options(
+                &serde_json::from_str(json).unwrap(),
+                &typ,
+                &options,
+            );
+            let streamed = from_reader(json.as_bytes(), &typ, &options);
+            assert_eq!(tree.is_ok(), streamed.is_ok(), "{json}");
+            if let (Ok(tree), Ok(streamed)) = (tree, streamed) {
+                assert_eq!(tree, streamed);
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_parse_errors() {
+        let options = JsonOptions::default();
+        let typ = items_type();
+        for json in [
+            r#"[{"id": 1, "tags": []}]"#,
+            r#"[{"id": -1, "tags": [], "status": {"draft": null}, "checked": {"ok": null}}]"#,
+            r#"[{"id": 1, "tags": [], "status": {"draft": 1}, "checked": {"ok": null}}]"#,
+            r#"[{"id": 1, "tags": [], "status": {"other": null}, "checked": {"ok": null}}]"#,
+            r#"[{"id": 1, "tags": [], "status": {"draft": null}, "checked": {"ok": 1}}]"#,
+            r#"[{"id": 1, "tags": [], "status": {"published": [1]}, "checked": {"ok": null}}]"#,
+            r#"[] trailing"#,
+        ] {
+            assert!(
+                from_reader(json.as_bytes(), &typ, &options).is_err(),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_adjacent_tagging_in_any_order() {
+        let options = JsonOptions {
+            variant_tagging: VariantTagging::adjacent(),
+            ..JsonOptions::default()
+        };
+        let typ = match item_type() {
+            AnalysedType::Record(TypeRecord { fields }) => fields[3].typ.clone(),
+            _ => unreachable!(),
+        };
+        let expected = TypeAnnotatedValue::parse_with_type_and_options(
+            &json!({ "tag": "published", "value": [1, "x"] }),
+            &typ,
+            &options,
+        )
+        .unwrap();
+
+        for json in [
+            r#"{"tag": "published", "value": [1, "x"]}"#,
+            r#"{"value": [1, "x"], "tag": "published"}"#,
+        ] {
+            assert_eq!(
+                from_reader(json.as_bytes(), &typ, &options).unwrap(),
+                expected
+            );
+        }
+    }
+
+    fn round_trip_options() -> impl Strategy<Value = JsonOptions> {
+        (any::<bool>(), any::<bool>(), any::<bool>(), any::<bool>()).prop_map(
+            |(char_as_string, int64_as_string, disambiguate_nested_options, adjacent)| {
+                JsonOptions {
+                    char_as_string,
+                    int64_as_string,
+                    disambiguate_nested_options,
+                    variant_tagging: if adjacent {
+                        VariantTagging::adjacent()
+                    } else {
+                        VariantTagging::External
+                    },
+                    ..JsonOptions::default()
+                }
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn streaming_round_trip(
+            ids in proptest::collection::vec(any::<u64>(), 0..10),
+            price in proptest::option::of(
+                proptest::num::f64::NORMAL | proptest::num::f64::SUBNORMAL | proptest::num::f64::ZERO
+            ),
+            options in round_trip_options(),
+        ) {
+            let json = serde_json::Value::Array(ids.iter().map(|id| json!({
+                "id": id,
+                "tags": [id.to_string()],
+                "price": price,
+                "status": { "published": [7, "v"] },
+                "checked": { "err": "e" }
+            })).collect());
+            let value = TypeAnnotatedValue::parse_with_type(&json, &items_type()).unwrap();
+
+            let mut bytes = Vec::new();
+            to_writer(&mut bytes, &value, &options).unwrap();
+            let written: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            prop_assert_eq!(&written, &value.to_json_value_with_options(&options).unwrap());
+
+            let parsed = from_reader(bytes.as_slice(), &items_type(), &options).unwrap();
+            prop_assert_eq!(parsed, value);
+        }
+    }
+
+    #[test]
+    fn streaming_strict_mode() {
+        let options = JsonOptions::strict();
+        let json = r#"[{"id": 1, "tags": [], "price": null, "status": {"draft": null}, "checked": {"ok": null}, "extra": 1}]"#;
+        assert!(from_reader(json.as_bytes(), &items_type(), &options).is_err());
+
+        let json = r#"[{"id": 1, "tags": [], "status": {"draft": null}, "checked": {"ok": null}}]"#;
+        assert!(from_reader(json.as_bytes(), &items_type(), &options).is_err());
+        assert!(from_reader(json.as_bytes(), &items_type(), &JsonOptions::default()).is_ok());
+    }
+}
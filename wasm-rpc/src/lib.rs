@@ -27,10 +27,18 @@ mod builder;
 /// Extension methods for extracting values from WitValue instances
 mod extractor;
 
+/// Invocations over HTTP, and a server exposing component instances to them
+#[cfg(feature = "http")]
+pub mod http;
+
 /// Conversion to and from JSON, in the presence of golem-wasm-ast generated type information
 #[cfg(feature = "json")]
 pub mod json;
 
+/// An in-process transport for the wasm-rpc host interface
+#[cfg(feature = "local")]
+pub mod local;
+
 /// Poem OpenAPI integration for some types
 #[cfg(feature = "poem_openapi")]
 pub mod poem;
@@ -47,6 +55,10 @@ pub mod serde;
 #[cfg(feature = "text")]
 mod text;
 
+/// A pluggable transport behind the wasm-rpc host interface
+#[cfg(feature = "transport")]
+pub mod transport;
+
 /// A version of values annotated with golem-wasm-ast generated type information
 #[cfg(feature = "typeinfo")]
 mod type_annotated_value;
@@ -62,7 +74,8 @@ pub use extractor::{WitNodePointer, WitValueExtractor};
 #[cfg(not(feature = "host"))]
 #[cfg(feature = "stub")]
 pub use bindings::golem::rpc::types::{
-    FutureInvokeResult, NodeIndex, RpcError, Uri, WasmRpc, WitNode, WitValue,
+    BatchInvocation, BatchOptions, FutureBatchInvokeResult, FutureInvokeResult, InvocationOptions,
+    InvokeResultStream, NodeIndex, RetryPolicy, RpcError, Uri, WasmRpc, WitNode, WitValue,
 };
 #[cfg(not(feature = "host"))]
 #[cfg(feature = "stub")]
@@ -86,22 +99,47 @@ bindgen!({
     with: {
         "golem:rpc/types/wasm-rpc": WasmRpcEntry,
         "golem:rpc/types/future-invoke-result": FutureInvokeResultEntry,
+        "golem:rpc/types/future-batch-invoke-result": FutureBatchInvokeResultEntry,
+        "golem:rpc/types/invoke-result-stream": InvokeResultStreamEntry,
         "wasi:io/poll/pollable": Pollable,
     }
 });
 
 #[cfg(feature = "host")]
-pub use golem::rpc::types::{Host, HostWasmRpc, NodeIndex, RpcError, Uri, WitNode, WitValue};
+pub use golem::rpc::types::{
+    BatchInvocation, BatchOptions, Host, HostWasmRpc, InvocationOptions, NodeIndex, RetryPolicy,
+    RpcError, Uri, WitNode, WitValue,
+};
 
 #[cfg(feature = "host")]
 pub struct WasmRpcEntry {
     pub payload: Box<dyn std::any::Any + Send + Sync>,
+    /// Options applied to every invocation through the resource. The options passed to an
+    /// invocation take precedence over these.
+    pub options: InvocationOptions,
+}
+
+// The type is generated by bindgen, which cannot derive Default only for it
+#[cfg(feature = "host")]
+#[allow(clippy::derivable_impls)]
+impl Default for InvocationOptions {
+    fn default() -> Self {
+        Self {
+            idempotency_key: None,
+            retry_policy: None,
+            headers: vec![],
+            timeout_nanos: None,
+        }
+    }
 }
 
 #[cfg(feature = "host")]
 #[async_trait::async_trait]
 pub trait SubscribeAny: std::any::Any {
     async fn ready(&mut self);
+    /// Stops waiting for the result, making `ready` return immediately. Payloads which cannot be
+    /// cancelled keep waiting for the result.
+    fn cancel(&mut self) {}
     fn as_any(&self) -> &dyn std::any::Any;
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
 }
@@ -119,11 +157,51 @@ impl wasmtime_wasi::Subscribe for FutureInvokeResultEntry {
     }
 }
 
+#[cfg(feature = "host")]
+pub struct FutureBatchInvokeResultEntry {
+    pub payload: Box<dyn SubscribeAny + Send + Sync>,
+}
+
+#[cfg(feature = "host")]
+#[async_trait::async_trait]
+impl wasmtime_wasi::Subscribe for FutureBatchInvokeResultEntry {
+    async fn ready(&mut self) {
+        self.payload.ready().await
+    }
+}
+
+/// Like `SubscribeAny`, for results delivered in parts
+#[cfg(feature = "host")]
+#[async_trait::async_trait]
+pub trait SubscribeStream: std::any::Any {
+    /// Waits until the next part is available or the stream has ended
+    async fn ready(&mut self);
+    fn as_any(&self) -> &dyn std::any::Any;
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+}
+
+#[cfg(feature = "host")]
+pub struct InvokeResultStreamEntry {
+    pub payload: Box<dyn SubscribeStream + Send + Sync>,
+}
+
+#[cfg(feature = "host")]
+#[async_trait::async_trait]
+impl wasmtime_wasi::Subscribe for InvokeResultStreamEntry {
+    async fn ready(&mut self) {
+        self.payload.ready().await
+    }
+}
+
 #[cfg(feature = "typeinfo")]
 pub use type_annotated_value::*;
 
 #[cfg(feature = "text")]
-pub use text::{type_annotated_value_from_str, type_annotated_value_to_string};
+pub use text::{
+    parse_function_call, type_annotated_value_from_str, type_annotated_value_to_string,
+    type_annotated_value_to_string_pretty, value_from_wave, value_to_wave, value_to_wave_pretty,
+    ParsedFunctionCall, WavePrettyOptions,
+};
 
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for Uri {
@@ -139,6 +217,26 @@ impl PartialEq for Uri {
     }
 }
 
+/// A request to invoke a function of a remote worker
+#[derive(Debug, Clone)]
+pub struct InvocationRequest {
+    pub target: Uri,
+    pub function_name: String,
+    pub params: Vec<WitValue>,
+    /// Invocations with the same idempotency key are only performed once
+    pub idempotency_key: Option<String>,
+}
+
+/// A request to invoke several functions of the same remote worker at once
+#[derive(Debug, Clone)]
+pub struct BatchInvocationRequest {
+    pub target: Uri,
+    pub invocations: Vec<BatchInvocation>,
+    pub options: BatchOptions,
+    /// Batches with the same idempotency key are only performed once
+    pub idempotency_key: Option<String>,
+}
+
 /// A tree representation of Value - isomorphic to the protobuf Val type but easier to work with in Rust
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -0,0 +1,536 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use golem_wasm_ast::analysis::AnalysedType;
+use tokio::sync::Mutex;
+use wasmtime::component::{Func, Instance, ResourceAny, Val};
+use wasmtime::Store;
+
+use crate::transport::{RpcTransport, WasmRpcCtx};
+use crate::wasmtime::{
+    decode_param, encode_output, type_to_analysed_type, EncodingError, PathSegment, ResourceStore,
+    ResourceTypes,
+};
+use crate::{RpcError, Uri, Value, WitValue};
+
+/// An `RpcTransport` routing wasm-rpc calls to component instances running in the same process,
+/// resolved by the `Uri` they were registered with.
+///
+/// The registered instances must be created by an engine with async support. Function names are
+/// resolved the same way as the names used by the generated stubs, for example `add`,
+/// `golem:it/api.{add-item}` or `golem:it/api.{cart.add-item}`, and the results of a call are
+/// returned as a tuple.
+///
+/// A worker handles one call at a time, so a call that would re-enter a worker already handling
+/// a call of the same chain, like a worker calling itself, fails with a `denied` error instead of
+/// waiting forever.
+///
+/// Clones share the registered instances.
+pub struct LocalWasmRpcHost<T> {
+    workers: Arc<std::sync::Mutex<HashMap<String, SharedWorker<T>>>>,
+}
+
+type SharedWorker<T> = Arc<Mutex<LocalWorker<T>>>;
+
+tokio::task_local! {
+    /// The workers handling the calls that led to the current one, outermost first
+    static ACTIVE_WORKERS: Vec<String>;
+}
+
+impl<T> Clone for LocalWasmRpcHost<T> {
+    fn clone(&self) -> Self {
+        Self {
+            workers: self.workers.clone(),
+        }
+    }
+}
+
+impl<T> Default for LocalWasmRpcHost<T> {
+    fn default() -> Self {
+        Self {
+            workers: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<T: Send + 'static> LocalWasmRpcHost<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a component instance to handle the calls addressed to `uri`, replacing any
+    /// previously registered instance.
+    pub fn register(&self, uri: Uri, store: Store<T>, instance: Instance) {
+        let worker = LocalWorker {
+            store,
+            instance,
+            resources: LocalResourceStore {
+                uri: uri.clone(),
+                next_id: 0,
+                resources: HashMap::new(),
+            },
+            resource_types: ResourceTypes::new(),
+        };
+        self.workers
+            .lock()
+            .unwrap()
+            .insert(uri.value, Arc::new(Mutex::new(worker)));
+    }
+
+    /// Returns the types of the results of a function of the component instance registered for
+    /// `uri`.
+    ///
+    /// Resource handles are identified by ids assigned per registered instance, so the same
+    /// resource type has the same id in the results of every function of the instance. The ids
+    /// depend on the order in which functions are looked up, so they cannot be compared across
+    /// instances, even of the same component.
+    pub async fn result_types(
+        &self,
+        uri: &Uri,
+        function_name: &str,
+    ) -> Result<Vec<AnalysedType>, RpcError> {
+        let worker = self.worker(uri)?;
+        let mut worker = worker.lock().await;
+        match worker.resolve_function(function_name) {
+            Some(func) => {
+                let LocalWorker {
+                    store,
+                    resource_types,
+                    ..
+                } = &mut *worker;
+                Ok(func
+                    .results(&*store)
+                    .iter()
+                    .map(|typ| type_to_analysed_type(typ, resource_types))
+                    .collect())
+            }
+            None if is_drop(function_name) => Ok(vec![]),
+            None => Err(RpcError::NotFound(format!(
+                "Function {function_name} not found"
+            ))),
+        }
+    }
+
+    fn worker(&self, uri: &Uri) -> Result<SharedWorker<T>, RpcError> {
+        self.workers
+            .lock()
+            .unwrap()
+            .get(&uri.value)
+            .cloned()
+            .ok_or_else(|| RpcError::NotFound(format!("No worker registered for {}", uri.value)))
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> RpcTransport for LocalWasmRpcHost<T> {
+    async fn invoke(
+        &self,
+        uri: &Uri,
+        function_name: &str,
+        params: Vec<WitValue>,
+    ) -> Result<WitValue, RpcError> {
+        let mut active_workers = ACTIVE_WORKERS
+            .try_with(|workers| workers.clone())
+            .unwrap_or_default();
+        if active_workers.contains(&uri.value) {
+            return Err(RpcError::Denied(format!(
+                "Re-entrant call of {function_name} on {}, which is already handling a call \
+                 leading to this one",
+                uri.value
+            )));
+        }
+
+        let worker = self.worker(uri)?;
+        active_workers.push(uri.value.clone());
+        ACTIVE_WORKERS
+            .scope(active_workers, async move {
+                let mut worker = worker.lock().await;
+                worker.invoke(function_name, params).await
+            })
+            .await
+    }
+}
+
+/// Store data implementing the wasm-rpc host interface with a `LocalWasmRpcHost`
+pub type LocalWasmRpcCtx<D, T> = WasmRpcCtx<D, LocalWasmRpcHost<T>>;
+
+struct LocalWorker<T> {
+    store: Store<T>,
+    instance: Instance,
+    resources: LocalResourceStore,
+    resource_types: ResourceTypes,
+}
+
+impl<T: Send> LocalWorker<T> {
+    async fn invoke(
+        &mut self,
+        function_name: &str,
+        params: Vec<WitValue>,
+    ) -> Result<WitValue, RpcError> {
+        let func = match self.resolve_function(function_name) {
+            Some(func) => func,
+            None if is_drop(function_name) => return self.drop_resource(params).await,
+            None => {
+                return Err(RpcError::NotFound(format!(
+                    "Function {function_name} not found"
+                )))
+            }
+        };
+
+        let param_types = func.params(&self.store);
+        if param_types.len() != params.len() {
+            return Err(RpcError::ProtocolError(format!(
+                "Unexpected number of parameters for {function_name} (got {}, expected: {})",
+                params.len(),
+                param_types.len()
+            )));
+        }
+
+        let mut vals = Vec::with_capacity(params.len());
+        for (idx, (param, typ)) in params.into_iter().zip(param_types.iter()).enumerate() {
+            let decoded = decode_param(&param.into(), typ, &mut self.resources)
+                .await
+                .map_err(|err| protocol_error(err.at(PathSegment::Param(idx))))?;
+            vals.push(decoded.val);
+        }
+
+        let result_types = func.results(&self.store);
+        let mut results = vec![Val::Bool(false); result_types.len()];
+        func.call_async(&mut self.store, &vals, &mut results)
+            .await
+            .map_err(|err| RpcError::RemoteInternalError(err.to_string()))?;
+        func.post_return_async(&mut self.store)
+            .await
+            .map_err(|err| RpcError::RemoteInternalError(err.to_string()))?;
+
+        let mut outputs = Vec::with_capacity(results.len());
+        for (idx, (result, typ)) in results.iter().zip(result_types.iter()).enumerate() {
+            let output = encode_output(result, typ, &mut self.resources)
+                .await
+                .map_err(|err| protocol_error(err.at(PathSegment::Result(idx))))?;
+            outputs.push(output);
+        }
+        Ok(Value::Tuple(outputs).into())
+    }
+
+    fn resolve_function(&mut self, function_name: &str) -> Option<Func> {
+        let (interface, item) = split_function_name(function_name);
+        self.find_function(interface, item)
+    }
+
+    fn find_function(&mut self, interface: Option<&str>, item: &str) -> Option<Func> {
+        let mut exports = self.instance.exports(&mut self.store);
+        let mut exports = match interface {
+            Some(interface) => exports.instance(interface)?,
+            None => exports.root(),
+        };
+
+        let mut candidates = vec![item.to_string()];
+        if let Some((resource, function)) = item.split_once('.') {
+            candidates.push(format!("[method]{item}"));
+            candidates.push(format!("[static]{item}"));
+            if function == "new" {
+                candidates.push(format!("[constructor]{resource}"));
+            }
+        }
+        candidates.iter().find_map(|name| exports.func(name))
+    }
+
+    /// Drops the resource passed as the only parameter of a `<resource>.drop` call
+    async fn drop_resource(&mut self, params: Vec<WitValue>) -> Result<WitValue, RpcError> {
+        let resource_id = match params.into_iter().map(Value::from).collect::<Vec<_>>()[..] {
+            [Value::Handle {
+                ref uri,
+                resource_id,
+            }] if *uri == self.resources.uri => resource_id,
+            _ => {
+                return Err(RpcError::ProtocolError(
+                    "Expected a single handle of the worker to drop".to_string(),
+                ))
+            }
+        };
+        let resource = self
+            .resources
+            .get(resource_id)
+            .await
+            .ok_or_else(|| RpcError::NotFound(format!("Resource {resource_id} not found")))?;
+        resource
+            .resource_drop_async(&mut self.store)
+            .await
+            .map_err(|err| RpcError::RemoteInternalError(err.to_string()))?;
+        Ok(Value::Tuple(vec![]).into())
+    }
+}
+
+/// Splits a function name like `golem:it/api.{cart.add-item}` to its interface and item
+fn split_function_name(function_name: &str) -> (Option<&str>, &str) {
+    match function_name
+        .strip_suffix('}')
+        .and_then(|name| name.split_once(".{"))
+    {
+        Some((interface, item)) => (Some(interface), item),
+        None => (None, function_name),
+    }
+}
+
+/// Resource drops are not exported functions but are still invoked by the generated stubs
+fn is_drop(function_name: &str) -> bool {
+    split_function_name(function_name).1.ends_with(".drop")
+}
+
+fn protocol_error(error: EncodingError) -> RpcError {
+    RpcError::ProtocolError(error.to_string())
+}
+
+struct LocalResourceStore {
+    uri: Uri,
+    next_id: u64,
+    resources: HashMap<u64, ResourceAny>,
+}
+
+#[async_trait]
+impl ResourceStore for LocalResourceStore {
+    fn self_uri(&self) -> Uri {
+        self.uri.clone()
+    }
+
+    async fn add(&mut self, resource: ResourceAny) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.resources.insert(id, resource);
+        id
+    }
+
+    async fn get(&mut self, resource_id: u64) -> Option<ResourceAny> {
+        self.resources.remove(&resource_id)
+    }
+
+    async fn borrow(&self, resource_id: u64) -> Option<ResourceAny> {
+        self.resources.get(&resource_id).cloned()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use wasmtime::component::{Component, Linker, Resource};
+    use wasmtime::{Config, Engine, Store};
+    use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
+
+    use crate::golem::rpc::types::HostFutureInvokeResult;
+    use crate::local::{LocalWasmRpcCtx, LocalWasmRpcHost, ACTIVE_WORKERS};
+    use crate::transport::RpcTransport;
+    use crate::{HostWasmRpc, RpcError, Uri, Value, WasmRpcEntry};
+
+    const CALCULATOR: &str = r#"
+        (component
+          (core module $m
+            (func (export "add") (param i32 i32) (result i32)
+              local.get 0
+              local.get 1
+              i32.add)
+            (func (export "fail") (result i32)
+              unreachable))
+          (core instance $i (instantiate $m))
+          (func $add (param "x" u32) (param "y" u32) (result u32)
+            (canon lift (core func $i "add")))
+          (func $fail (result u32)
+            (canon lift (core func $i "fail")))
+          (instance $api
+            (export "add" (func $add))
+            (export "fail" (func $fail)))
+          (export "golem:it/api" (instance $api))
+          (export "add" (func $add)))
+    "#;
+
+    struct TestData {
+        table: ResourceTable,
+        ctx: WasiCtx,
+    }
+
+    impl WasiView for TestData {
+        fn table(&mut self) -> &mut ResourceTable {
+            &mut self.table
+        }
+
+        fn ctx(&mut self) -> &mut WasiCtx {
+            &mut self.ctx
+        }
+    }
+
+    pub(crate) fn calculator_uri() -> Uri {
+        Uri {
+            value: "urn:worker:calculator/1".to_string(),
+        }
+    }
+
+    /// A host with the calculator component registered as `calculator_uri()`
+    pub(crate) async fn calculator_host() -> LocalWasmRpcHost<()> {
+        let mut config = Config::new();
+        config.async_support(true);
+        let engine = Engine::new(&config).unwrap();
+        let component = Component::new(&engine, CALCULATOR).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = Linker::new(&engine)
+            .instantiate_async(&mut store, &component)
+            .await
+            .unwrap();
+
+        let rpc = LocalWasmRpcHost::new();
+        rpc.register(calculator_uri(), store, instance);
+        rpc
+    }
+
+    async fn caller() -> LocalWasmRpcCtx<TestData, ()> {
+        LocalWasmRpcCtx::new(
+            TestData {
+                table: ResourceTable::new(),
+                ctx: WasiCtxBuilder::new().build(),
+            },
+            calculator_host().await,
+        )
+    }
+
+    async fn invoke_and_await(
+        ctx: &mut LocalWasmRpcCtx<TestData, ()>,
+        rpc: &Resource<WasmRpcEntry>,
+        function_name: &str,
+        params: Vec<Value>,
+    ) -> Result<Value, String> {
+        let rpc = Resource::new_borrow(rpc.rep());
+        ctx.invoke_and_await(
+            rpc,
+            function_name.to_string(),
+            params.into_iter().map(|param| param.into()).collect(),
+        )
+        .await
+        .unwrap()
+        .map(Value::from)
+        .map_err(|err| format!("{err:?}"))
+    }
+
+    #[tokio::test]
+    async fn invoke_local_functions() {
+        let mut ctx = caller().await;
+        let rpc = ctx.new(calculator_uri()).await.unwrap();
+
+        assert_eq!(
+            invoke_and_await(&mut ctx, &rpc, "add", vec![Value::U32(1), Value::U32(2)]).await,
+            Ok(Value::Tuple(vec![Value::U32(3)]))
+        );
+        assert_eq!(
+            invoke_and_await(
+                &mut ctx,
+                &rpc,
+                "golem:it/api.{add}",
+                vec![Value::U32(40), Value::U32(2)]
+            )
+            .await,
+            Ok(Value::Tuple(vec![Value::U32(42)]))
+        );
+    }
+
+    #[tokio::test]
+    async fn async_invoke_local_function() {
+        let mut ctx = caller().await;
+        let rpc = ctx.new(calculator_uri()).await.unwrap();
+
+        let future = ctx
+            .async_invoke_and_await(
+                rpc,
+                "golem:it/api.{add}".to_string(),
+                vec![Value::U32(1).into(), Value::U32(1).into()],
+            )
+            .await
+            .unwrap();
+        ctx.table().get_mut(&future).unwrap().payload.ready().await;
+        let result = ctx
+            .get(Resource::new_borrow(future.rep()))
+            .await
+            .unwrap()
+            .map(|result| result.map(Value::from).map_err(|err| format!("{err:?}")));
+        assert_eq!(result, Some(Ok(Value::Tuple(vec![Value::U32(2)]))));
+    }
+
+    #[tokio::test]
+    async fn local_invocation_errors() {
+        let mut ctx = caller().await;
+        let rpc = ctx.new(calculator_uri()).await.unwrap();
+
+        assert!(matches!(
+            ctx.invoke_and_await(
+                Resource::new_borrow(rpc.rep()),
+                "golem:it/api.{sub}".to_string(),
+                vec![]
+            )
+            .await
+            .unwrap(),
+            Err(RpcError::NotFound(_))
+        ));
+        assert_eq!(
+            invoke_and_await(
+                &mut ctx,
+                &rpc,
+                "add",
+                vec![Value::U32(1), Value::String("2".to_string())]
+            )
+            .await,
+            Err("RpcError::ProtocolError(\"param 1: expected u32, got string\")".to_string())
+        );
+        assert!(matches!(
+            ctx.invoke_and_await(
+                Resource::new_borrow(rpc.rep()),
+                "golem:it/api.{fail}".to_string(),
+                vec![]
+            )
+            .await
+            .unwrap(),
+            Err(RpcError::RemoteInternalError(_))
+        ));
+
+        let unknown = ctx
+            .new(Uri {
+                value: "urn:worker:calculator/2".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            ctx.invoke_and_await(unknown, "add".to_string(), vec![])
+                .await
+                .unwrap(),
+            Err(RpcError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn re_entrant_calls_are_denied() {
+        let rpc = calculator_host().await;
+        let params = vec![Value::U32(1).into(), Value::U32(2).into()];
+
+        // as if the calculator was calling itself
+        let result = ACTIVE_WORKERS
+            .scope(
+                vec![calculator_uri().value],
+                rpc.invoke(&calculator_uri(), "add", params.clone()),
+            )
+            .await;
+        assert!(matches!(result, Err(RpcError::Denied(_))));
+
+        let result = rpc.invoke(&calculator_uri(), "add", params).await.unwrap();
+        assert_eq!(Value::from(result), Value::Tuple(vec![Value::U32(3)]));
+    }
+}
@@ -12,12 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::json::{json_schema, HANDLE_FORMAT};
 use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
-use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_ast::analysis::{
+    AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+    AnalysedType,
+};
 use poem_openapi::registry::{MetaSchema, MetaSchemaRef, Registry};
 use poem_openapi::types::{IsObjectType, ParseFromJSON, ParseResult, ToJSON, Type};
 use serde_json::Value;
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
 
 impl Type for TypeAnnotatedValue {
     const IS_REQUIRED: bool = true;
@@ -71,3 +77,497 @@ impl ParseFromJSON for TypeAnnotatedValue {
 }
 
 impl IsObjectType for TypeAnnotatedValue {}
+
+/// References to the registered OpenAPI schemas of a single exported function
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionSchemaRefs {
+    pub parameters: MetaSchemaRef,
+    pub result: MetaSchemaRef,
+}
+
+/// Registers a parameters and a result schema for every function exported by a component, keyed
+/// by the fully qualified function name.
+///
+/// The schema names are derived from `namespace`, identifying the component within the registry,
+/// and the function names, with the characters not allowed in OpenAPI component names escaped,
+/// and suffixed by `.params` and `.result`. For example the schemas of `golem:it/api.{add-item}`
+/// in the `shop` namespace are named `shop.golem_3a_it_2f_api._7b_add-item_7d_.params` and
+/// `shop.golem_3a_it_2f_api._7b_add-item_7d_.result`.
+///
+/// Fails if a schema of the same name was already registered with a different definition.
+pub fn register_export_schemas(
+    registry: &mut Registry,
+    namespace: &str,
+    exports: &[AnalysedExport],
+) -> Result<Vec<(String, FunctionSchemaRefs)>, String> {
+    let mut result = Vec::new();
+    for export in exports {
+        match export {
+            AnalysedExport::Function(function) => {
+                let refs =
+                    register_function_schemas(registry, namespace, &function.name, function)?;
+                result.push((function.name.clone(), refs));
+            }
+            AnalysedExport::Instance(instance) => {
+                for function in &instance.functions {
+                    let name = format!("{}.{{{}}}", instance.name, function.name);
+                    let refs = register_function_schemas(registry, namespace, &name, function)?;
+                    result.push((name, refs));
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Registers the parameters and result schemas of a single function under names derived from
+/// `namespace` and `name`, and returns references to them.
+///
+/// Fails if a schema of the same name was already registered with a different definition.
+pub fn register_function_schemas(
+    registry: &mut Registry,
+    namespace: &str,
+    name: &str,
+    function: &AnalysedFunction,
+) -> Result<FunctionSchemaRefs, String> {
+    let base_name = format!("{}.{}", schema_name(namespace), schema_name(name));
+    let parameters_name = format!("{base_name}.params");
+    let result_name = format!("{base_name}.result");
+
+    register_schema(
+        registry,
+        &parameters_name,
+        function_parameters_schema(&function.parameters),
+    )?;
+    register_schema(
+        registry,
+        &result_name,
+        function_result_schema(&function.results),
+    )?;
+
+    Ok(FunctionSchemaRefs {
+        parameters: MetaSchemaRef::Reference(parameters_name),
+        result: MetaSchemaRef::Reference(result_name),
+    })
+}
+
+/// Registers a schema unless the same one is already registered under its name, as
+/// `Registry::create_schema` keeps any existing schema
+fn register_schema(registry: &mut Registry, name: &str, schema: MetaSchema) -> Result<(), String> {
+    if let Some(existing) = registry.schemas.get(name) {
+        let existing = MetaSchema {
+            rust_typename: None,
+            ..existing.clone()
+        };
+        return if existing == schema {
+            Ok(())
+        } else {
+            Err(format!(
+                "The schema {name} is already registered with a different definition"
+            ))
+        };
+    }
+    registry.create_schema::<AnalysedFunction, _>(name.to_string(), |_| schema);
+    Ok(())
+}
+
+/// Describes the request body of a function invocation: an object with one property per
+/// parameter, keyed by the parameter's name.
+pub fn function_parameters_schema(parameters: &[AnalysedFunctionParameter]) -> MetaSchema {
+    object_schema(parameters.iter().map(|p| (p.name.as_str(), &p.typ)))
+}
+
+/// Describes the response body of a function invocation.
+///
+/// A single unnamed result is represented by its own value, named results by an object keyed by
+/// the result names, and functions without results by an empty object.
+pub fn function_result_schema(results: &[AnalysedFunctionResult]) -> MetaSchema {
+    match results {
+        [AnalysedFunctionResult { name: None, typ }] => analysed_type_schema(typ),
+        _ => object_schema(results.iter().enumerate().map(|(idx, r)| {
+            let name = match &r.name {
+                Some(name) => Cow::Borrowed(name.as_str()),
+                None => Cow::Owned(idx.to_string()),
+            };
+            (name, &r.typ)
+        })),
+    }
+}
+
+/// Converts an `AnalysedType` to an OpenAPI schema describing its untyped JSON representation.
+///
+/// The schema is derived from the JSON Schema generated by `json::json_schema`. OpenAPI 3.0 has
+/// no `null` type, so `null` is expressed with `nullable`, and no positional tuples, so tuple items
+/// are described by the union of the item types.
+pub fn analysed_type_schema(typ: &AnalysedType) -> MetaSchema {
+    json_schema_to_meta(&json_schema(typ))
+}
+
+fn json_schema_to_meta(schema: &Value) -> MetaSchema {
+    let any_of = schema.get("anyOf").and_then(Value::as_array);
+    let one_of = schema.get("oneOf").and_then(Value::as_array);
+    if let Some(members) = any_of.or(one_of) {
+        let (nulls, members): (Vec<_>, Vec<_>) = members.iter().partition(|m| is_null_schema(m));
+        let nullable = !nulls.is_empty();
+        return match members.as_slice() {
+            [] => null_schema(),
+            [member] => MetaSchema {
+                nullable,
+                ..json_schema_to_meta(member)
+            },
+            _ => {
+                let members = members
+                    .iter()
+                    .map(|member| inline(json_schema_to_meta(member)))
+                    .collect();
+                if any_of.is_some() {
+                    MetaSchema {
+                        any_of: members,
+                        nullable,
+                        ..MetaSchema::ANY
+                    }
+                } else {
+                    MetaSchema {
+                        one_of: members,
+                        nullable,
+                        ..MetaSchema::ANY
+                    }
+                }
+            }
+        };
+    }
+    if is_null_schema(schema) {
+        return null_schema();
+    }
+
+    let ty = match schema.get("type").and_then(Value::as_str) {
+        Some("boolean") => "boolean",
+        Some("integer") => "integer",
+        Some("number") => "number",
+        Some("string") => "string",
+        Some("array") => "array",
+        Some("object") => "object",
+        _ => MetaSchema::ANY.ty,
+    };
+    let mut meta = MetaSchema::new(ty);
+    // the handle format is the only one used by `json_schema`
+    meta.format = schema
+        .get("format")
+        .and_then(Value::as_str)
+        .map(|_| HANDLE_FORMAT);
+    meta.pattern = schema
+        .get("pattern")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    meta.minimum = schema.get("minimum").and_then(Value::as_f64);
+    meta.maximum = schema.get("maximum").and_then(Value::as_f64);
+    meta.enum_items = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    meta.min_items = schema
+        .get("minItems")
+        .and_then(Value::as_u64)
+        .map(|n| n as usize);
+    meta.max_items = schema
+        .get("maxItems")
+        .and_then(Value::as_u64)
+        .map(|n| n as usize);
+    meta.items = match (schema.get("prefixItems"), schema.get("items")) {
+        (Some(Value::Array(items)), _) => Some(Box::new(inline(MetaSchema {
+            any_of: items
+                .iter()
+                .map(|item| inline(json_schema_to_meta(item)))
+                .collect(),
+            ..MetaSchema::ANY
+        }))),
+        (_, Some(items)) if items.is_object() => Some(Box::new(inline(json_schema_to_meta(items)))),
+        _ => None,
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (name, property) in properties {
+            add_property(
+                &mut meta,
+                name,
+                json_schema_to_meta(property),
+                required.contains(&name.as_str()),
+            );
+        }
+    }
+    meta
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+fn null_schema() -> MetaSchema {
+    MetaSchema {
+        nullable: true,
+        enum_items: vec![Value::Null],
+        ..MetaSchema::ANY
+    }
+}
+
+fn object_schema<'a, N: AsRef<str>>(
+    fields: impl IntoIterator<Item = (N, &'a AnalysedType)>,
+) -> MetaSchema {
+    let mut schema = MetaSchema::new("object");
+    for (name, typ) in fields {
+        // missing option fields are parsed as `none`
+        let required = !matches!(typ, AnalysedType::Option(_));
+        add_property(
+            &mut schema,
+            name.as_ref(),
+            analysed_type_schema(typ),
+            required,
+        );
+    }
+    schema
+}
+
+/// Adds a property to an object schema, or allows any additional property if its name can no
+/// longer be interned
+fn add_property(schema: &mut MetaSchema, name: &str, property: MetaSchema, required: bool) {
+    let interned = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .intern(name);
+    match interned {
+        Some(name) => {
+            if required {
+                schema.required.push(name);
+            }
+            schema.properties.push((name, inline(property)));
+        }
+        None => {
+            let any = schema
+                .additional_properties
+                .get_or_insert_with(|| Box::new(inline(MetaSchema::ANY)));
+            if let MetaSchemaRef::Inline(any) = any.as_mut() {
+                any.any_of.push(inline(property));
+            }
+        }
+    }
+}
+
+fn inline(schema: MetaSchema) -> MetaSchemaRef {
+    MetaSchemaRef::Inline(Box::new(schema))
+}
+
+/// The property names of all registered schemas
+static NAMES: OnceLock<Mutex<Names>> = OnceLock::new();
+
+/// At most this many bytes of property names are leaked by the process
+const MAX_NAME_BYTES: usize = 1024 * 1024;
+
+/// Names have to be `'static` in `MetaSchema`, so they are leaked, once per distinct name and up
+/// to a limit, as components may be registered for as long as the process runs
+struct Names {
+    names: HashSet<&'static str>,
+    bytes: usize,
+    max_bytes: usize,
+}
+
+impl Default for Names {
+    fn default() -> Self {
+        Self {
+            names: HashSet::new(),
+            bytes: 0,
+            max_bytes: MAX_NAME_BYTES,
+        }
+    }
+}
+
+impl Names {
+    /// Returns the leaked copy of `name`, or `None` if leaking it would exceed the limit
+    fn intern(&mut self, name: &str) -> Option<&'static str> {
+        if let Some(name) = self.names.get(name) {
+            return Some(name);
+        }
+        if self.bytes + name.len() > self.max_bytes {
+            return None;
+        }
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        self.names.insert(name);
+        self.bytes += name.len();
+        Some(name)
+    }
+}
+
+/// Escapes a function name to the characters allowed in OpenAPI component names, without
+/// mapping two function names to the same schema name: `_` is escaped as `__` and other
+/// characters as `_<hex code point>_`.
+fn schema_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+            result.push(c);
+        } else if c == '_' {
+            result.push_str("__");
+        } else {
+            result.push_str(&format!("_{:x}_", c as u32));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedInstance, AnalysedType, TypeF64, TypeOption, TypeResult, TypeStr, TypeU32,
+    };
+    use poem_openapi::registry::{MetaSchemaRef, Registry};
+    use serde_json::Value;
+
+    use crate::poem::{
+        analysed_type_schema, function_parameters_schema, function_result_schema,
+        register_export_schemas, schema_name, Names,
+    };
+
+    fn add_item() -> AnalysedFunction {
+        AnalysedFunction {
+            name: "add-item".to_string(),
+            parameters: vec![
+                AnalysedFunctionParameter {
+                    name: "product-id".to_string(),
+                    typ: AnalysedType::Str(TypeStr),
+                },
+                AnalysedFunctionParameter {
+                    name: "quantity".to_string(),
+                    typ: AnalysedType::Option(TypeOption {
+                        inner: Box::new(AnalysedType::U32(TypeU32)),
+                    }),
+                },
+            ],
+            results: vec![AnalysedFunctionResult {
+                name: None,
+                typ: AnalysedType::U32(TypeU32),
+            }],
+        }
+    }
+
+    #[test]
+    fn parameters_are_described_by_an_object() {
+        let schema = function_parameters_schema(&add_item().parameters);
+        assert_eq!(schema.ty, "object");
+        assert_eq!(schema.required, vec!["product-id"]);
+        assert_eq!(
+            schema
+                .properties
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>(),
+            vec!["product-id", "quantity"]
+        );
+    }
+
+    #[test]
+    fn single_unnamed_result_is_described_by_its_type() {
+        let schema = function_result_schema(&add_item().results);
+        assert_eq!(schema.ty, "integer");
+        assert_eq!(schema.maximum, Some(u32::MAX as f64));
+    }
+
+    #[test]
+    fn exported_functions_are_registered() {
+        let mut registry = Registry::new();
+        let refs = register_export_schemas(
+            &mut registry,
+            "shop",
+            &[AnalysedExport::Instance(AnalysedInstance {
+                name: "golem:it/api".to_string(),
+                functions: vec![add_item()],
+            })],
+        )
+        .unwrap();
+
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].0, "golem:it/api.{add-item}");
+        assert_eq!(
+            refs[0].1.parameters,
+            MetaSchemaRef::Reference("shop.golem_3a_it_2f_api._7b_add-item_7d_.params".to_string())
+        );
+        assert!(registry
+            .schemas
+            .contains_key("shop.golem_3a_it_2f_api._7b_add-item_7d_.result"));
+    }
+
+    #[test]
+    fn differing_schemas_of_the_same_name_are_rejected() {
+        let mut registry = Registry::new();
+        let exports = |function: AnalysedFunction| {
+            vec![AnalysedExport::Instance(AnalysedInstance {
+                name: "golem:it/api".to_string(),
+                functions: vec![function],
+            })]
+        };
+        let other_add_item = AnalysedFunction {
+            parameters: vec![],
+            ..add_item()
+        };
+
+        assert!(register_export_schemas(&mut registry, "shop", &exports(add_item())).is_ok());
+        assert!(register_export_schemas(&mut registry, "shop", &exports(add_item())).is_ok());
+        assert!(
+            register_export_schemas(&mut registry, "other", &exports(other_add_item.clone()))
+                .is_ok()
+        );
+        assert!(register_export_schemas(&mut registry, "shop", &exports(other_add_item)).is_err());
+    }
+
+    #[test]
+    fn schemas_follow_the_json_schema() {
+        let option = analysed_type_schema(&AnalysedType::Option(TypeOption {
+            inner: Box::new(AnalysedType::U32(TypeU32)),
+        }));
+        assert_eq!(option.ty, "integer");
+        assert!(option.nullable);
+
+        let float = analysed_type_schema(&AnalysedType::F64(TypeF64));
+        assert_eq!(float.any_of.len(), 2);
+
+        let result = analysed_type_schema(&AnalysedType::Result(TypeResult {
+            ok: Some(Box::new(AnalysedType::Str(TypeStr))),
+            err: None,
+        }));
+        let MetaSchemaRef::Inline(err) = &result.any_of[1] else {
+            panic!("expected an inline schema")
+        };
+        let MetaSchemaRef::Inline(err_value) = &err.properties[0].1 else {
+            panic!("expected an inline schema")
+        };
+        assert!(err_value.nullable);
+        assert_eq!(err_value.enum_items, vec![Value::Null]);
+    }
+
+    #[test]
+    fn names_are_leaked_up_to_a_limit() {
+        let mut names = Names {
+            max_bytes: 8,
+            ..Names::default()
+        };
+        let first = names.intern("name").unwrap();
+        assert!(std::ptr::eq(first, names.intern("name").unwrap()));
+        assert!(names.intern("other").is_none());
+        assert!(names.intern("last").is_some());
+        assert!(names.intern("x").is_none());
+    }
+
+    #[test]
+    fn schema_names_are_distinct() {
+        assert_eq!(schema_name("a:b"), "a_3a_b");
+        assert_eq!(schema_name("a_b"), "a__b");
+        assert_eq!(schema_name("a-b.c"), "a-b.c");
+    }
+}
@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use crate::protobuf::typed_result::ResultValue;
-use crate::{Uri, Value};
-use golem_wasm_ast::analysis::{AnalysedFunctionParameter, AnalysedType};
+use crate::Value;
+use golem_wasm_ast::analysis::{self, AnalysedFunctionParameter, AnalysedType};
 
 include!(concat!(env!("OUT_DIR"), "/wasm.rpc.rs"));
 
@@ -480,7 +480,7 @@ impl TryFrom<type_annotated_value::TypeAnnotatedValue> for Value {
                 }
             }
             type_annotated_value::TypeAnnotatedValue::Handle(handle) => Ok(Value::Handle {
-                uri: Uri { value: handle.uri },
+                uri: super::Uri { value: handle.uri },
                 resource_id: handle.resource_id,
             }),
             type_annotated_value::TypeAnnotatedValue::Variant(variant) => {
@@ -707,7 +707,9 @@ impl TryFrom<Val> for Value {
             Some(val::Val::Flags(ValFlags { count, value })) => {
                 let mut flags = vec![false; count as usize];
                 for i in value {
-                    flags[i as usize] = true;
+                    *flags
+                        .get_mut(i as usize)
+                        .ok_or("Protobuf ValFlags has invalid flag index".to_string())? = true;
                 }
                 Ok(Value::Flags(flags))
             }
@@ -745,28 +747,632 @@ impl TryFrom<Val> for Value {
     }
 }
 
+impl From<super::Uri> for Uri {
+    fn from(value: super::Uri) -> Self {
+        Uri { value: value.value }
+    }
+}
+
+impl From<Uri> for super::Uri {
+    fn from(value: Uri) -> Self {
+        super::Uri { value: value.value }
+    }
+}
+
+impl From<super::RpcError> for RpcError {
+    fn from(value: super::RpcError) -> Self {
+        let error = match value {
+            super::RpcError::ProtocolError(details) => rpc_error::Error::ProtocolError(details),
+            super::RpcError::Denied(details) => rpc_error::Error::Denied(details),
+            super::RpcError::NotFound(details) => rpc_error::Error::NotFound(details),
+            super::RpcError::RemoteInternalError(details) => {
+                rpc_error::Error::RemoteInternalError(details)
+            }
+            super::RpcError::Timeout(details) => rpc_error::Error::Timeout(details),
+        };
+        RpcError { error: Some(error) }
+    }
+}
+
+impl TryFrom<RpcError> for super::RpcError {
+    type Error = String;
+
+    fn try_from(value: RpcError) -> Result<Self, Self::Error> {
+        match value.error {
+            None => Err("Protobuf RpcError has no value".to_string()),
+            Some(rpc_error::Error::ProtocolError(details)) => {
+                Ok(super::RpcError::ProtocolError(details))
+            }
+            Some(rpc_error::Error::Denied(details)) => Ok(super::RpcError::Denied(details)),
+            Some(rpc_error::Error::NotFound(details)) => Ok(super::RpcError::NotFound(details)),
+            Some(rpc_error::Error::RemoteInternalError(details)) => {
+                Ok(super::RpcError::RemoteInternalError(details))
+            }
+            Some(rpc_error::Error::Timeout(details)) => Ok(super::RpcError::Timeout(details)),
+        }
+    }
+}
+
+impl From<super::InvocationRequest> for InvocationRequest {
+    fn from(value: super::InvocationRequest) -> Self {
+        InvocationRequest {
+            target: Some(value.target.into()),
+            function_name: value.function_name,
+            params: value.params.into_iter().map(|param| param.into()).collect(),
+            idempotency_key: value.idempotency_key,
+        }
+    }
+}
+
+impl TryFrom<InvocationRequest> for super::InvocationRequest {
+    type Error = String;
+
+    fn try_from(value: InvocationRequest) -> Result<Self, Self::Error> {
+        Ok(super::InvocationRequest {
+            target: value
+                .target
+                .ok_or("Protobuf InvocationRequest has no target".to_string())?
+                .into(),
+            function_name: value.function_name,
+            params: value
+                .params
+                .into_iter()
+                .map(|param| param.try_into())
+                .collect::<Result<_, _>>()?,
+            idempotency_key: value.idempotency_key,
+        })
+    }
+}
+
+impl From<Result<super::WitValue, super::RpcError>> for InvocationResponse {
+    fn from(value: Result<super::WitValue, super::RpcError>) -> Self {
+        let result = match value {
+            Ok(value) => invocation_response::Result::Value(value.into()),
+            Err(error) => invocation_response::Result::Error(error.into()),
+        };
+        InvocationResponse {
+            result: Some(result),
+        }
+    }
+}
+
+impl TryFrom<InvocationResponse> for Result<super::WitValue, super::RpcError> {
+    type Error = String;
+
+    fn try_from(value: InvocationResponse) -> Result<Self, Self::Error> {
+        match value.result {
+            None => Err("Protobuf InvocationResponse has no result".to_string()),
+            Some(invocation_response::Result::Value(value)) => Ok(Ok(value.try_into()?)),
+            Some(invocation_response::Result::Error(error)) => Ok(Err(error.try_into()?)),
+        }
+    }
+}
+
+impl From<super::BatchInvocationRequest> for BatchInvocationRequest {
+    fn from(value: super::BatchInvocationRequest) -> Self {
+        BatchInvocationRequest {
+            target: Some(value.target.into()),
+            invocations: value
+                .invocations
+                .into_iter()
+                .map(|invocation| BatchInvocation {
+                    function_name: invocation.function_name,
+                    params: invocation
+                        .function_params
+                        .into_iter()
+                        .map(|param| param.into())
+                        .collect(),
+                })
+                .collect(),
+            parallel: value.options.parallel,
+            stop_on_first_error: value.options.stop_on_first_error,
+            idempotency_key: value.idempotency_key,
+        }
+    }
+}
+
+impl TryFrom<BatchInvocationRequest> for super::BatchInvocationRequest {
+    type Error = String;
+
+    fn try_from(value: BatchInvocationRequest) -> Result<Self, Self::Error> {
+        Ok(super::BatchInvocationRequest {
+            target: value
+                .target
+                .ok_or("Protobuf BatchInvocationRequest has no target".to_string())?
+                .into(),
+            invocations: value
+                .invocations
+                .into_iter()
+                .map(|invocation| {
+                    Ok(super::BatchInvocation {
+                        function_name: invocation.function_name,
+                        function_params: invocation
+                            .params
+                            .into_iter()
+                            .map(|param| param.try_into())
+                            .collect::<Result<_, String>>()?,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            options: super::BatchOptions {
+                parallel: value.parallel,
+                stop_on_first_error: value.stop_on_first_error,
+            },
+            idempotency_key: value.idempotency_key,
+        })
+    }
+}
+
+impl From<Vec<Result<super::WitValue, super::RpcError>>> for BatchInvocationResponse {
+    fn from(value: Vec<Result<super::WitValue, super::RpcError>>) -> Self {
+        BatchInvocationResponse {
+            results: value.into_iter().map(|result| result.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<BatchInvocationResponse> for Vec<Result<super::WitValue, super::RpcError>> {
+    type Error = String;
+
+    fn try_from(value: BatchInvocationResponse) -> Result<Self, Self::Error> {
+        value
+            .results
+            .into_iter()
+            .map(|result| result.try_into())
+            .collect()
+    }
+}
+
+impl From<Value> for ValV2 {
+    fn from(value: Value) -> Self {
+        let val = match value {
+            Value::Bool(value) => val_v2::Val::Bool(value),
+            Value::U8(value) => val_v2::Val::U8(value as u32),
+            Value::U16(value) => val_v2::Val::U16(value as u32),
+            Value::U32(value) => val_v2::Val::U32(value),
+            Value::U64(value) => val_v2::Val::U64(value),
+            Value::S8(value) => val_v2::Val::S8(value as i32),
+            Value::S16(value) => val_v2::Val::S16(value as i32),
+            Value::S32(value) => val_v2::Val::S32(value),
+            Value::S64(value) => val_v2::Val::S64(value),
+            Value::F32(value) => val_v2::Val::F32(value),
+            Value::F64(value) => val_v2::Val::F64(value),
+            Value::Char(value) => val_v2::Val::Char(value as u32),
+            Value::String(value) => val_v2::Val::String(value),
+            Value::List(items)
+                if !items.is_empty() && items.iter().all(|item| matches!(item, Value::U8(_))) =>
+            {
+                val_v2::Val::Bytes(
+                    items
+                        .into_iter()
+                        .map(|item| match item {
+                            Value::U8(value) => value,
+                            _ => unreachable!(),
+                        })
+                        .collect(),
+                )
+            }
+            Value::List(items) => val_v2::Val::List(ValV2List {
+                values: items.into_iter().map(|item| item.into()).collect(),
+            }),
+            Value::Tuple(items) => val_v2::Val::Tuple(ValV2List {
+                values: items.into_iter().map(|item| item.into()).collect(),
+            }),
+            Value::Record(fields) => val_v2::Val::Record(ValV2List {
+                values: fields.into_iter().map(|field| field.into()).collect(),
+            }),
+            Value::Variant {
+                case_idx,
+                case_value,
+            } => val_v2::Val::Variant(Box::new(ValV2Variant {
+                case_idx,
+                value: case_value.map(|case_value| Box::new((*case_value).into())),
+            })),
+            Value::Enum(value) => val_v2::Val::Enum(value),
+            Value::Flags(values) => {
+                let mut bits = vec![0u8; values.len().div_ceil(8)];
+                for (i, value) in values.iter().enumerate() {
+                    if *value {
+                        bits[i / 8] |= 1 << (i % 8);
+                    }
+                }
+                val_v2::Val::Flags(ValV2Flags {
+                    count: values.len() as u32,
+                    bits,
+                })
+            }
+            Value::Option(value) => val_v2::Val::Option(Box::new(ValV2Option {
+                value: value.map(|value| Box::new((*value).into())),
+            })),
+            Value::Result(Ok(value)) => val_v2::Val::Result(Box::new(ValV2Result {
+                is_err: false,
+                value: value.map(|value| Box::new((*value).into())),
+            })),
+            Value::Result(Err(value)) => val_v2::Val::Result(Box::new(ValV2Result {
+                is_err: true,
+                value: value.map(|value| Box::new((*value).into())),
+            })),
+            Value::Handle { uri, resource_id } => val_v2::Val::Handle(ValHandle {
+                uri: uri.value,
+                value: resource_id,
+            }),
+        };
+        ValV2 { val: Some(val) }
+    }
+}
+
+impl TryFrom<ValV2> for Value {
+    type Error = String;
+
+    fn try_from(value: ValV2) -> Result<Self, Self::Error> {
+        fn try_into_values(values: Vec<ValV2>) -> Result<Vec<Value>, String> {
+            values.into_iter().map(|value| value.try_into()).collect()
+        }
+
+        fn try_into_boxed(value: Option<Box<ValV2>>) -> Result<Option<Box<Value>>, String> {
+            value
+                .map(|value| (*value).try_into().map(Box::new))
+                .transpose()
+        }
+
+        fn narrow<T: TryFrom<i64>>(value: i64, name: &str) -> Result<T, String> {
+            T::try_from(value)
+                .map_err(|_| format!("Protobuf ValV2 has invalid {name} value {value}"))
+        }
+
+        match value.val {
+            None => Err("Protobuf ValV2 has no value".to_string()),
+            Some(val_v2::Val::Bool(value)) => Ok(Value::Bool(value)),
+            Some(val_v2::Val::U8(value)) => Ok(Value::U8(narrow(value as i64, "u8")?)),
+            Some(val_v2::Val::U16(value)) => Ok(Value::U16(narrow(value as i64, "u16")?)),
+            Some(val_v2::Val::U32(value)) => Ok(Value::U32(value)),
+            Some(val_v2::Val::U64(value)) => Ok(Value::U64(value)),
+            Some(val_v2::Val::S8(value)) => Ok(Value::S8(narrow(value as i64, "s8")?)),
+            Some(val_v2::Val::S16(value)) => Ok(Value::S16(narrow(value as i64, "s16")?)),
+            Some(val_v2::Val::S32(value)) => Ok(Value::S32(value)),
+            Some(val_v2::Val::S64(value)) => Ok(Value::S64(value)),
+            Some(val_v2::Val::F32(value)) => Ok(Value::F32(value)),
+            Some(val_v2::Val::F64(value)) => Ok(Value::F64(value)),
+            Some(val_v2::Val::Char(value)) => Ok(Value::Char(
+                char::from_u32(value)
+                    .ok_or(format!("Protobuf ValV2 has invalid char value {value}"))?,
+            )),
+            Some(val_v2::Val::String(value)) => Ok(Value::String(value)),
+            Some(val_v2::Val::Bytes(bytes)) => {
+                Ok(Value::List(bytes.into_iter().map(Value::U8).collect()))
+            }
+            Some(val_v2::Val::List(ValV2List { values })) => {
+                Ok(Value::List(try_into_values(values)?))
+            }
+            Some(val_v2::Val::Tuple(ValV2List { values })) => {
+                Ok(Value::Tuple(try_into_values(values)?))
+            }
+            Some(val_v2::Val::Record(ValV2List { values })) => {
+                Ok(Value::Record(try_into_values(values)?))
+            }
+            Some(val_v2::Val::Variant(variant)) => Ok(Value::Variant {
+                case_idx: variant.case_idx,
+                case_value: try_into_boxed(variant.value)?,
+            }),
+            Some(val_v2::Val::Enum(value)) => Ok(Value::Enum(value)),
+            Some(val_v2::Val::Flags(ValV2Flags { count, bits })) => {
+                if bits.len() != (count as usize).div_ceil(8) {
+                    return Err(format!(
+                        "Protobuf ValV2Flags has {} bytes for {count} flags",
+                        bits.len()
+                    ));
+                }
+                Ok(Value::Flags(
+                    (0..count as usize)
+                        .map(|i| bits[i / 8] & (1 << (i % 8)) != 0)
+                        .collect(),
+                ))
+            }
+            Some(val_v2::Val::Option(option)) => Ok(Value::Option(try_into_boxed(option.value)?)),
+            Some(val_v2::Val::Result(result)) => {
+                let value = try_into_boxed(result.value)?;
+                if result.is_err {
+                    Ok(Value::Result(Err(value)))
+                } else {
+                    Ok(Value::Result(Ok(value)))
+                }
+            }
+            Some(val_v2::Val::Handle(ValHandle { uri, value })) => Ok(Value::Handle {
+                uri: super::Uri { value: uri },
+                resource_id: value,
+            }),
+        }
+    }
+}
+
+impl From<super::WitValue> for ValV2 {
+    fn from(value: super::WitValue) -> Self {
+        let value: Value = value.into();
+        value.into()
+    }
+}
+
+impl TryFrom<ValV2> for super::WitValue {
+    type Error = String;
+
+    fn try_from(value: ValV2) -> Result<Self, Self::Error> {
+        let value: Value = value.try_into()?;
+        Ok(value.into())
+    }
+}
+
+/// Migrates a value from the old `Val` encoding to `ValV2`
+impl TryFrom<Val> for ValV2 {
+    type Error = String;
+
+    fn try_from(value: Val) -> Result<Self, Self::Error> {
+        let value: Value = value.try_into()?;
+        Ok(value.into())
+    }
+}
+
+impl TryFrom<type_annotated_value::TypeAnnotatedValue> for Val {
+    type Error = String;
+
+    fn try_from(value: type_annotated_value::TypeAnnotatedValue) -> Result<Self, Self::Error> {
+        let value: Value = value.try_into()?;
+        Ok(value.into())
+    }
+}
+
+/// Reconstructs a typed value from its positional protobuf representation and its type.
+///
+/// Unlike going through `Value`, the `Val` is validated against the type: primitive kinds and
+/// ranges, variant, option and result discriminants, enum ranges and flag counts must all match.
+#[cfg(feature = "typeinfo")]
+pub fn type_annotated_value_from_val(
+    val: &Val,
+    typ: &AnalysedType,
+) -> Result<type_annotated_value::TypeAnnotatedValue, String> {
+    use crate::TypeAnnotatedValueConstructors;
+
+    let value = typed_val_to_value(val, typ)?;
+    type_annotated_value::TypeAnnotatedValue::create(&value, typ)
+        .map_err(|errors| errors.join(", "))
+}
+
+#[cfg(feature = "typeinfo")]
+fn typed_val_to_value(val: &Val, typ: &AnalysedType) -> Result<Value, String> {
+    fn in_range<T: TryFrom<i64>>(value: i64, name: &str) -> Result<T, String> {
+        T::try_from(value).map_err(|_| format!("Value {value} is out of range for {name}"))
+    }
+
+    fn typed_items(values: &[Val], types: &[&AnalysedType]) -> Result<Vec<Value>, String> {
+        values
+            .iter()
+            .zip(types)
+            .map(|(val, typ)| typed_val_to_value(val, typ))
+            .collect()
+    }
+
+    fn typed_case(
+        value: &Option<Box<Val>>,
+        typ: &Option<Box<AnalysedType>>,
+        name: &str,
+    ) -> Result<Option<Box<Value>>, String> {
+        match (value, typ) {
+            (Some(value), Some(typ)) => Ok(Some(Box::new(typed_val_to_value(value, typ)?))),
+            (None, None) => Ok(None),
+            (Some(_), None) => Err(format!("Unit {name} has a value")),
+            (None, Some(_)) => Err(format!("Non-unit {name} has no value")),
+        }
+    }
+
+    let inner = val
+        .val
+        .as_ref()
+        .ok_or("Protobuf Val has no value".to_string())?;
+    match (inner, typ) {
+        (val::Val::Bool(value), AnalysedType::Bool(_)) => Ok(Value::Bool(*value)),
+        (val::Val::S8(value), AnalysedType::S8(_)) => Ok(Value::S8(in_range(*value as i64, "s8")?)),
+        (val::Val::U8(value), AnalysedType::U8(_)) => Ok(Value::U8(in_range(*value as i64, "u8")?)),
+        (val::Val::S16(value), AnalysedType::S16(_)) => {
+            Ok(Value::S16(in_range(*value as i64, "s16")?))
+        }
+        (val::Val::U16(value), AnalysedType::U16(_)) => {
+            Ok(Value::U16(in_range(*value as i64, "u16")?))
+        }
+        (val::Val::S32(value), AnalysedType::S32(_)) => Ok(Value::S32(*value)),
+        (val::Val::U32(value), AnalysedType::U32(_)) => Ok(Value::U32(in_range(*value, "u32")?)),
+        (val::Val::S64(value), AnalysedType::S64(_)) => Ok(Value::S64(*value)),
+        // u64 values are stored bit-for-bit in the signed field
+        (val::Val::U64(value), AnalysedType::U64(_)) => Ok(Value::U64(*value as u64)),
+        (val::Val::F32(value), AnalysedType::F32(_)) => Ok(Value::F32(*value)),
+        (val::Val::F64(value), AnalysedType::F64(_)) => Ok(Value::F64(*value)),
+        (val::Val::Char(value), AnalysedType::Chr(_)) => Ok(Value::Char(
+            char::from_u32(*value as u32).ok_or(format!("Value {value} is not a valid char"))?,
+        )),
+        (val::Val::String(value), AnalysedType::Str(_)) => Ok(Value::String(value.clone())),
+        (val::Val::List(ValList { values }), AnalysedType::List(analysis::TypeList { inner })) => {
+            Ok(Value::List(
+                values
+                    .iter()
+                    .map(|value| typed_val_to_value(value, inner))
+                    .collect::<Result<_, _>>()?,
+            ))
+        }
+        (
+            val::Val::Tuple(ValTuple { values }),
+            AnalysedType::Tuple(analysis::TypeTuple { items }),
+        ) => {
+            if values.len() != items.len() {
+                return Err(format!(
+                    "Tuple has unexpected number of elements: {} vs {}",
+                    values.len(),
+                    items.len()
+                ));
+            }
+            Ok(Value::Tuple(typed_items(
+                values,
+                &items.iter().collect::<Vec<_>>(),
+            )?))
+        }
+        (
+            val::Val::Record(ValRecord { values }),
+            AnalysedType::Record(analysis::TypeRecord { fields }),
+        ) => {
+            if values.len() != fields.len() {
+                return Err(format!(
+                    "Record has unexpected number of fields: {} vs {}",
+                    values.len(),
+                    fields.len()
+                ));
+            }
+            Ok(Value::Record(typed_items(
+                values,
+                &fields.iter().map(|field| &field.typ).collect::<Vec<_>>(),
+            )?))
+        }
+        (val::Val::Variant(variant), AnalysedType::Variant(analysis::TypeVariant { cases })) => {
+            let case = usize::try_from(variant.discriminant)
+                .ok()
+                .and_then(|idx| cases.get(idx))
+                .ok_or(format!(
+                    "Variant discriminant {} is out of range for {} cases",
+                    variant.discriminant,
+                    cases.len()
+                ))?;
+            let case_type = case.typ.clone().map(Box::new);
+            Ok(Value::Variant {
+                case_idx: variant.discriminant as u32,
+                case_value: typed_case(
+                    &variant.value,
+                    &case_type,
+                    &format!("variant case {}", case.name),
+                )?,
+            })
+        }
+        (
+            val::Val::Enum(ValEnum { discriminant }),
+            AnalysedType::Enum(analysis::TypeEnum { cases }),
+        ) => {
+            if usize::try_from(*discriminant).is_ok_and(|idx| idx < cases.len()) {
+                Ok(Value::Enum(*discriminant as u32))
+            } else {
+                Err(format!(
+                    "Enum discriminant {discriminant} is out of range for {} cases",
+                    cases.len()
+                ))
+            }
+        }
+        (
+            val::Val::Flags(ValFlags { count, value }),
+            AnalysedType::Flags(analysis::TypeFlags { names }),
+        ) => {
+            if *count as usize != names.len() {
+                return Err(format!(
+                    "Unexpected number of flags: {count} vs {}",
+                    names.len()
+                ));
+            }
+            let mut flags = vec![false; names.len()];
+            for idx in value {
+                match usize::try_from(*idx)
+                    .ok()
+                    .and_then(|idx| flags.get_mut(idx))
+                {
+                    Some(flag) => *flag = true,
+                    None => {
+                        return Err(format!(
+                            "Flag index {idx} is out of range for {count} flags"
+                        ))
+                    }
+                }
+            }
+            Ok(Value::Flags(flags))
+        }
+        (val::Val::Option(option), AnalysedType::Option(analysis::TypeOption { inner })) => {
+            match (option.discriminant, &option.value) {
+                (0, None) => Ok(Value::Option(None)),
+                (1, Some(value)) => Ok(Value::Option(Some(Box::new(typed_val_to_value(
+                    value, inner,
+                )?)))),
+                _ => Err("Protobuf ValOption has invalid discriminant or value".to_string()),
+            }
+        }
+        (val::Val::Result(result), AnalysedType::Result(analysis::TypeResult { ok, err })) => {
+            match result.discriminant {
+                0 => Ok(Value::Result(Ok(typed_case(
+                    &result.value,
+                    ok,
+                    "ok result",
+                )?))),
+                1 => Ok(Value::Result(Err(typed_case(
+                    &result.value,
+                    err,
+                    "error result",
+                )?))),
+                discriminant => Err(format!("Result discriminant {discriminant} is invalid")),
+            }
+        }
+        (val::Val::Handle(ValHandle { uri, value }), AnalysedType::Handle(_)) => {
+            Ok(Value::Handle {
+                uri: super::Uri { value: uri.clone() },
+                resource_id: *value,
+            })
+        }
+        (_, typ) => Err(format!("Unexpected value for type {typ:?}")),
+    }
+}
+
+/// Checks that the given parameter values match the expected function parameters, reporting
+/// every mismatching parameter by name.
 #[cfg(feature = "typeinfo")]
 pub fn function_parameters(
     parameters: &[Val],
     expected_parameters: Vec<AnalysedFunctionParameter>,
 ) -> Result<(), Vec<String>> {
-    if parameters.len() == expected_parameters.len() {
-        Ok(())
-    } else {
-        Err(vec![format!(
+    use crate::TypeAnnotatedValueConstructors;
+
+    if parameters.len() != expected_parameters.len() {
+        return Err(vec![format!(
             "Unexpected number of parameters (got {}, expected: {})",
             parameters.len(),
             expected_parameters.len()
-        )])
+        )]);
+    }
+
+    let mut errors = vec![];
+    for (val, fp) in parameters.iter().zip(expected_parameters.iter()) {
+        let result = Value::try_from(val.clone())
+            .map_err(|err| vec![err])
+            .and_then(|value| type_annotated_value::TypeAnnotatedValue::create(&value, &fp.typ));
+        if let Err(err) = result {
+            errors.extend(
+                err.into_iter()
+                    .map(|err| format!("Invalid value for parameter {}: {}", fp.name, err)),
+            );
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Val, WitValue};
-    use crate::Value;
+    use super::{
+        val, val_v2, BatchInvocationRequest, BatchInvocationResponse, InvocationRequest,
+        InvocationResponse, Val, ValEnum, ValFlags, ValV2, ValV2Flags, ValVariant, WitValue,
+    };
+    use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
+    use crate::protobuf::type_annotated_value_from_val;
+    use crate::{TypeAnnotatedValueConstructors, Value};
+    use golem_wasm_ast::analysis::{
+        AnalysedType, NameOptionTypePair, NameTypePair, TypeEnum, TypeFlags, TypeList, TypeOption,
+        TypeRecord, TypeStr, TypeU32, TypeU8, TypeVariant,
+    };
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb_sized;
+    use prost::Message;
 
     const CASES: u32 = 10000;
     const SIZE: usize = 4096;
@@ -785,6 +1391,24 @@ mod tests {
             prop_assert_eq!(value, round_trip_value);
         }
 
+        #[test]
+        fn round_trip_val_v2(value in arb_sized::<Value>(SIZE).prop_filter("Value must be equal to itself", |v| v.eq(v))) {
+            let wit_value: crate::WitValue = value.clone().into();
+
+            let protobuf_val: ValV2 = wit_value.into();
+            let round_trip_wit_value: crate::WitValue = protobuf_val.try_into().unwrap();
+            let round_trip_value: Value = round_trip_wit_value.into();
+            prop_assert_eq!(value, round_trip_value);
+        }
+
+        #[test]
+        fn migrate_val_to_v2(value in arb_sized::<Value>(SIZE).prop_filter("Value must be equal to itself", |v| v.eq(v))) {
+            let protobuf_val: Val = value.clone().into();
+            let migrated: ValV2 = protobuf_val.try_into().unwrap();
+            let round_trip_value: Value = migrated.try_into().unwrap();
+            prop_assert_eq!(value, round_trip_value);
+        }
+
         #[test]
         fn round_trip_val(value in arb_sized::<Value>(SIZE).prop_filter("Value must be equal to itself", |v| v.eq(v))) {
             let wit_value: crate::WitValue = value.clone().into();
@@ -795,4 +1419,305 @@ mod tests {
             prop_assert_eq!(value, round_trip_value);
         }
     }
+
+    fn enum_type() -> AnalysedType {
+        AnalysedType::Enum(TypeEnum {
+            cases: vec!["small".to_string(), "large".to_string()],
+        })
+    }
+
+    fn order_type() -> AnalysedType {
+        AnalysedType::Record(TypeRecord {
+            fields: vec![
+                NameTypePair {
+                    name: "id".to_string(),
+                    typ: AnalysedType::Str(TypeStr),
+                },
+                NameTypePair {
+                    name: "size".to_string(),
+                    typ: enum_type(),
+                },
+                NameTypePair {
+                    name: "options".to_string(),
+                    typ: AnalysedType::Flags(TypeFlags {
+                        names: vec!["gift".to_string(), "express".to_string()],
+                    }),
+                },
+                NameTypePair {
+                    name: "quantities".to_string(),
+                    typ: AnalysedType::List(TypeList {
+                        inner: Box::new(AnalysedType::U8(TypeU8)),
+                    }),
+                },
+                NameTypePair {
+                    name: "status".to_string(),
+                    typ: AnalysedType::Variant(TypeVariant {
+                        cases: vec![
+                            NameOptionTypePair {
+                                name: "pending".to_string(),
+                                typ: None,
+                            },
+                            NameOptionTypePair {
+                                name: "shipped".to_string(),
+                                typ: Some(AnalysedType::U32(TypeU32)),
+                            },
+                        ],
+                    }),
+                },
+                NameTypePair {
+                    name: "note".to_string(),
+                    typ: AnalysedType::Option(TypeOption {
+                        inner: Box::new(AnalysedType::Str(TypeStr)),
+                    }),
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn round_trip_typed_val() {
+        let value = Value::Record(vec![
+            Value::String("order-1".to_string()),
+            Value::Enum(1),
+            Value::Flags(vec![false, true]),
+            Value::List(vec![Value::U8(1), Value::U8(255)]),
+            Value::Variant {
+                case_idx: 1,
+                case_value: Some(Box::new(Value::U32(42))),
+            },
+            Value::Option(None),
+        ]);
+        let typed_value = TypeAnnotatedValue::create(&value, &order_type()).unwrap();
+
+        let val = Val::try_from(typed_value.clone()).unwrap();
+        let round_trip = type_annotated_value_from_val(&val, &order_type()).unwrap();
+
+        assert_eq!(round_trip, typed_value);
+    }
+
+    fn typed_val_error(val: val::Val, typ: &AnalysedType) -> String {
+        type_annotated_value_from_val(&Val { val: Some(val) }, typ).unwrap_err()
+    }
+
+    #[test]
+    fn typed_val_is_validated() {
+        assert_eq!(
+            typed_val_error(val::Val::Enum(ValEnum { discriminant: 2 }), &enum_type()),
+            "Enum discriminant 2 is out of range for 2 cases"
+        );
+        assert_eq!(
+            typed_val_error(val::Val::U8(256), &AnalysedType::U8(TypeU8)),
+            "Value 256 is out of range for u8"
+        );
+        assert_eq!(
+            typed_val_error(
+                val::Val::Flags(ValFlags {
+                    count: 2,
+                    value: vec![2],
+                }),
+                &AnalysedType::Flags(TypeFlags {
+                    names: vec!["gift".to_string(), "express".to_string()],
+                }),
+            ),
+            "Flag index 2 is out of range for 2 flags"
+        );
+        assert_eq!(
+            typed_val_error(
+                val::Val::Flags(ValFlags {
+                    count: 3,
+                    value: vec![],
+                }),
+                &AnalysedType::Flags(TypeFlags {
+                    names: vec!["gift".to_string(), "express".to_string()],
+                }),
+            ),
+            "Unexpected number of flags: 3 vs 2"
+        );
+        assert_eq!(
+            typed_val_error(
+                val::Val::Variant(Box::new(ValVariant {
+                    discriminant: 0,
+                    value: Some(Box::new(Val {
+                        val: Some(val::Val::U32(1)),
+                    })),
+                })),
+                &AnalysedType::Variant(TypeVariant {
+                    cases: vec![NameOptionTypePair {
+                        name: "pending".to_string(),
+                        typ: None,
+                    }],
+                }),
+            ),
+            "Unit variant case pending has a value"
+        );
+        assert!(
+            typed_val_error(val::Val::String("x".to_string()), &AnalysedType::U8(TypeU8))
+                .starts_with("Unexpected value for type")
+        );
+    }
+
+    #[test]
+    fn val_v2_encoding() {
+        let value: ValV2 = Value::U64(u64::MAX).into();
+        assert_eq!(value.val, Some(val_v2::Val::U64(u64::MAX)));
+
+        let value: ValV2 = Value::List(vec![Value::U8(1), Value::U8(2)]).into();
+        assert_eq!(value.val, Some(val_v2::Val::Bytes(vec![1, 2])));
+
+        let flags: Vec<bool> = (0..10).map(|i| i % 3 == 0).collect();
+        let value: ValV2 = Value::Flags(flags.clone()).into();
+        assert_eq!(
+            value.val,
+            Some(val_v2::Val::Flags(ValV2Flags {
+                count: 10,
+                bits: vec![0b0100_1001, 0b10],
+            }))
+        );
+        assert_eq!(Value::try_from(value), Ok(Value::Flags(flags)));
+    }
+
+    #[test]
+    fn val_v2_is_smaller() {
+        let value = Value::Record(vec![
+            Value::List((0..=255).map(Value::U8).collect()),
+            Value::Flags(vec![true; 16]),
+            Value::U32(u32::MAX),
+        ]);
+        let v1: Val = value.clone().into();
+        let v2: ValV2 = value.into();
+        assert!(v2.encoded_len() * 2 < v1.encoded_len());
+    }
+
+    #[test]
+    fn round_trip_invocation_request() {
+        let request = crate::InvocationRequest {
+            target: crate::Uri {
+                value: "urn:worker:component-1/worker-1".to_string(),
+            },
+            function_name: "golem:it/api.{add-item}".to_string(),
+            params: vec![Value::U32(1).into(), Value::String("x".to_string()).into()],
+            idempotency_key: Some("key-1".to_string()),
+        };
+
+        let encoded = InvocationRequest::from(request.clone()).encode_to_vec();
+        let decoded: crate::InvocationRequest = InvocationRequest::decode(encoded.as_slice())
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(decoded.target, request.target);
+        assert_eq!(decoded.function_name, request.function_name);
+        assert_eq!(
+            decoded
+                .params
+                .into_iter()
+                .map(Value::from)
+                .collect::<Vec<_>>(),
+            vec![Value::U32(1), Value::String("x".to_string())]
+        );
+        assert_eq!(decoded.idempotency_key, request.idempotency_key);
+    }
+
+    #[test]
+    fn round_trip_invocation_response() {
+        let round_trip = |response: Result<crate::WitValue, crate::RpcError>| {
+            let encoded = InvocationResponse::from(response).encode_to_vec();
+            let decoded: Result<crate::WitValue, crate::RpcError> =
+                InvocationResponse::decode(encoded.as_slice())
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+            decoded.map(Value::from).map_err(|err| format!("{err:?}"))
+        };
+
+        assert_eq!(
+            round_trip(Ok(Value::Bool(true).into())),
+            Ok(Value::Bool(true))
+        );
+        for error in [
+            crate::RpcError::ProtocolError("protocol".to_string()),
+            crate::RpcError::Denied("denied".to_string()),
+            crate::RpcError::NotFound("not found".to_string()),
+            crate::RpcError::RemoteInternalError("internal".to_string()),
+            crate::RpcError::Timeout("timeout".to_string()),
+        ] {
+            let expected = format!("{error:?}");
+            assert_eq!(round_trip(Err(error)), Err(expected));
+        }
+    }
+
+    #[test]
+    fn round_trip_batch_invocation() {
+        let request = crate::BatchInvocationRequest {
+            target: crate::Uri {
+                value: "urn:worker:component-1/worker-1".to_string(),
+            },
+            invocations: vec![
+                crate::BatchInvocation {
+                    function_name: "golem:it/api.{add-item}".to_string(),
+                    function_params: vec![Value::U32(1).into()],
+                },
+                crate::BatchInvocation {
+                    function_name: "golem:it/api.{checkout}".to_string(),
+                    function_params: vec![],
+                },
+            ],
+            options: crate::BatchOptions {
+                parallel: true,
+                stop_on_first_error: false,
+            },
+            idempotency_key: None,
+        };
+
+        let encoded = BatchInvocationRequest::from(request.clone()).encode_to_vec();
+        let decoded: crate::BatchInvocationRequest =
+            BatchInvocationRequest::decode(encoded.as_slice())
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        assert_eq!(decoded.target, request.target);
+        assert_eq!(
+            decoded
+                .invocations
+                .into_iter()
+                .map(|invocation| (
+                    invocation.function_name,
+                    invocation
+                        .function_params
+                        .into_iter()
+                        .map(Value::from)
+                        .collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("golem:it/api.{add-item}".to_string(), vec![Value::U32(1)]),
+                ("golem:it/api.{checkout}".to_string(), vec![])
+            ]
+        );
+        assert!(decoded.options.parallel);
+        assert!(!decoded.options.stop_on_first_error);
+        assert_eq!(decoded.idempotency_key, None);
+
+        let response = vec![
+            Ok(Value::Bool(true).into()),
+            Err(crate::RpcError::NotFound("missing".to_string())),
+        ];
+        let encoded = BatchInvocationResponse::from(response).encode_to_vec();
+        let decoded: Vec<Result<crate::WitValue, crate::RpcError>> =
+            BatchInvocationResponse::decode(encoded.as_slice())
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert_eq!(
+            decoded
+                .into_iter()
+                .map(|result| result.map(Value::from).map_err(|err| format!("{err:?}")))
+                .collect::<Vec<_>>(),
+            vec![
+                Ok(Value::Bool(true)),
+                Err("RpcError::NotFound(\"missing\")".to_string())
+            ]
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use crate::WitValue;
+use crate::{RpcError, Uri, WitValue};
 use serde::{Deserialize, Deserializer, Serialize};
 
 impl<'de> Deserialize<'de> for WitValue {
@@ -23,3 +23,87 @@ impl Serialize for WitValue {
         binary.serialize(serializer)
     }
 }
+
+/// Serialized as a plain string
+impl Serialize for Uri {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Uri {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Ok(Uri { value })
+    }
+}
+
+/// The serialized form of RpcError, an externally tagged enum using the case names of
+/// `wasm-rpc.wit`, such as `{"not-found": "..."}` in JSON
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum RpcErrorRepr {
+    ProtocolError(String),
+    Denied(String),
+    NotFound(String),
+    RemoteInternalError(String),
+    Timeout(String),
+}
+
+impl Serialize for RpcError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let repr = match self.clone() {
+            RpcError::ProtocolError(details) => RpcErrorRepr::ProtocolError(details),
+            RpcError::Denied(details) => RpcErrorRepr::Denied(details),
+            RpcError::NotFound(details) => RpcErrorRepr::NotFound(details),
+            RpcError::RemoteInternalError(details) => RpcErrorRepr::RemoteInternalError(details),
+            RpcError::Timeout(details) => RpcErrorRepr::Timeout(details),
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match RpcErrorRepr::deserialize(deserializer)? {
+            RpcErrorRepr::ProtocolError(details) => RpcError::ProtocolError(details),
+            RpcErrorRepr::Denied(details) => RpcError::Denied(details),
+            RpcErrorRepr::NotFound(details) => RpcError::NotFound(details),
+            RpcErrorRepr::RemoteInternalError(details) => RpcError::RemoteInternalError(details),
+            RpcErrorRepr::Timeout(details) => RpcError::Timeout(details),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RpcError, Uri};
+
+    #[test]
+    fn rpc_error_and_uri_as_json() {
+        let error = RpcError::NotFound("worker-1".to_string());
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(json, r#"{"not-found":"worker-1"}"#);
+        let decoded: RpcError = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{decoded:?}"), format!("{error:?}"));
+
+        let uri = Uri {
+            value: "urn:worker:component-1/worker-1".to_string(),
+        };
+        let json = serde_json::to_string(&uri).unwrap();
+        assert_eq!(json, r#""urn:worker:component-1/worker-1""#);
+        assert_eq!(serde_json::from_str::<Uri>(&json).unwrap(), uri);
+    }
+}
@@ -1,14 +1,21 @@
 use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
 use crate::protobuf::typed_result::ResultValue;
 use crate::protobuf::{
-    NameValuePair, TypedEnum, TypedFlags, TypedList, TypedOption, TypedRecord, TypedTuple,
-    TypedVariant,
+    NameValuePair, TypedEnum, TypedFlags, TypedHandle, TypedList, TypedOption, TypedRecord,
+    TypedTuple, TypedVariant,
 };
 use crate::protobuf::{TypeAnnotatedValue as RootTypeAnnotatedValue, TypedResult};
-use golem_wasm_ast::analysis::{protobuf, TypeEnum, TypeFlags};
+use crate::{TypeAnnotatedValueConstructors, Value};
+use golem_wasm_ast::analysis::{
+    protobuf, AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedResourceMode,
+    TypeEnum, TypeFlags, TypeHandle, TypeOption,
+};
 use golem_wasm_ast::analysis::{AnalysedType, TypeList, TypeRecord, TypeTuple, TypeVariant};
 use std::borrow::Cow;
 use std::ops::Deref;
+use std::str::FromStr;
+use wasm_wave::lex::Keyword;
+use wasm_wave::untyped::UntypedValue;
 use wasm_wave::wasm::{WasmType, WasmTypeKind, WasmValue, WasmValueError};
 use wasm_wave::{from_str, to_string};
 
@@ -17,9 +24,9 @@ pub fn type_annotated_value_from_str(
     input: &str,
 ) -> Result<TypeAnnotatedValue, String> {
     let parsed_typed_value: TypeAnnotatedValuePrintable =
-        from_str(analysed_type, input).map_err(|err| err.to_string())?;
+        from_str(&WaveType(analysed_type.clone()), input).map_err(|err| err.to_string())?;
 
-    Ok(parsed_typed_value.0)
+    typed_child(parsed_typed_value, analysed_type).map_err(|err| err.to_string())
 }
 
 pub fn type_annotated_value_to_string(value: &TypeAnnotatedValue) -> Result<String, String> {
@@ -31,16 +38,549 @@ pub fn type_annotated_value_to_string(value: &TypeAnnotatedValue) -> Result<Stri
     Ok(typed_value_str)
 }
 
+/// Converts a value of the given type to its WAVE representation.
+pub fn value_to_wave(value: &Value, typ: &AnalysedType) -> Result<String, String> {
+    type_annotated_value_to_string(&create_type_annotated_value(value, typ)?)
+}
+
+/// Converts a value of the given type to a multi-line WAVE representation, breaking lists,
+/// records, tuples and flags that do not fit into the line width.
+pub fn value_to_wave_pretty(
+    value: &Value,
+    typ: &AnalysedType,
+    options: &WavePrettyOptions,
+) -> Result<String, String> {
+    type_annotated_value_to_string_pretty(&create_type_annotated_value(value, typ)?, options)
+}
+
+/// Parses the WAVE representation of a value of the given type.
+pub fn value_from_wave(input: &str, typ: &AnalysedType) -> Result<Value, String> {
+    Value::try_from(type_annotated_value_from_str(typ, input)?)
+}
+
+fn create_type_annotated_value(
+    value: &Value,
+    typ: &AnalysedType,
+) -> Result<TypeAnnotatedValue, String> {
+    TypeAnnotatedValue::create(value, typ).map_err(|errors| errors.join(", "))
+}
+
+/// A function call parsed from WAVE, such as `add(1, 2)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedFunctionCall {
+    /// The fully qualified name of the called function, for example
+    /// `golem:it/api.{[method]cart.add-item}`
+    pub function_name: String,
+    pub function: AnalysedFunction,
+    /// The typed arguments. For resource methods the `self` parameter is not part of the call
+    /// syntax, so it is not included here either.
+    pub args: Vec<TypeAnnotatedValue>,
+}
+
+/// Parses a function call expression against the functions exported by a component.
+///
+/// The function can be referred to by its fully qualified name, such as
+/// `golem:it/api.{add-item}`, or only by its name if that is unique among the exports.
+/// Resource constructors, methods and static methods are called as `cart.new(...)`,
+/// `cart.add-item(...)` and `cart.create(...)`, or by their full names like
+/// `[method]cart.add-item`. Trailing `option` arguments can be omitted.
+pub fn parse_function_call(
+    input: &str,
+    exports: &[AnalysedExport],
+) -> Result<ParsedFunctionCall, String> {
+    let (name, args) = input
+        .split_once('(')
+        .map(|(name, _)| (name.trim(), &input[name.len()..]))
+        .ok_or_else(|| format!("Expected a function call, found {input}"))?;
+
+    let (function_name, function) = find_function(name, exports)?;
+    let params: Vec<&AnalysedFunctionParameter> = if function.is_method() {
+        function.parameters.iter().skip(1).collect()
+    } else {
+        function.parameters.iter().collect()
+    };
+
+    let args = UntypedValue::parse(args).map_err(|err| err.to_string())?;
+    let types: Vec<WaveType> = params.iter().map(|p| WaveType(p.typ.clone())).collect();
+    let values: Vec<TypeAnnotatedValuePrintable> = args
+        .node()
+        .to_wasm_params(&types, args.source())
+        .map_err(|err| format!("Invalid arguments for {function_name}: {err}"))?;
+    let args = values
+        .into_iter()
+        .zip(params)
+        .map(|(value, param)| {
+            typed_child(value, &param.typ)
+                .map_err(|err| format!("Invalid value for parameter {}: {err}", param.name))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(ParsedFunctionCall {
+        function_name,
+        function: function.clone(),
+        args,
+    })
+}
+
+pub(crate) fn find_function<'a>(
+    name: &str,
+    exports: &'a [AnalysedExport],
+) -> Result<(String, &'a AnalysedFunction), String> {
+    let (interface, item) = match name.strip_suffix('}').and_then(|n| n.split_once(".{")) {
+        Some((interface, item)) => (Some(interface), item),
+        None => (None, name),
+    };
+
+    let mut candidates = vec![item.to_string()];
+    if let Some((resource, method)) = item.split_once('.') {
+        if !item.starts_with('[') {
+            candidates.push(format!("[method]{item}"));
+            candidates.push(format!("[static]{item}"));
+            if method == "new" {
+                candidates.push(format!("[constructor]{resource}"));
+            }
+        }
+    }
+
+    let mut found = vec![];
+    for export in exports {
+        match export {
+            AnalysedExport::Function(function) => {
+                if interface.is_none() && candidates.contains(&function.name) {
+                    found.push((function.name.clone(), function));
+                }
+            }
+            AnalysedExport::Instance(instance) => {
+                if interface.is_none_or(|i| i == instance.name) {
+                    for function in &instance.functions {
+                        if candidates.contains(&function.name) {
+                            found.push((
+                                format!("{}.{{{}}}", instance.name, function.name),
+                                function,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    match found.len() {
+        0 => Err(format!("Unknown function {name}")),
+        1 => Ok(found.remove(0)),
+        _ => Err(format!(
+            "Ambiguous function name {name}, found {}",
+            found
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Options for multi-line WAVE output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WavePrettyOptions {
+    /// The number of spaces to indent nested values with
+    pub indent: usize,
+    /// The line width above which lists, records, tuples and flags are broken into multiple lines
+    pub max_width: usize,
+}
+
+impl Default for WavePrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            max_width: 80,
+        }
+    }
+}
+
+/// Converts a typed value to a multi-line WAVE representation, breaking lists, records, tuples
+/// and flags that do not fit into the line width.
+pub fn type_annotated_value_to_string_pretty(
+    value: &TypeAnnotatedValue,
+    options: &WavePrettyOptions,
+) -> Result<String, String> {
+    let layout = Layout::of(value)?;
+    let mut printer = PrettyPrinter {
+        options,
+        output: String::new(),
+    };
+    printer.write(&layout, 0, 0);
+    Ok(printer.output)
+}
+
+/// The parts of the WAVE representation of a value, with the width of its compact form, measured
+/// in a single pass over the value.
+struct Layout {
+    width: usize,
+    node: LayoutNode,
+}
+
+enum LayoutNode {
+    /// A value that is never broken into multiple lines
+    Text(String),
+    /// A list, tuple or record, with the field name prefixes of records
+    Items {
+        open: &'static str,
+        close: &'static str,
+        items: Vec<(String, Layout)>,
+    },
+    Flags(Vec<String>),
+    /// An option, result or variant case with a value
+    Case {
+        name: String,
+        inner: Box<Layout>,
+    },
+}
+
+impl Layout {
+    fn of(value: &TypeAnnotatedValue) -> Result<Self, String> {
+        match value {
+            TypeAnnotatedValue::List(TypedList { values, .. }) => {
+                Self::items("[", "]", values.iter().map(|value| ("", value)))
+            }
+            TypeAnnotatedValue::Tuple(TypedTuple { value, .. }) => {
+                Self::items("(", ")", value.iter().map(|value| ("", value)))
+            }
+            TypeAnnotatedValue::Record(TypedRecord { value, .. }) => {
+                // like the compact form, `none` fields are omitted
+                let fields = value
+                    .iter()
+                    .map(|field| {
+                        let value = field
+                            .value
+                            .as_ref()
+                            .ok_or_else(|| format!("Missing value of field {}", field.name))?;
+                        Ok((format!("{}: ", field.name), value))
+                    })
+                    .filter(|field| {
+                        !matches!(field, Ok((_, RootTypeAnnotatedValue {
+                            type_annotated_value: Some(TypeAnnotatedValue::Option(option)),
+                        })) if option.value.is_none())
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                if fields.is_empty() {
+                    Ok(Self::text("{:}".to_string()))
+                } else {
+                    Self::items(
+                        "{",
+                        "}",
+                        fields.iter().map(|(name, value)| (name.as_str(), *value)),
+                    )
+                }
+            }
+            TypeAnnotatedValue::Flags(TypedFlags { values, .. }) => {
+                let width = 2 + Self::joined_width(values.iter().map(|name| name.chars().count()));
+                Ok(Self {
+                    width,
+                    node: LayoutNode::Flags(values.clone()),
+                })
+            }
+            TypeAnnotatedValue::Option(option) => {
+                match option
+                    .value
+                    .as_ref()
+                    .and_then(|v| v.type_annotated_value.as_ref())
+                {
+                    Some(inner) => Self::case("some".to_string(), inner),
+                    None => Ok(Self::text("none".to_string())),
+                }
+            }
+            TypeAnnotatedValue::Result(result) => {
+                let (name, inner) = match &result.result_value {
+                    Some(ResultValue::OkValue(ok)) => ("ok", ok.type_annotated_value.as_ref()),
+                    Some(ResultValue::ErrorValue(err)) => {
+                        ("err", err.type_annotated_value.as_ref())
+                    }
+                    None => return Err("Missing value in result".to_string()),
+                };
+                match inner {
+                    Some(inner) => Self::case(name.to_string(), inner),
+                    None => Ok(Self::text(name.to_string())),
+                }
+            }
+            TypeAnnotatedValue::Variant(variant) => {
+                let name = if Keyword::decode(&variant.case_name).is_some() {
+                    format!("%{}", variant.case_name)
+                } else {
+                    variant.case_name.clone()
+                };
+                match variant
+                    .case_value
+                    .as_ref()
+                    .and_then(|v| v.type_annotated_value.as_ref())
+                {
+                    Some(inner) => Self::case(name, inner),
+                    None => Ok(Self::text(name)),
+                }
+            }
+            _ => {
+                let text = to_string(&TypeAnnotatedValuePrintable(value.clone()))
+                    .map_err(|err| err.to_string())?;
+                Ok(Self::text(text))
+            }
+        }
+    }
+
+    fn text(text: String) -> Self {
+        Self {
+            width: text.chars().count(),
+            node: LayoutNode::Text(text),
+        }
+    }
+
+    fn items<'v>(
+        open: &'static str,
+        close: &'static str,
+        items: impl Iterator<Item = (&'v str, &'v RootTypeAnnotatedValue)>,
+    ) -> Result<Self, String> {
+        let items = items
+            .map(|(prefix, value)| {
+                let value = value
+                    .type_annotated_value
+                    .as_ref()
+                    .ok_or_else(|| "Missing value".to_string())?;
+                Ok((prefix.to_string(), Self::of(value)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let width = open.len()
+            + close.len()
+            + Self::joined_width(
+                items
+                    .iter()
+                    .map(|(prefix, item)| prefix.chars().count() + item.width),
+            );
+        Ok(Self {
+            width,
+            node: LayoutNode::Items { open, close, items },
+        })
+    }
+
+    fn case(name: String, inner: &TypeAnnotatedValue) -> Result<Self, String> {
+        let inner = Self::of(inner)?;
+        Ok(Self {
+            width: name.chars().count() + 2 + inner.width,
+            node: LayoutNode::Case {
+                name,
+                inner: Box::new(inner),
+            },
+        })
+    }
+
+    /// The width of items separated by `, `
+    fn joined_width(widths: impl Iterator<Item = usize>) -> usize {
+        widths
+            .enumerate()
+            .map(|(idx, width)| if idx == 0 { width } else { width + 2 })
+            .sum()
+    }
+}
+
+struct PrettyPrinter<'a> {
+    options: &'a WavePrettyOptions,
+    output: String,
+}
+
+impl PrettyPrinter<'_> {
+    /// Writes the value starting at the given column of a line indented by `level`.
+    fn write(&mut self, layout: &Layout, level: usize, column: usize) {
+        if column + layout.width <= self.options.max_width {
+            self.write_compact(layout);
+            return;
+        }
+
+        match &layout.node {
+            LayoutNode::Text(text) => self.output.push_str(text),
+            LayoutNode::Items { open, close, items } => {
+                self.output.push_str(open);
+                for (idx, (prefix, item)) in items.iter().enumerate() {
+                    self.new_line(level + 1);
+                    self.output.push_str(prefix);
+                    let column = (level + 1) * self.options.indent + prefix.chars().count();
+                    // leave room for the separator
+                    self.write(item, level + 1, column + 1);
+                    if idx + 1 < items.len() {
+                        self.output.push(',');
+                    }
+                }
+                if !items.is_empty() {
+                    self.new_line(level);
+                }
+                self.output.push_str(close);
+            }
+            LayoutNode::Flags(names) => {
+                self.output.push('{');
+                for (idx, name) in names.iter().enumerate() {
+                    self.new_line(level + 1);
+                    self.output.push_str(name);
+                    if idx + 1 < names.len() {
+                        self.output.push(',');
+                    }
+                }
+                self.new_line(level);
+                self.output.push('}');
+            }
+            LayoutNode::Case { name, inner } => {
+                self.output.push_str(name);
+                self.output.push('(');
+                self.write(inner, level, column + name.chars().count() + 1);
+                self.output.push(')');
+            }
+        }
+    }
+
+    /// Writes the value the same way as `wasm_wave::to_string`
+    fn write_compact(&mut self, layout: &Layout) {
+        match &layout.node {
+            LayoutNode::Text(text) => self.output.push_str(text),
+            LayoutNode::Items { open, close, items } => {
+                self.output.push_str(open);
+                for (idx, (prefix, item)) in items.iter().enumerate() {
+                    if idx != 0 {
+                        self.output.push_str(", ");
+                    }
+                    self.output.push_str(prefix);
+                    self.write_compact(item);
+                }
+                self.output.push_str(close);
+            }
+            LayoutNode::Flags(names) => {
+                self.output.push('{');
+                self.output.push_str(&names.join(", "));
+                self.output.push('}');
+            }
+            LayoutNode::Case { name, inner } => {
+                self.output.push_str(name);
+                self.output.push('(');
+                self.write_compact(inner);
+                self.output.push(')');
+            }
+        }
+    }
+
+    fn new_line(&mut self, level: usize) {
+        self.output.push('\n');
+        self.output
+            .extend(std::iter::repeat_n(' ', level * self.options.indent));
+    }
+}
+
+/// The type of WAVE values.
+///
+/// WAVE has no syntax for resource handles, so they are represented by a string literal in the
+/// same `<uri>/<resource-id>` form as in JSON, for example `"urn:worker:component/worker/42"`.
+#[derive(Debug, Clone)]
+pub struct WaveType(pub AnalysedType);
+
+impl WasmType for WaveType {
+    fn kind(&self) -> WasmTypeKind {
+        match &self.0 {
+            AnalysedType::Handle(_) => WasmTypeKind::String,
+            typ => typ.kind(),
+        }
+    }
+
+    fn list_element_type(&self) -> Option<Self> {
+        self.0.list_element_type().map(WaveType)
+    }
+
+    fn record_fields(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, Self)> + '_> {
+        Box::new(
+            self.0
+                .record_fields()
+                .map(|(name, typ)| (name, WaveType(typ))),
+        )
+    }
+
+    fn tuple_element_types(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        Box::new(self.0.tuple_element_types().map(WaveType))
+    }
+
+    fn variant_cases(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, Option<Self>)> + '_> {
+        Box::new(
+            self.0
+                .variant_cases()
+                .map(|(name, typ)| (name, typ.map(WaveType))),
+        )
+    }
+
+    fn enum_cases(&self) -> Box<dyn Iterator<Item = Cow<'_, str>> + '_> {
+        self.0.enum_cases()
+    }
+
+    fn option_some_type(&self) -> Option<Self> {
+        self.0.option_some_type().map(WaveType)
+    }
+
+    fn result_types(&self) -> Option<(Option<Self>, Option<Self>)> {
+        self.0
+            .result_types()
+            .map(|(ok, err)| (ok.map(WaveType), err.map(WaveType)))
+    }
+
+    fn flags_names(&self) -> Box<dyn Iterator<Item = Cow<'_, str>> + '_> {
+        self.0.flags_names()
+    }
+}
+
+/// Unwraps a parsed value of the given type, turning the string literals parsed for handles into
+/// handle values.
+fn typed_child(
+    value: TypeAnnotatedValuePrintable,
+    typ: &AnalysedType,
+) -> Result<TypeAnnotatedValue, WasmValueError> {
+    match (value.0, typ) {
+        (TypeAnnotatedValue::Str(str), AnalysedType::Handle(TypeHandle { resource_id, mode })) => {
+            // not assuming much about the uri format, just checking it ends with a /<resource-id-u64>
+            let (uri, id) = str.rsplit_once('/').ok_or_else(|| {
+                WasmValueError::Other(format!(
+                    "Expected a handle in the form of <uri>/<resource-id>, found {str}"
+                ))
+            })?;
+            let id = u64::from_str(id).map_err(|err| {
+                WasmValueError::Other(format!(
+                    "Failed to parse resource-id section of the handle value: {err}"
+                ))
+            })?;
+            Ok(TypeAnnotatedValue::Handle(TypedHandle {
+                typ: Some(protobuf::TypeHandle {
+                    resource_id: resource_id.0,
+                    mode: match mode {
+                        AnalysedResourceMode::Owned => 1,
+                        AnalysedResourceMode::Borrowed => 2,
+                    },
+                }),
+                uri: uri.to_string(),
+                resource_id: id,
+            }))
+        }
+        (value, _) => Ok(value),
+    }
+}
+
+fn typed_case(
+    value: Option<TypeAnnotatedValuePrintable>,
+    typ: Option<&AnalysedType>,
+) -> Result<Option<TypeAnnotatedValue>, WasmValueError> {
+    match (value, typ) {
+        (Some(value), Some(typ)) => typed_child(value, typ).map(Some),
+        (value, _) => Ok(value.map(|v| v.0)),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TypeAnnotatedValuePrintable(pub TypeAnnotatedValue);
 
 impl WasmValue for TypeAnnotatedValuePrintable {
-    type Type = AnalysedType;
+    type Type = WaveType;
 
     fn kind(&self) -> WasmTypeKind {
         let analysed_type = AnalysedType::try_from(&self.0)
             .expect("Failed to retrieve AnalysedType from TypeAnnotatedValue");
-        analysed_type.kind()
+        WaveType(analysed_type).kind()
     }
 
     fn make_bool(val: bool) -> Self {
@@ -99,14 +639,16 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         ty: &Self::Type,
         vals: impl IntoIterator<Item = Self>,
     ) -> Result<Self, WasmValueError> {
-        if let AnalysedType::List(TypeList { inner: typ }) = ty {
+        if let AnalysedType::List(TypeList { inner: typ }) = &ty.0 {
             let list = TypedList {
                 values: vals
                     .into_iter()
-                    .map(|v| RootTypeAnnotatedValue {
-                        type_annotated_value: Some(v.0),
+                    .map(|v| {
+                        Ok(RootTypeAnnotatedValue {
+                            type_annotated_value: Some(typed_child(v, typ)?),
+                        })
                     })
-                    .collect(),
+                    .collect::<Result<_, _>>()?,
                 typ: Some(typ.deref().into()),
             };
 
@@ -114,7 +656,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         } else {
             Err(WasmValueError::WrongTypeKind {
                 kind: ty.kind(),
-                ty: format!("{ty:?}"),
+                ty: format!("{:?}", ty.0),
             })
         }
     }
@@ -123,17 +665,23 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         ty: &Self::Type,
         fields: impl IntoIterator<Item = (&'a str, Self)>,
     ) -> Result<Self, WasmValueError> {
-        if let AnalysedType::Record(TypeRecord { fields: types }) = ty {
+        if let AnalysedType::Record(TypeRecord { fields: types }) = &ty.0 {
             let record = TypedRecord {
                 value: fields
                     .into_iter()
-                    .map(|(name, value)| NameValuePair {
-                        name: name.to_string(),
-                        value: Some(RootTypeAnnotatedValue {
-                            type_annotated_value: Some(value.0),
-                        }),
+                    .map(|(name, value)| {
+                        let value = match types.iter().find(|pair| pair.name == name) {
+                            Some(pair) => typed_child(value, &pair.typ)?,
+                            None => value.0,
+                        };
+                        Ok(NameValuePair {
+                            name: name.to_string(),
+                            value: Some(RootTypeAnnotatedValue {
+                                type_annotated_value: Some(value),
+                            }),
+                        })
                     })
-                    .collect(),
+                    .collect::<Result<_, _>>()?,
                 typ: types
                     .iter()
                     .map(|pair| protobuf::NameTypePair {
@@ -148,7 +696,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         } else {
             Err(WasmValueError::WrongTypeKind {
                 kind: ty.kind(),
-                ty: format!("{ty:?}"),
+                ty: format!("{:?}", ty.0),
             })
         }
     }
@@ -157,14 +705,17 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         ty: &Self::Type,
         vals: impl IntoIterator<Item = Self>,
     ) -> Result<Self, WasmValueError> {
-        if let AnalysedType::Tuple(TypeTuple { items: types }) = ty {
+        if let AnalysedType::Tuple(TypeTuple { items: types }) = &ty.0 {
             let tuple = TypedTuple {
                 value: vals
                     .into_iter()
-                    .map(|v| RootTypeAnnotatedValue {
-                        type_annotated_value: Some(v.0),
+                    .zip(types)
+                    .map(|(v, typ)| {
+                        Ok(RootTypeAnnotatedValue {
+                            type_annotated_value: Some(typed_child(v, typ)?),
+                        })
                     })
-                    .collect(),
+                    .collect::<Result<_, _>>()?,
                 typ: types.iter().map(|t| t.into()).collect(),
             };
             Ok(TypeAnnotatedValuePrintable(TypeAnnotatedValue::Tuple(
@@ -173,7 +724,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         } else {
             Err(WasmValueError::WrongTypeKind {
                 kind: ty.kind(),
-                ty: format!("{ty:?}"),
+                ty: format!("{:?}", ty.0),
             })
         }
     }
@@ -183,7 +734,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         case: &str,
         val: Option<Self>,
     ) -> Result<Self, WasmValueError> {
-        if let AnalysedType::Variant(TypeVariant { cases }) = ty {
+        if let AnalysedType::Variant(TypeVariant { cases }) = &ty.0 {
             let case_type = cases.iter().find_map(|pair| {
                 if pair.name == case {
                     Some(&pair.typ)
@@ -191,7 +742,11 @@ impl WasmValue for TypeAnnotatedValuePrintable {
                     None
                 }
             });
-            if case_type.is_some() {
+            if let Some(case_type) = case_type {
+                let case_value = match (val, case_type) {
+                    (Some(v), Some(typ)) => Some(typed_child(v, typ)?),
+                    (v, _) => v.map(|v| v.0),
+                };
                 let variant = TypedVariant {
                     typ: Some(protobuf::TypeVariant {
                         cases: cases
@@ -203,9 +758,9 @@ impl WasmValue for TypeAnnotatedValuePrintable {
                             .collect(),
                     }),
                     case_name: case.to_string(),
-                    case_value: val.map(|v| {
+                    case_value: case_value.map(|v| {
                         Box::new(RootTypeAnnotatedValue {
-                            type_annotated_value: Some(v.0),
+                            type_annotated_value: Some(v),
                         })
                     }),
                 };
@@ -218,13 +773,13 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         } else {
             Err(WasmValueError::WrongTypeKind {
                 kind: ty.kind(),
-                ty: format!("{ty:?}"),
+                ty: format!("{:?}", ty.0),
             })
         }
     }
 
     fn make_enum(ty: &Self::Type, case: &str) -> Result<Self, WasmValueError> {
-        if let AnalysedType::Enum(TypeEnum { cases }) = ty {
+        if let AnalysedType::Enum(TypeEnum { cases }) = &ty.0 {
             if cases.contains(&case.to_string()) {
                 let enum_value = TypedEnum {
                     typ: cases.to_vec(),
@@ -239,19 +794,29 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         } else {
             Err(WasmValueError::WrongTypeKind {
                 kind: ty.kind(),
-                ty: format!("{ty:?}"),
+                ty: format!("{:?}", ty.0),
             })
         }
     }
 
     fn make_option(ty: &Self::Type, val: Option<Self>) -> Result<Self, WasmValueError> {
-        let option = TypedOption {
-            typ: Some(ty.into()),
-            value: val.map(|v| {
-                Box::new(RootTypeAnnotatedValue {
-                    type_annotated_value: Some(v.0),
+        let inner_type = match &ty.0 {
+            AnalysedType::Option(TypeOption { inner }) => inner.deref(),
+            _ => {
+                return Err(WasmValueError::WrongTypeKind {
+                    kind: ty.kind(),
+                    ty: format!("{:?}", ty.0),
                 })
-            }),
+            }
+        };
+        let option = TypedOption {
+            typ: Some(inner_type.into()),
+            value: match val {
+                Some(v) => Some(Box::new(RootTypeAnnotatedValue {
+                    type_annotated_value: Some(typed_child(v, inner_type)?),
+                })),
+                None => None,
+            },
         };
 
         Ok(TypeAnnotatedValuePrintable(TypeAnnotatedValue::Option(
@@ -263,22 +828,19 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         ty: &Self::Type,
         val: Result<Option<Self>, Option<Self>>,
     ) -> Result<Self, WasmValueError> {
-        if let AnalysedType::Result(golem_wasm_ast::analysis::TypeResult { ok, err }) = ty {
+        if let AnalysedType::Result(golem_wasm_ast::analysis::TypeResult { ok, err }) = &ty.0 {
             let result0 = TypedResult {
                 ok: ok.clone().map(|v| v.deref().into()),
                 error: err.clone().map(|v| v.deref().into()),
-                result_value: match val {
-                    Ok(Some(v)) => Some(ResultValue::OkValue(Box::new(RootTypeAnnotatedValue {
-                        type_annotated_value: Some(v.0),
-                    }))),
-                    Ok(None) => None,
-                    Err(Some(v)) => {
-                        Some(ResultValue::ErrorValue(Box::new(RootTypeAnnotatedValue {
-                            type_annotated_value: Some(v.0),
-                        })))
-                    }
-                    Err(None) => None,
-                },
+                // unit cases are represented by a missing inner value
+                result_value: Some(match val {
+                    Ok(v) => ResultValue::OkValue(Box::new(RootTypeAnnotatedValue {
+                        type_annotated_value: typed_case(v, ok.as_deref())?,
+                    })),
+                    Err(v) => ResultValue::ErrorValue(Box::new(RootTypeAnnotatedValue {
+                        type_annotated_value: typed_case(v, err.as_deref())?,
+                    })),
+                }),
             };
             Ok(TypeAnnotatedValuePrintable(TypeAnnotatedValue::Result(
                 Box::new(result0),
@@ -286,7 +848,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         } else {
             Err(WasmValueError::WrongTypeKind {
                 kind: ty.kind(),
-                ty: format!("{ty:?}"),
+                ty: format!("{:?}", ty.0),
             })
         }
     }
@@ -295,7 +857,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         ty: &Self::Type,
         names: impl IntoIterator<Item = &'a str>,
     ) -> Result<Self, WasmValueError> {
-        if let AnalysedType::Flags(TypeFlags { names: all_names }) = ty {
+        if let AnalysedType::Flags(TypeFlags { names: all_names }) = &ty.0 {
             let names: Vec<String> = names.into_iter().map(|name| name.to_string()).collect();
 
             let invalid_names: Vec<String> = names
@@ -319,7 +881,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         } else {
             Err(WasmValueError::WrongTypeKind {
                 kind: ty.kind(),
-                ty: format!("{ty:?}"),
+                ty: format!("{:?}", ty.0),
             })
         }
     }
@@ -408,14 +970,17 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         }
     }
 
-    fn unwrap_string(&self) -> Cow<str> {
+    fn unwrap_string(&self) -> Cow<'_, str> {
         match self.0.clone() {
             TypeAnnotatedValue::Str(value) => Cow::Owned(value.clone()),
+            TypeAnnotatedValue::Handle(TypedHandle {
+                uri, resource_id, ..
+            }) => Cow::Owned(format!("{uri}/{resource_id}")),
             _ => panic!("Expected string, found {:?}", self),
         }
     }
 
-    fn unwrap_list(&self) -> Box<dyn Iterator<Item = Cow<Self>> + '_> {
+    fn unwrap_list(&self) -> Box<dyn Iterator<Item = Cow<'_, Self>> + '_> {
         match self.0.clone() {
             TypeAnnotatedValue::List(TypedList { typ: _, values }) => {
                 Box::new(values.into_iter().map(|v| {
@@ -428,7 +993,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         }
     }
 
-    fn unwrap_record(&self) -> Box<dyn Iterator<Item = (Cow<str>, Cow<Self>)> + '_> {
+    fn unwrap_record(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, Cow<'_, Self>)> + '_> {
         match self.0.clone() {
             TypeAnnotatedValue::Record(TypedRecord { typ: _, value }) => {
                 Box::new(value.into_iter().map(|name_value| {
@@ -445,7 +1010,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         }
     }
 
-    fn unwrap_tuple(&self) -> Box<dyn Iterator<Item = Cow<Self>> + '_> {
+    fn unwrap_tuple(&self) -> Box<dyn Iterator<Item = Cow<'_, Self>> + '_> {
         match self.0.clone() {
             TypeAnnotatedValue::Tuple(TypedTuple { typ: _, value }) => {
                 Box::new(value.into_iter().map(|x| {
@@ -460,7 +1025,7 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         }
     }
 
-    fn unwrap_variant(&self) -> (Cow<str>, Option<Cow<Self>>) {
+    fn unwrap_variant(&self) -> (Cow<'_, str>, Option<Cow<'_, Self>>) {
         match self.0.clone() {
             TypeAnnotatedValue::Variant(variant) => {
                 let case_name = Cow::Owned(variant.case_name);
@@ -473,14 +1038,14 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         }
     }
 
-    fn unwrap_enum(&self) -> Cow<str> {
+    fn unwrap_enum(&self) -> Cow<'_, str> {
         match self.0.clone() {
             TypeAnnotatedValue::Enum(TypedEnum { typ: _, value }) => Cow::Owned(value),
             _ => panic!("Expected enum, found {:?}", self),
         }
     }
 
-    fn unwrap_option(&self) -> Option<Cow<Self>> {
+    fn unwrap_option(&self) -> Option<Cow<'_, Self>> {
         match self.0.clone() {
             TypeAnnotatedValue::Option(option) => option.value.as_ref().and_then(|v| {
                 v.type_annotated_value
@@ -491,30 +1056,22 @@ impl WasmValue for TypeAnnotatedValuePrintable {
         }
     }
 
-    fn unwrap_result(&self) -> Result<Option<Cow<Self>>, Option<Cow<Self>>> {
+    fn unwrap_result(&self) -> Result<Option<Cow<'_, Self>>, Option<Cow<'_, Self>>> {
         match self.0.clone() {
             TypeAnnotatedValue::Result(result0) => match result0.result_value {
-                Some(result) => match result {
-                    ResultValue::OkValue(ok) => match ok.type_annotated_value {
-                        Some(ok_value) => {
-                            Ok(Some(Cow::Owned(TypeAnnotatedValuePrintable(ok_value))))
-                        }
-                        None => panic!("Expected ok, found None"),
-                    },
-                    ResultValue::ErrorValue(error) => match error.type_annotated_value {
-                        Some(error_value) => {
-                            Err(Some(Cow::Owned(TypeAnnotatedValuePrintable(error_value))))
-                        }
-                        None => panic!("Expected error, found None"),
-                    },
-                },
-                None => panic!("Expected ok, found None"),
+                Some(ResultValue::OkValue(ok)) => Ok(ok
+                    .type_annotated_value
+                    .map(|ok_value| Cow::Owned(TypeAnnotatedValuePrintable(ok_value)))),
+                Some(ResultValue::ErrorValue(error)) => Err(error
+                    .type_annotated_value
+                    .map(|error_value| Cow::Owned(TypeAnnotatedValuePrintable(error_value)))),
+                None => panic!("Expected ok or error, found None"),
             },
             _ => panic!("Expected result, found {:?}", self),
         }
     }
 
-    fn unwrap_flags(&self) -> Box<dyn Iterator<Item = Cow<str>> + '_> {
+    fn unwrap_flags(&self) -> Box<dyn Iterator<Item = Cow<'_, str>> + '_> {
         match self.0.clone() {
             TypeAnnotatedValue::Flags(TypedFlags { typ: _, values }) => {
                 Box::new(values.into_iter().map(Cow::Owned))
@@ -528,11 +1085,16 @@ impl WasmValue for TypeAnnotatedValuePrintable {
 mod tests {
     use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
     use crate::text::type_annotated_value_from_str;
-    use crate::{type_annotated_value_to_string, TypeAnnotatedValueConstructors, Value};
+    use crate::{
+        parse_function_call, type_annotated_value_to_string, value_from_wave, value_to_wave,
+        value_to_wave_pretty, TypeAnnotatedValueConstructors, Uri, Value, WavePrettyOptions,
+    };
     use golem_wasm_ast::analysis::{
-        AnalysedType, NameOptionTypePair, NameTypePair, TypeBool, TypeChr, TypeEnum, TypeF32,
-        TypeF64, TypeFlags, TypeOption, TypeRecord, TypeResult, TypeS16, TypeS32, TypeS64, TypeS8,
-        TypeStr, TypeTuple, TypeU16, TypeU32, TypeU64, TypeU8, TypeVariant,
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedInstance, AnalysedResourceId, AnalysedResourceMode, AnalysedType,
+        NameOptionTypePair, NameTypePair, TypeBool, TypeChr, TypeEnum, TypeF32, TypeF64, TypeFlags,
+        TypeHandle, TypeList, TypeOption, TypeRecord, TypeResult, TypeS16, TypeS32, TypeS64,
+        TypeS8, TypeStr, TypeTuple, TypeU16, TypeU32, TypeU64, TypeU8, TypeVariant,
     };
 
     fn round_trip(value: Value, typ: AnalysedType) {
@@ -761,4 +1323,373 @@ mod tests {
             }),
         );
     }
+
+    #[test]
+    fn round_trip_unit_result() {
+        round_trip(
+            Value::Result(Ok(None)),
+            AnalysedType::Result(TypeResult {
+                ok: None,
+                err: Some(Box::new(AnalysedType::Str(TypeStr))),
+            }),
+        );
+    }
+
+    fn handle_type() -> AnalysedType {
+        AnalysedType::Handle(TypeHandle {
+            resource_id: AnalysedResourceId(7),
+            mode: AnalysedResourceMode::Borrowed,
+        })
+    }
+
+    fn handle(resource_id: u64) -> Value {
+        handle_at("urn:worker:component-1/worker-1", resource_id)
+    }
+
+    fn handle_at(uri: &str, resource_id: u64) -> Value {
+        Value::Handle {
+            uri: Uri {
+                value: uri.to_string(),
+            },
+            resource_id,
+        }
+    }
+
+    #[test]
+    fn round_trip_handle() {
+        round_trip(handle(42), handle_type());
+    }
+
+    #[test]
+    fn round_trip_nested_handles() {
+        round_trip(
+            Value::Record(vec![
+                Value::List(vec![handle(1), handle(2)]),
+                Value::Option(Some(Box::new(handle(3)))),
+                Value::Result(Err(Some(Box::new(handle(4))))),
+            ]),
+            AnalysedType::Record(TypeRecord {
+                fields: vec![
+                    NameTypePair {
+                        name: "items".to_string(),
+                        typ: AnalysedType::List(TypeList {
+                            inner: Box::new(handle_type()),
+                        }),
+                    },
+                    NameTypePair {
+                        name: "current".to_string(),
+                        typ: AnalysedType::Option(TypeOption {
+                            inner: Box::new(handle_type()),
+                        }),
+                    },
+                    NameTypePair {
+                        name: "last".to_string(),
+                        typ: AnalysedType::Result(TypeResult {
+                            ok: None,
+                            err: Some(Box::new(handle_type())),
+                        }),
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn handle_as_wave() {
+        assert_eq!(
+            value_to_wave(&handle(42), &handle_type()),
+            Ok(r#""urn:worker:component-1/worker-1/42""#.to_string())
+        );
+        assert_eq!(
+            value_from_wave(r#""urn:worker:component-1/worker-1/42""#, &handle_type()),
+            Ok(handle(42))
+        );
+        assert!(value_from_wave(r#""urn:worker:component-1""#, &handle_type()).is_err());
+    }
+
+    fn cart_exports() -> Vec<AnalysedExport> {
+        let cart = |mode| {
+            AnalysedType::Handle(TypeHandle {
+                resource_id: AnalysedResourceId(0),
+                mode,
+            })
+        };
+        let function =
+            |name: &str, parameters: Vec<(&str, AnalysedType)>, results| AnalysedFunction {
+                name: name.to_string(),
+                parameters: parameters
+                    .into_iter()
+                    .map(|(name, typ)| AnalysedFunctionParameter {
+                        name: name.to_string(),
+                        typ,
+                    })
+                    .collect(),
+                results,
+            };
+        vec![
+            AnalysedExport::Function(function(
+                "add",
+                vec![
+                    ("x", AnalysedType::U32(TypeU32)),
+                    ("y", AnalysedType::U32(TypeU32)),
+                ],
+                vec![],
+            )),
+            AnalysedExport::Instance(AnalysedInstance {
+                name: "golem:it/api".to_string(),
+                functions: vec![
+                    function(
+                        "[constructor]cart",
+                        vec![("user-id", AnalysedType::Str(TypeStr))],
+                        vec![AnalysedFunctionResult {
+                            name: None,
+                            typ: cart(AnalysedResourceMode::Owned),
+                        }],
+                    ),
+                    function(
+                        "[method]cart.add-item",
+                        vec![
+                            ("self", cart(AnalysedResourceMode::Borrowed)),
+                            (
+                                "item",
+                                AnalysedType::Record(TypeRecord {
+                                    fields: vec![
+                                        NameTypePair {
+                                            name: "product-id".to_string(),
+                                            typ: AnalysedType::Str(TypeStr),
+                                        },
+                                        NameTypePair {
+                                            name: "quantity".to_string(),
+                                            typ: AnalysedType::U32(TypeU32),
+                                        },
+                                    ],
+                                }),
+                            ),
+                            (
+                                "note",
+                                AnalysedType::Option(TypeOption {
+                                    inner: Box::new(AnalysedType::Str(TypeStr)),
+                                }),
+                            ),
+                        ],
+                        vec![],
+                    ),
+                    function(
+                        "[static]cart.merge",
+                        vec![
+                            ("first", cart(AnalysedResourceMode::Borrowed)),
+                            ("second", cart(AnalysedResourceMode::Borrowed)),
+                        ],
+                        vec![],
+                    ),
+                ],
+            }),
+            AnalysedExport::Instance(AnalysedInstance {
+                name: "golem:it/other".to_string(),
+                functions: vec![function("add", vec![], vec![])],
+            }),
+        ]
+    }
+
+    fn call_args(input: &str) -> Result<(String, Vec<Value>), String> {
+        let call = parse_function_call(input, &cart_exports())?;
+        let args = call
+            .args
+            .into_iter()
+            .map(Value::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok((call.function_name, args))
+    }
+
+    #[test]
+    fn parse_function_calls() {
+        assert_eq!(
+            call_args("golem:it/api.{[constructor]cart}(\"user-1\")"),
+            Ok((
+                "golem:it/api.{[constructor]cart}".to_string(),
+                vec![Value::String("user-1".to_string())]
+            ))
+        );
+        assert_eq!(
+            call_args("cart.new(\"user-1\")"),
+            Ok((
+                "golem:it/api.{[constructor]cart}".to_string(),
+                vec![Value::String("user-1".to_string())]
+            ))
+        );
+        assert_eq!(
+            call_args("cart.add-item({product-id: \"x\", quantity: 2})"),
+            Ok((
+                "golem:it/api.{[method]cart.add-item}".to_string(),
+                vec![
+                    Value::Record(vec![Value::String("x".to_string()), Value::U32(2)]),
+                    Value::Option(None)
+                ]
+            ))
+        );
+        assert_eq!(
+            call_args("cart.merge(\"urn:worker:c/w/1\", \"urn:worker:c/w/2\")"),
+            Ok((
+                "golem:it/api.{[static]cart.merge}".to_string(),
+                vec![
+                    handle_at("urn:worker:c/w", 1),
+                    handle_at("urn:worker:c/w", 2)
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_function_call_errors() {
+        assert_eq!(
+            call_args("add(1, 2)"),
+            Err("Ambiguous function name add, found add, golem:it/other.{add}".to_string())
+        );
+        assert_eq!(
+            call_args("cart.remove-item(1)"),
+            Err("Unknown function cart.remove-item".to_string())
+        );
+        assert!(call_args("golem:it/api.{cart.add-item}(1)").is_err());
+        assert!(call_args("add").is_err());
+    }
+
+    fn order_type() -> AnalysedType {
+        AnalysedType::Record(TypeRecord {
+            fields: vec![
+                NameTypePair {
+                    name: "id".to_string(),
+                    typ: AnalysedType::Str(TypeStr),
+                },
+                NameTypePair {
+                    name: "items".to_string(),
+                    typ: AnalysedType::List(TypeList {
+                        inner: Box::new(AnalysedType::Tuple(TypeTuple {
+                            items: vec![AnalysedType::Str(TypeStr), AnalysedType::U32(TypeU32)],
+                        })),
+                    }),
+                },
+                NameTypePair {
+                    name: "note".to_string(),
+                    typ: AnalysedType::Option(TypeOption {
+                        inner: Box::new(AnalysedType::Str(TypeStr)),
+                    }),
+                },
+            ],
+        })
+    }
+
+    fn order() -> Value {
+        Value::Record(vec![
+            Value::String("order-1".to_string()),
+            Value::List(vec![
+                Value::Tuple(vec![Value::String("G1000".to_string()), Value::U32(2)]),
+                Value::Tuple(vec![Value::String("G1001".to_string()), Value::U32(1)]),
+            ]),
+            Value::Option(None),
+        ])
+    }
+
+    #[test]
+    fn value_to_and_from_wave() {
+        let wave = value_to_wave(&order(), &order_type()).unwrap();
+        assert_eq!(
+            wave,
+            r#"{id: "order-1", items: [("G1000", 2), ("G1001", 1)]}"#
+        );
+        assert_eq!(value_from_wave(&wave, &order_type()).unwrap(), order());
+    }
+
+    #[test]
+    fn value_to_wave_with_wrong_type() {
+        assert!(value_to_wave(&Value::List(vec![]), &AnalysedType::Str(TypeStr)).is_err());
+        assert!(value_from_wave("1", &AnalysedType::Str(TypeStr)).is_err());
+    }
+
+    #[test]
+    fn pretty_print() {
+        let options = WavePrettyOptions {
+            indent: 4,
+            max_width: 30,
+        };
+        let wave = value_to_wave_pretty(&order(), &order_type(), &options).unwrap();
+        assert_eq!(
+            wave,
+            r#"{
+    id: "order-1",
+    items: [
+        ("G1000", 2),
+        ("G1001", 1)
+    ]
+}"#
+        );
+        assert_eq!(value_from_wave(&wave, &order_type()).unwrap(), order());
+
+        // values fitting into the line width are not broken
+        let wave =
+            value_to_wave_pretty(&order(), &order_type(), &WavePrettyOptions::default()).unwrap();
+        assert_eq!(wave, value_to_wave(&order(), &order_type()).unwrap());
+    }
+
+    #[test]
+    fn pretty_print_wrapped_values() {
+        let typ = AnalysedType::Tuple(TypeTuple {
+            items: vec![
+                AnalysedType::Option(TypeOption {
+                    inner: Box::new(AnalysedType::List(TypeList {
+                        inner: Box::new(AnalysedType::U8(TypeU8)),
+                    })),
+                }),
+                AnalysedType::Result(TypeResult {
+                    ok: None,
+                    err: Some(Box::new(AnalysedType::Str(TypeStr))),
+                }),
+                AnalysedType::Flags(TypeFlags {
+                    names: vec!["read".to_string(), "write".to_string()],
+                }),
+                AnalysedType::Variant(TypeVariant {
+                    cases: vec![NameOptionTypePair {
+                        name: "none".to_string(),
+                        typ: Some(AnalysedType::Bool(TypeBool)),
+                    }],
+                }),
+            ],
+        });
+        let value = Value::Tuple(vec![
+            Value::Option(Some(Box::new(Value::List(vec![
+                Value::U8(1),
+                Value::U8(2),
+            ])))),
+            Value::Result(Ok(None)),
+            Value::Flags(vec![true, true]),
+            Value::Variant {
+                case_idx: 0,
+                case_value: Some(Box::new(Value::Bool(true))),
+            },
+        ]);
+
+        let wave = value_to_wave_pretty(&value, &typ, &WavePrettyOptions::default()).unwrap();
+        assert_eq!(wave, value_to_wave(&value, &typ).unwrap());
+
+        let options = WavePrettyOptions {
+            indent: 2,
+            max_width: 12,
+        };
+        let wave = value_to_wave_pretty(&value, &typ, &options).unwrap();
+        assert_eq!(
+            wave,
+            r#"(
+  some([
+    1,
+    2
+  ]),
+  ok,
+  {
+    read,
+    write
+  },
+  %none(true)
+)"#
+        );
+        assert_eq!(value_from_wave(&wave, &typ).unwrap(), value);
+    }
 }
@@ -0,0 +1,1344 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, FuturesOrdered};
+use futures::{Stream, StreamExt};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use wasmtime::component::Resource;
+use wasmtime_wasi::{ResourceTable, WasiCtx, WasiView};
+
+use crate::golem::rpc::types::{
+    HostFutureBatchInvokeResult, HostFutureInvokeResult, HostInvokeResultStream,
+};
+use crate::{
+    BatchInvocation, BatchOptions, FutureBatchInvokeResultEntry, FutureInvokeResultEntry, Host,
+    HostWasmRpc, InvocationOptions, InvokeResultStreamEntry, Pollable, RetryPolicy, RpcError,
+    SubscribeAny, SubscribeStream, Uri, WasmRpcEntry, WitValue,
+};
+
+/// Delivers wasm-rpc calls to the worker identified by a `Uri` and returns their results.
+///
+/// Function names and results follow the conventions of the generated stubs: functions are
+/// named like `golem:it/api.{cart.add-item}` and results are returned as a tuple.
+#[async_trait]
+pub trait RpcTransport: Send + Sync + 'static {
+    async fn invoke(
+        &self,
+        uri: &Uri,
+        function_name: &str,
+        params: Vec<WitValue>,
+    ) -> Result<WitValue, RpcError>;
+
+    /// Invokes a function with the given options. Retries and timeouts are handled by the
+    /// caller, while the transport is responsible for delivering the idempotency key and the
+    /// headers. By default the options are ignored.
+    async fn invoke_with_options(
+        &self,
+        uri: &Uri,
+        function_name: &str,
+        params: Vec<WitValue>,
+        _options: &InvocationOptions,
+    ) -> Result<WitValue, RpcError> {
+        self.invoke(uri, function_name, params).await
+    }
+
+    /// Invokes a function producing its result in parts. Retries and timeouts do not apply to
+    /// streams. By default the result of `invoke_with_options` is the only part.
+    fn stream_invoke(
+        self: Arc<Self>,
+        uri: Uri,
+        function_name: String,
+        params: Vec<WitValue>,
+        options: InvocationOptions,
+    ) -> BoxStream<'static, Result<WitValue, RpcError>> {
+        stream::once(async move {
+            self.invoke_with_options(&uri, &function_name, params, &options)
+                .await
+        })
+        .boxed()
+    }
+
+    /// Performs several invocations on the same worker as configured by `batch_options`,
+    /// returning one result per performed invocation. Every invocation uses `options`.
+    ///
+    /// By default the invocations are delivered separately through `invoke_with_options`, with
+    /// the retries and timeout of `options` applied to each of them. Transports delivering the
+    /// batch at once bound the whole batch by the timeout and do not retry it.
+    async fn invoke_batch(
+        self: Arc<Self>,
+        uri: Uri,
+        invocations: Vec<BatchInvocation>,
+        options: InvocationOptions,
+        batch_options: BatchOptions,
+    ) -> Vec<Result<WitValue, RpcError>> {
+        invoke_separately(self, uri, invocations, options, batch_options).await
+    }
+}
+
+/// Store data implementing the wasm-rpc host interface on top of an `RpcTransport`, wrapping the
+/// embedder's own store data.
+///
+/// The WASI implementation is delegated to `data`, so `wasmtime_wasi::add_to_linker_async` and
+/// `crate::golem::rpc::types::add_to_linker` can both be used with a `Linker<WasmRpcCtx<D, R>>`.
+pub struct WasmRpcCtx<D, R> {
+    pub data: D,
+    pub transport: Arc<R>,
+    /// The options of the wasm-rpc resources created by the component
+    pub default_options: InvocationOptions,
+}
+
+impl<D, R> WasmRpcCtx<D, R> {
+    pub fn new(data: D, transport: R) -> Self {
+        Self {
+            data,
+            transport: Arc::new(transport),
+            default_options: InvocationOptions::default(),
+        }
+    }
+}
+
+impl<D: WasiView, R: Send + Sync> WasiView for WasmRpcCtx<D, R> {
+    fn table(&mut self) -> &mut ResourceTable {
+        self.data.table()
+    }
+
+    fn ctx(&mut self) -> &mut WasiCtx {
+        self.data.ctx()
+    }
+}
+
+type InvokeResult = Result<WitValue, RpcError>;
+
+/// The payload of `FutureInvokeResultEntry` and `FutureBatchInvokeResultEntry` for calls made
+/// through an `RpcTransport`.
+///
+/// The invocation runs in its own task from the creation of the payload, so it makes progress
+/// while the guest does other work, and is aborted when cancelled or dropped.
+struct TransportFutureInvokeResult<T> {
+    // only locked to make the payload Sync
+    state: Mutex<TransportFutureInvokeResultState<T>>,
+}
+
+enum TransportFutureInvokeResultState<T> {
+    Pending {
+        task: JoinHandle<T>,
+        /// The result if the invocation gets cancelled
+        cancelled: T,
+    },
+    Done(T),
+}
+
+impl<T: Clone + Send + 'static> TransportFutureInvokeResult<T> {
+    fn new(future: impl Future<Output = T> + Send + 'static, cancelled: T) -> Self {
+        Self {
+            state: Mutex::new(TransportFutureInvokeResultState::Pending {
+                task: tokio::spawn(future),
+                cancelled,
+            }),
+        }
+    }
+
+    /// Returns the result if the call has already finished, without waiting for it
+    fn try_get(&mut self) -> Option<T> {
+        let state = self.state.get_mut().unwrap();
+        if let TransportFutureInvokeResultState::Pending { task, cancelled } = state {
+            match Pin::new(task).poll(&mut Context::from_waker(Waker::noop())) {
+                Poll::Ready(result) => {
+                    *state = TransportFutureInvokeResultState::Done(task_result(result, cancelled))
+                }
+                Poll::Pending => return None,
+            }
+        }
+        match state {
+            TransportFutureInvokeResultState::Done(result) => Some(result.clone()),
+            TransportFutureInvokeResultState::Pending { .. } => None,
+        }
+    }
+}
+
+/// The result of a finished invocation task, which is only cancelled by `cancel`
+fn task_result<T: Clone>(result: Result<T, tokio::task::JoinError>, cancelled: &T) -> T {
+    match result {
+        Ok(result) => result,
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(_) => cancelled.clone(),
+    }
+}
+
+impl<T> Drop for TransportFutureInvokeResult<T> {
+    fn drop(&mut self) {
+        if let TransportFutureInvokeResultState::Pending { task, .. } =
+            self.state.get_mut().unwrap()
+        {
+            task.abort();
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Clone + Send + 'static> SubscribeAny for TransportFutureInvokeResult<T> {
+    async fn ready(&mut self) {
+        let state = self.state.get_mut().unwrap();
+        if let TransportFutureInvokeResultState::Pending { task, cancelled } = state {
+            let result = task_result(task.await, cancelled);
+            *state = TransportFutureInvokeResultState::Done(result);
+        }
+    }
+
+    /// Aborts the invocation's task if it has not finished yet, replacing its result with the
+    /// cancelled one
+    fn cancel(&mut self) {
+        let state = self.state.get_mut().unwrap();
+        if let TransportFutureInvokeResultState::Pending { task, cancelled } = state {
+            task.abort();
+            *state = TransportFutureInvokeResultState::Done(cancelled.clone());
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// The payload of `InvokeResultStreamEntry` for calls made through an `RpcTransport`
+struct TransportInvokeResultStream {
+    // only locked to make the payload Sync
+    stream: Mutex<BoxStream<'static, InvokeResult>>,
+    /// The part received by `ready`, not yet returned by `get-next`
+    next: Option<InvokeResult>,
+    ended: bool,
+}
+
+impl TransportInvokeResultStream {
+    fn new(stream: BoxStream<'static, InvokeResult>) -> Self {
+        Self {
+            stream: Mutex::new(stream),
+            next: None,
+            ended: false,
+        }
+    }
+
+    /// Returns the next part if it is already available, without waiting for it
+    fn try_next(&mut self) -> Option<InvokeResult> {
+        if let Some(next) = self.next.take() {
+            return Some(next);
+        }
+        if self.ended {
+            return None;
+        }
+        match self
+            .stream
+            .get_mut()
+            .unwrap()
+            .poll_next_unpin(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(Some(next)) => Some(next),
+            Poll::Ready(None) => {
+                self.ended = true;
+                None
+            }
+            Poll::Pending => None,
+        }
+    }
+}
+
+#[async_trait]
+impl SubscribeStream for TransportInvokeResultStream {
+    async fn ready(&mut self) {
+        if self.next.is_none() && !self.ended {
+            self.next = self.stream.get_mut().unwrap().next().await;
+            self.ended = self.next.is_none();
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// The result of a cancelled invocation
+fn cancelled(function_name: &str) -> InvokeResult {
+    Err(RpcError::Timeout(format!(
+        "Invocation of {function_name} was cancelled"
+    )))
+}
+
+/// Fails the invocation with a `timeout` error if it does not finish within `timeout_nanos`
+/// after `started`
+async fn with_timeout(
+    future: impl Future<Output = InvokeResult>,
+    function_name: &str,
+    started: Instant,
+    timeout_nanos: u64,
+) -> InvokeResult {
+    let timeout = Duration::from_nanos(timeout_nanos);
+    tokio::time::timeout_at(started + timeout, future)
+        .await
+        .unwrap_or_else(|_| {
+            Err(RpcError::Timeout(format!(
+                "Invocation of {function_name} did not finish in {timeout:?}"
+            )))
+        })
+}
+
+/// Performs an invocation, retrying it as configured by `options`.
+///
+/// The timeout of the first attempt runs from `started`, or from the first poll if it is not
+/// given, and the timeout of each retry from the start of the retry.
+async fn invoke_with_retries<R: RpcTransport + ?Sized>(
+    transport: Arc<R>,
+    uri: Uri,
+    function_name: String,
+    params: Vec<WitValue>,
+    options: InvocationOptions,
+    started: Option<Instant>,
+) -> InvokeResult {
+    let mut started = started.unwrap_or_else(Instant::now);
+    let mut attempt = 1;
+    loop {
+        let future = transport.invoke_with_options(&uri, &function_name, params.clone(), &options);
+        let result = match options.timeout_nanos {
+            Some(timeout_nanos) => {
+                with_timeout(future, &function_name, started, timeout_nanos).await
+            }
+            None => future.await,
+        };
+        match (result, &options.retry_policy) {
+            (Err(RpcError::RemoteInternalError(_) | RpcError::Timeout(_)), Some(policy))
+                if attempt < policy.max_attempts =>
+            {
+                tokio::time::sleep(retry_delay(policy, attempt)).await;
+                started = Instant::now();
+                attempt += 1;
+            }
+            (result, _) => return result,
+        }
+    }
+}
+
+/// Performs the invocations of a batch one by one as configured by `batch_options`
+async fn invoke_separately<R: RpcTransport + ?Sized>(
+    transport: Arc<R>,
+    uri: Uri,
+    invocations: Vec<BatchInvocation>,
+    options: InvocationOptions,
+    batch_options: BatchOptions,
+) -> Vec<InvokeResult> {
+    // the timeouts run from the start of each invocation, which are sequential by default
+    let invocations = invocations.into_iter().map(|invocation| {
+        invoke_with_retries(
+            transport.clone(),
+            uri.clone(),
+            invocation.function_name,
+            invocation.function_params,
+            options.clone(),
+            None,
+        )
+    });
+    let mut results: Pin<Box<dyn Stream<Item = InvokeResult> + Send>> = if batch_options.parallel {
+        Box::pin(invocations.collect::<FuturesOrdered<_>>())
+    } else {
+        Box::pin(stream::iter(invocations).then(|invocation| invocation))
+    };
+    let mut batch_results = Vec::new();
+    while let Some(result) = results.next().await {
+        let failed = result.is_err();
+        batch_results.push(result);
+        if failed && batch_options.stop_on_first_error {
+            break;
+        }
+    }
+    batch_results
+}
+
+/// The delay before retrying an invocation for the `attempt`th time
+fn retry_delay(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let delay = policy.min_delay_nanos as f64 * policy.multiplier.powi(attempt as i32 - 1);
+    Duration::from_nanos(delay.min(policy.max_delay_nanos as f64) as u64)
+}
+
+/// Combines the options of a wasm-rpc resource with the options of an invocation, the latter
+/// taking precedence. The headers of the invocation replace the headers of the same name.
+fn merge_options(defaults: &InvocationOptions, options: InvocationOptions) -> InvocationOptions {
+    let mut headers: Vec<(String, String)> = defaults
+        .headers
+        .iter()
+        .filter(|(name, _)| {
+            !options
+                .headers
+                .iter()
+                .any(|(other, _)| other.eq_ignore_ascii_case(name))
+        })
+        .cloned()
+        .collect();
+    headers.extend(options.headers);
+    InvocationOptions {
+        idempotency_key: options
+            .idempotency_key
+            .or_else(|| defaults.idempotency_key.clone()),
+        retry_policy: options.retry_policy.or(defaults.retry_policy),
+        headers,
+        timeout_nanos: options.timeout_nanos.or(defaults.timeout_nanos),
+    }
+}
+
+impl<D: WasiView, R: RpcTransport> WasmRpcCtx<D, R> {
+    /// The worker targeted by a wasm-rpc resource, and the options of the resource
+    fn target(
+        &mut self,
+        resource: &Resource<WasmRpcEntry>,
+    ) -> wasmtime::Result<(Uri, InvocationOptions)> {
+        let entry = self.table().get(resource)?;
+        let uri = entry
+            .payload
+            .downcast_ref::<Uri>()
+            .cloned()
+            .ok_or_else(|| {
+                wasmtime::Error::msg("wasm-rpc resource was not created by WasmRpcCtx")
+            })?;
+        Ok((uri, entry.options.clone()))
+    }
+
+    /// Creates the future performing an invocation through a wasm-rpc resource, with the
+    /// options returned by `options` for the options of the resource. Its timeout runs from now,
+    /// not from the first poll of the future.
+    fn invocation(
+        &mut self,
+        resource: &Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        options: impl FnOnce(&InvocationOptions) -> InvocationOptions,
+    ) -> wasmtime::Result<impl Future<Output = InvokeResult> + Send + 'static> {
+        let (uri, defaults) = self.target(resource)?;
+        let options = options(&defaults);
+        Ok(invoke_with_retries(
+            self.transport.clone(),
+            uri,
+            function_name,
+            function_params,
+            options,
+            Some(Instant::now()),
+        ))
+    }
+
+    fn batch_invocation(
+        &mut self,
+        resource: &Resource<WasmRpcEntry>,
+        invocations: Vec<BatchInvocation>,
+        options: BatchOptions,
+    ) -> wasmtime::Result<impl Future<Output = Vec<InvokeResult>> + Send + 'static> {
+        let (uri, defaults) = self.target(resource)?;
+        Ok(self
+            .transport
+            .clone()
+            .invoke_batch(uri, invocations, defaults, options))
+    }
+
+    fn push_future_invoke_result(
+        &mut self,
+        function_name: &str,
+        future: impl Future<Output = InvokeResult> + Send + 'static,
+    ) -> wasmtime::Result<Resource<FutureInvokeResultEntry>> {
+        let entry = FutureInvokeResultEntry {
+            payload: Box::new(TransportFutureInvokeResult::new(
+                future,
+                cancelled(function_name),
+            )),
+        };
+        Ok(self.table().push(entry)?)
+    }
+}
+
+#[async_trait]
+impl<D: WasiView, R: RpcTransport> HostWasmRpc for WasmRpcCtx<D, R> {
+    async fn new(&mut self, location: Uri) -> wasmtime::Result<Resource<WasmRpcEntry>> {
+        let entry = WasmRpcEntry {
+            payload: Box::new(location),
+            options: self.default_options.clone(),
+        };
+        Ok(self.table().push(entry)?)
+    }
+
+    async fn invoke_and_await(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> wasmtime::Result<Result<WitValue, RpcError>> {
+        let invocation = self.invocation(&self_, function_name, function_params, Clone::clone)?;
+        Ok(invocation.await)
+    }
+
+    /// Waits for the invocation to finish like `invoke_and_await`, but ignores its result
+    async fn invoke(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> wasmtime::Result<Result<(), RpcError>> {
+        let invocation = self.invocation(&self_, function_name, function_params, Clone::clone)?;
+        Ok(invocation.await.map(|_| ()))
+    }
+
+    async fn async_invoke_and_await(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> wasmtime::Result<Resource<FutureInvokeResultEntry>> {
+        let invocation =
+            self.invocation(&self_, function_name.clone(), function_params, Clone::clone)?;
+        self.push_future_invoke_result(&function_name, invocation)
+    }
+
+    async fn invoke_and_await_with_timeout(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_nanos: u64,
+    ) -> wasmtime::Result<Result<WitValue, RpcError>> {
+        let invocation = self.invocation(&self_, function_name, function_params, |options| {
+            InvocationOptions {
+                timeout_nanos: Some(timeout_nanos),
+                ..options.clone()
+            }
+        })?;
+        Ok(invocation.await)
+    }
+
+    async fn async_invoke_and_await_with_timeout(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_nanos: u64,
+    ) -> wasmtime::Result<Resource<FutureInvokeResultEntry>> {
+        let invocation =
+            self.invocation(&self_, function_name.clone(), function_params, |options| {
+                InvocationOptions {
+                    timeout_nanos: Some(timeout_nanos),
+                    ..options.clone()
+                }
+            })?;
+        self.push_future_invoke_result(&function_name, invocation)
+    }
+
+    async fn invoke_and_await_with_options(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        options: InvocationOptions,
+    ) -> wasmtime::Result<Result<WitValue, RpcError>> {
+        let invocation = self.invocation(&self_, function_name, function_params, |defaults| {
+            merge_options(defaults, options)
+        })?;
+        Ok(invocation.await)
+    }
+
+    /// Waits for the invocation to finish like `invoke_and_await`, but ignores its result
+    async fn invoke_with_options(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        options: InvocationOptions,
+    ) -> wasmtime::Result<Result<(), RpcError>> {
+        let invocation = self.invocation(&self_, function_name, function_params, |defaults| {
+            merge_options(defaults, options)
+        })?;
+        Ok(invocation.await.map(|_| ()))
+    }
+
+    async fn async_invoke_and_await_with_options(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        options: InvocationOptions,
+    ) -> wasmtime::Result<Resource<FutureInvokeResultEntry>> {
+        let invocation =
+            self.invocation(&self_, function_name.clone(), function_params, |defaults| {
+                merge_options(defaults, options)
+            })?;
+        self.push_future_invoke_result(&function_name, invocation)
+    }
+
+    async fn invoke_batch(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        invocations: Vec<BatchInvocation>,
+        options: BatchOptions,
+    ) -> wasmtime::Result<Vec<Result<WitValue, RpcError>>> {
+        let batch = self.batch_invocation(&self_, invocations, options)?;
+        Ok(batch.await)
+    }
+
+    async fn async_invoke_batch(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        invocations: Vec<BatchInvocation>,
+        options: BatchOptions,
+    ) -> wasmtime::Result<Resource<FutureBatchInvokeResultEntry>> {
+        let cancelled_results = invocations
+            .iter()
+            .map(|invocation| cancelled(&invocation.function_name))
+            .collect();
+        let batch = self.batch_invocation(&self_, invocations, options)?;
+        let entry = FutureBatchInvokeResultEntry {
+            payload: Box::new(TransportFutureInvokeResult::new(batch, cancelled_results)),
+        };
+        Ok(self.table().push(entry)?)
+    }
+
+    async fn stream_invoke(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> wasmtime::Result<Resource<InvokeResultStreamEntry>> {
+        let (uri, options) = self.target(&self_)?;
+        let stream =
+            self.transport
+                .clone()
+                .stream_invoke(uri, function_name, function_params, options);
+        let entry = InvokeResultStreamEntry {
+            payload: Box::new(TransportInvokeResultStream::new(stream)),
+        };
+        Ok(self.table().push(entry)?)
+    }
+
+    fn drop(&mut self, rep: Resource<WasmRpcEntry>) -> wasmtime::Result<()> {
+        self.table().delete(rep)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<D: WasiView, R: RpcTransport> HostFutureInvokeResult for WasmRpcCtx<D, R> {
+    async fn subscribe(
+        &mut self,
+        self_: Resource<FutureInvokeResultEntry>,
+    ) -> wasmtime::Result<Resource<Pollable>> {
+        wasmtime_wasi::subscribe(self.table(), self_)
+    }
+
+    async fn get(
+        &mut self,
+        self_: Resource<FutureInvokeResultEntry>,
+    ) -> wasmtime::Result<Option<Result<WitValue, RpcError>>> {
+        let entry = self.table().get_mut(&self_)?;
+        let payload = entry
+            .payload
+            .as_any_mut()
+            .downcast_mut::<TransportFutureInvokeResult<InvokeResult>>()
+            .ok_or_else(|| {
+                wasmtime::Error::msg("future-invoke-result was not created by WasmRpcCtx")
+            })?;
+        Ok(payload.try_get())
+    }
+
+    async fn cancel(&mut self, self_: Resource<FutureInvokeResultEntry>) -> wasmtime::Result<()> {
+        let entry = self.table().get_mut(&self_)?;
+        entry.payload.cancel();
+        Ok(())
+    }
+
+    fn drop(&mut self, rep: Resource<FutureInvokeResultEntry>) -> wasmtime::Result<()> {
+        self.table().delete(rep)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<D: WasiView, R: RpcTransport> HostFutureBatchInvokeResult for WasmRpcCtx<D, R> {
+    async fn subscribe(
+        &mut self,
+        self_: Resource<FutureBatchInvokeResultEntry>,
+    ) -> wasmtime::Result<Resource<Pollable>> {
+        wasmtime_wasi::subscribe(self.table(), self_)
+    }
+
+    async fn get(
+        &mut self,
+        self_: Resource<FutureBatchInvokeResultEntry>,
+    ) -> wasmtime::Result<Option<Vec<Result<WitValue, RpcError>>>> {
+        let entry = self.table().get_mut(&self_)?;
+        let payload = entry
+            .payload
+            .as_any_mut()
+            .downcast_mut::<TransportFutureInvokeResult<Vec<InvokeResult>>>()
+            .ok_or_else(|| {
+                wasmtime::Error::msg("future-batch-invoke-result was not created by WasmRpcCtx")
+            })?;
+        Ok(payload.try_get())
+    }
+
+    async fn cancel(
+        &mut self,
+        self_: Resource<FutureBatchInvokeResultEntry>,
+    ) -> wasmtime::Result<()> {
+        let entry = self.table().get_mut(&self_)?;
+        entry.payload.cancel();
+        Ok(())
+    }
+
+    fn drop(&mut self, rep: Resource<FutureBatchInvokeResultEntry>) -> wasmtime::Result<()> {
+        self.table().delete(rep)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<D: WasiView, R: RpcTransport> HostInvokeResultStream for WasmRpcCtx<D, R> {
+    async fn subscribe(
+        &mut self,
+        self_: Resource<InvokeResultStreamEntry>,
+    ) -> wasmtime::Result<Resource<Pollable>> {
+        wasmtime_wasi::subscribe(self.table(), self_)
+    }
+
+    async fn get_next(
+        &mut self,
+        self_: Resource<InvokeResultStreamEntry>,
+    ) -> wasmtime::Result<Option<Result<WitValue, RpcError>>> {
+        let entry = self.table().get_mut(&self_)?;
+        let payload = entry
+            .payload
+            .as_any_mut()
+            .downcast_mut::<TransportInvokeResultStream>()
+            .ok_or_else(|| {
+                wasmtime::Error::msg("invoke-result-stream was not created by WasmRpcCtx")
+            })?;
+        Ok(payload.try_next())
+    }
+
+    fn drop(&mut self, rep: Resource<InvokeResultStreamEntry>) -> wasmtime::Result<()> {
+        self.table().delete(rep)?;
+        Ok(())
+    }
+}
+
+impl<D: WasiView, R: RpcTransport> Host for WasmRpcCtx<D, R> {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use futures::stream::{self, BoxStream};
+    use futures::StreamExt;
+    use tokio::sync::{mpsc, oneshot};
+    use wasmtime::component::Resource;
+    use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
+
+    use crate::golem::rpc::types::{HostFutureInvokeResult, HostInvokeResultStream};
+    use crate::transport::{RpcTransport, WasmRpcCtx};
+    use crate::{
+        BatchInvocation, BatchOptions, HostWasmRpc, InvocationOptions, RetryPolicy, RpcError, Uri,
+        Value, WitValue,
+    };
+
+    struct TestData {
+        table: ResourceTable,
+        ctx: WasiCtx,
+    }
+
+    impl WasiView for TestData {
+        fn table(&mut self) -> &mut ResourceTable {
+            &mut self.table
+        }
+
+        fn ctx(&mut self) -> &mut WasiCtx {
+            &mut self.ctx
+        }
+    }
+
+    /// Echoes the target and the parameters of `echo` calls, after `release` has been received
+    struct EchoTransport {
+        release: tokio::sync::Mutex<Option<oneshot::Receiver<()>>>,
+    }
+
+    #[async_trait]
+    impl RpcTransport for EchoTransport {
+        async fn invoke(
+            &self,
+            uri: &Uri,
+            function_name: &str,
+            params: Vec<WitValue>,
+        ) -> Result<WitValue, RpcError> {
+            let release = self.release.lock().await.take();
+            if let Some(release) = release {
+                release.await.unwrap();
+            }
+            if function_name != "echo" {
+                return Err(RpcError::NotFound(function_name.to_string()));
+            }
+            let mut values = vec![Value::String(uri.value.clone())];
+            values.extend(params.into_iter().map(Value::from));
+            Ok(Value::Tuple(values).into())
+        }
+    }
+
+    fn ctx(release: Option<oneshot::Receiver<()>>) -> WasmRpcCtx<TestData, EchoTransport> {
+        WasmRpcCtx::new(
+            TestData {
+                table: ResourceTable::new(),
+                ctx: WasiCtxBuilder::new().build(),
+            },
+            EchoTransport {
+                release: tokio::sync::Mutex::new(release),
+            },
+        )
+    }
+
+    fn target() -> Uri {
+        Uri {
+            value: "urn:worker:echo/1".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn invoke_through_transport() {
+        let mut ctx = ctx(None);
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let result = ctx
+            .invoke_and_await(
+                Resource::new_borrow(rpc.rep()),
+                "echo".to_string(),
+                vec![Value::U8(1).into()],
+            )
+            .await
+            .unwrap()
+            .map(Value::from)
+            .map_err(|err| format!("{err:?}"));
+        assert_eq!(
+            result,
+            Ok(Value::Tuple(vec![
+                Value::String("urn:worker:echo/1".to_string()),
+                Value::U8(1)
+            ]))
+        );
+
+        let result = ctx
+            .invoke(Resource::new_borrow(rpc.rep()), "other".to_string(), vec![])
+            .await
+            .unwrap();
+        assert!(matches!(result, Err(RpcError::NotFound(name)) if name == "other"));
+
+        HostWasmRpc::drop(&mut ctx, rpc).unwrap();
+    }
+
+    #[tokio::test]
+    async fn async_invoke_through_transport() {
+        let (release, released) = oneshot::channel();
+        let mut ctx = ctx(Some(released));
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let future = ctx
+            .async_invoke_and_await(rpc, "echo".to_string(), vec![])
+            .await
+            .unwrap();
+        let result = ctx.get(Resource::new_borrow(future.rep())).await.unwrap();
+        assert!(result.is_none());
+
+        release.send(()).unwrap();
+        ctx.table().get_mut(&future).unwrap().payload.ready().await;
+        let result = ctx
+            .get(Resource::new_borrow(future.rep()))
+            .await
+            .unwrap()
+            .map(|result| result.map(Value::from).map_err(|err| format!("{err:?}")));
+        assert_eq!(
+            result,
+            Some(Ok(Value::Tuple(vec![Value::String(
+                "urn:worker:echo/1".to_string()
+            )])))
+        );
+
+        HostFutureInvokeResult::drop(&mut ctx, future).unwrap();
+    }
+
+    fn batch() -> Vec<BatchInvocation> {
+        ["echo", "other", "echo"]
+            .iter()
+            .enumerate()
+            .map(|(idx, function_name)| BatchInvocation {
+                function_name: function_name.to_string(),
+                function_params: vec![Value::U8(idx as u8).into()],
+            })
+            .collect()
+    }
+
+    fn batch_results(results: Vec<Result<WitValue, RpcError>>) -> Vec<Result<Value, String>> {
+        results
+            .into_iter()
+            .map(|result| result.map(Value::from).map_err(|err| format!("{err:?}")))
+            .collect()
+    }
+
+    fn echoed(idx: u8) -> Result<Value, String> {
+        Ok(Value::Tuple(vec![
+            Value::String("urn:worker:echo/1".to_string()),
+            Value::U8(idx),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn batch_invocations() {
+        let mut ctx = ctx(None);
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let results = ctx
+            .invoke_batch(
+                Resource::new_borrow(rpc.rep()),
+                batch(),
+                BatchOptions {
+                    parallel: false,
+                    stop_on_first_error: false,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            batch_results(results),
+            vec![
+                echoed(0),
+                Err("RpcError::NotFound(\"other\")".to_string()),
+                echoed(2)
+            ]
+        );
+
+        let results = ctx
+            .invoke_batch(
+                rpc,
+                batch(),
+                BatchOptions {
+                    parallel: true,
+                    stop_on_first_error: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            batch_results(results),
+            vec![echoed(0), Err("RpcError::NotFound(\"other\")".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn async_batch_invocations() {
+        // not imported by the module, as its methods share their names with HostFutureInvokeResult
+        use crate::golem::rpc::types::HostFutureBatchInvokeResult;
+
+        let (release, released) = oneshot::channel();
+        let mut ctx = ctx(Some(released));
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let future = ctx
+            .async_invoke_batch(
+                rpc,
+                batch(),
+                BatchOptions {
+                    parallel: true,
+                    stop_on_first_error: false,
+                },
+            )
+            .await
+            .unwrap();
+        let result = HostFutureBatchInvokeResult::get(&mut ctx, Resource::new_borrow(future.rep()))
+            .await
+            .unwrap();
+        assert!(result.is_none());
+
+        release.send(()).unwrap();
+        ctx.table().get_mut(&future).unwrap().payload.ready().await;
+        let results =
+            HostFutureBatchInvokeResult::get(&mut ctx, Resource::new_borrow(future.rep()))
+                .await
+                .unwrap()
+                .map(batch_results);
+        assert_eq!(
+            results,
+            Some(vec![
+                echoed(0),
+                Err("RpcError::NotFound(\"other\")".to_string()),
+                echoed(2)
+            ])
+        );
+
+        HostFutureBatchInvokeResult::drop(&mut ctx, future).unwrap();
+    }
+
+    #[tokio::test]
+    async fn invocation_timeouts() {
+        let (_release, released) = oneshot::channel();
+        let mut ctx = ctx(Some(released));
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let result = ctx
+            .invoke_and_await_with_timeout(
+                Resource::new_borrow(rpc.rep()),
+                "echo".to_string(),
+                vec![],
+                1_000_000,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, Err(RpcError::Timeout(_))));
+
+        // the first invocation consumed the release channel, so this one finishes in time
+        let future = ctx
+            .async_invoke_and_await_with_timeout(rpc, "echo".to_string(), vec![], 1_000_000_000)
+            .await
+            .unwrap();
+        ctx.table().get_mut(&future).unwrap().payload.ready().await;
+        let result = ctx.get(Resource::new_borrow(future.rep())).await.unwrap();
+        assert!(matches!(result, Some(Ok(_))));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn async_invocation_timeouts_start_with_the_call() {
+        let (_release, released) = oneshot::channel();
+        let mut ctx = ctx(Some(released));
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let future = ctx
+            .async_invoke_and_await_with_timeout(rpc, "echo".to_string(), vec![], 1_000_000_000)
+            .await
+            .unwrap();
+        tokio::time::advance(Duration::from_secs(2)).await;
+
+        // the invocation timed out while the guest did other work
+        ctx.table().get_mut(&future).unwrap().payload.ready().await;
+        let result = ctx.get(Resource::new_borrow(future.rep())).await.unwrap();
+        assert!(matches!(result, Some(Err(RpcError::Timeout(_)))));
+    }
+
+    #[tokio::test]
+    async fn async_invocations_run_without_being_polled() {
+        let (_release, released) = oneshot::channel();
+        let mut ctx = ctx(Some(released));
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let _future = ctx
+            .async_invoke_and_await(rpc, "echo".to_string(), vec![])
+            .await
+            .unwrap();
+        // the guest does other work without polling the invocation
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // the invocation has started waiting for the release channel
+        assert!(ctx.transport.release.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn cancel_async_invocation() {
+        let (release, released) = oneshot::channel();
+        let mut ctx = ctx(Some(released));
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let future = ctx
+            .async_invoke_and_await(rpc, "echo".to_string(), vec![])
+            .await
+            .unwrap();
+        ctx.cancel(Resource::new_borrow(future.rep()))
+            .await
+            .unwrap();
+        // would let the invocation finish if it was not cancelled
+        let _ = release.send(());
+
+        ctx.table().get_mut(&future).unwrap().payload.ready().await;
+        let result = ctx.get(Resource::new_borrow(future.rep())).await.unwrap();
+        assert!(matches!(result, Some(Err(RpcError::Timeout(_)))));
+    }
+
+    /// Fails the first `failures` invocations, recording the options of every invocation
+    struct FlakyTransport {
+        failures: u32,
+        invocations: std::sync::Mutex<Vec<InvocationOptions>>,
+    }
+
+    #[async_trait]
+    impl RpcTransport for FlakyTransport {
+        async fn invoke(
+            &self,
+            _uri: &Uri,
+            _function_name: &str,
+            _params: Vec<WitValue>,
+        ) -> Result<WitValue, RpcError> {
+            unreachable!("invocations are made with options")
+        }
+
+        async fn invoke_with_options(
+            &self,
+            _uri: &Uri,
+            _function_name: &str,
+            _params: Vec<WitValue>,
+            options: &InvocationOptions,
+        ) -> Result<WitValue, RpcError> {
+            let mut invocations = self.invocations.lock().unwrap();
+            invocations.push(options.clone());
+            if invocations.len() as u32 <= self.failures {
+                Err(RpcError::RemoteInternalError("unavailable".to_string()))
+            } else {
+                Ok(Value::Tuple(vec![]).into())
+            }
+        }
+    }
+
+    fn flaky_ctx(failures: u32) -> WasmRpcCtx<TestData, FlakyTransport> {
+        WasmRpcCtx::new(
+            TestData {
+                table: ResourceTable::new(),
+                ctx: WasiCtxBuilder::new().build(),
+            },
+            FlakyTransport {
+                failures,
+                invocations: std::sync::Mutex::new(vec![]),
+            },
+        )
+    }
+
+    fn retries(max_attempts: u32) -> InvocationOptions {
+        InvocationOptions {
+            retry_policy: Some(RetryPolicy {
+                max_attempts,
+                min_delay_nanos: 1_000,
+                max_delay_nanos: 10_000,
+                multiplier: 2.0,
+            }),
+            ..InvocationOptions::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_failed_invocations() {
+        let mut ctx = flaky_ctx(2);
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let result = ctx
+            .invoke_and_await_with_options(
+                Resource::new_borrow(rpc.rep()),
+                "f".to_string(),
+                vec![],
+                retries(3),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_ok());
+        assert_eq!(ctx.transport.invocations.lock().unwrap().len(), 3);
+
+        let mut ctx = flaky_ctx(2);
+        let rpc = ctx.new(target()).await.unwrap();
+        let result = ctx
+            .invoke_and_await_with_options(rpc, "f".to_string(), vec![], retries(2))
+            .await
+            .unwrap();
+        assert!(matches!(result, Err(RpcError::RemoteInternalError(_))));
+        assert_eq!(ctx.transport.invocations.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn invocation_options_override_resource_options() {
+        let mut ctx = flaky_ctx(0);
+        ctx.default_options = InvocationOptions {
+            idempotency_key: Some("default-key".to_string()),
+            headers: vec![
+                ("Tenant".to_string(), "tenant-1".to_string()),
+                ("traceparent".to_string(), "trace-1".to_string()),
+            ],
+            ..InvocationOptions::default()
+        };
+        let rpc = ctx.new(target()).await.unwrap();
+
+        ctx.invoke_and_await(Resource::new_borrow(rpc.rep()), "f".to_string(), vec![])
+            .await
+            .unwrap()
+            .unwrap();
+        ctx.invoke_with_options(
+            rpc,
+            "f".to_string(),
+            vec![],
+            InvocationOptions {
+                headers: vec![("tenant".to_string(), "tenant-2".to_string())],
+                timeout_nanos: Some(1_000_000_000),
+                ..InvocationOptions::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let invocations = ctx.transport.invocations.lock().unwrap();
+        assert_eq!(
+            invocations[0].idempotency_key.as_deref(),
+            Some("default-key")
+        );
+        assert_eq!(invocations[0].headers.len(), 2);
+        assert_eq!(
+            invocations[1].idempotency_key.as_deref(),
+            Some("default-key")
+        );
+        assert_eq!(
+            invocations[1].headers,
+            vec![
+                ("traceparent".to_string(), "trace-1".to_string()),
+                ("tenant".to_string(), "tenant-2".to_string())
+            ]
+        );
+        assert_eq!(invocations[1].timeout_nanos, Some(1_000_000_000));
+    }
+
+    #[tokio::test]
+    async fn stream_invoke_through_transport() {
+        let mut ctx = ctx(None);
+        let rpc = ctx.new(target()).await.unwrap();
+
+        let stream = ctx
+            .stream_invoke(rpc, "echo".to_string(), vec![Value::U8(1).into()])
+            .await
+            .unwrap();
+        ctx.table().get_mut(&stream).unwrap().payload.ready().await;
+        let next = ctx
+            .get_next(Resource::new_borrow(stream.rep()))
+            .await
+            .unwrap()
+            .map(|result| result.map(Value::from).map_err(|err| format!("{err:?}")));
+        assert_eq!(next, Some(echoed(1)));
+
+        ctx.table().get_mut(&stream).unwrap().payload.ready().await;
+        let next = ctx
+            .get_next(Resource::new_borrow(stream.rep()))
+            .await
+            .unwrap();
+        assert!(next.is_none());
+
+        HostInvokeResultStream::drop(&mut ctx, stream).unwrap();
+    }
+
+    /// Streams the parts received from `parts`
+    struct PagedTransport {
+        parts: std::sync::Mutex<Option<mpsc::UnboundedReceiver<Result<WitValue, RpcError>>>>,
+    }
+
+    #[async_trait]
+    impl RpcTransport for PagedTransport {
+        async fn invoke(
+            &self,
+            _uri: &Uri,
+            _function_name: &str,
+            _params: Vec<WitValue>,
+        ) -> Result<WitValue, RpcError> {
+            unreachable!("only streaming invocations are made")
+        }
+
+        fn stream_invoke(
+            self: Arc<Self>,
+            _uri: Uri,
+            _function_name: String,
+            _params: Vec<WitValue>,
+            _options: InvocationOptions,
+        ) -> BoxStream<'static, Result<WitValue, RpcError>> {
+            let parts = self.parts.lock().unwrap().take().unwrap();
+            stream::unfold(parts, |mut parts| async move {
+                parts.recv().await.map(|part| (part, parts))
+            })
+            .boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_results_in_parts() {
+        let (parts, received) = mpsc::unbounded_channel();
+        let mut ctx = WasmRpcCtx::new(
+            TestData {
+                table: ResourceTable::new(),
+                ctx: WasiCtxBuilder::new().build(),
+            },
+            PagedTransport {
+                parts: std::sync::Mutex::new(Some(received)),
+            },
+        );
+        let rpc = ctx.new(target()).await.unwrap();
+        let stream = ctx
+            .stream_invoke(rpc, "list".to_string(), vec![])
+            .await
+            .unwrap();
+
+        let next = ctx
+            .get_next(Resource::new_borrow(stream.rep()))
+            .await
+            .unwrap();
+        assert!(next.is_none());
+
+        parts.send(Ok(Value::U32(1).into())).unwrap();
+        ctx.table().get_mut(&stream).unwrap().payload.ready().await;
+        let next = ctx
+            .get_next(Resource::new_borrow(stream.rep()))
+            .await
+            .unwrap();
+        assert_eq!(
+            next.map(|next| next.map(Value::from).map_err(|err| format!("{err:?}"))),
+            Some(Ok(Value::U32(1)))
+        );
+
+        parts
+            .send(Err(RpcError::RemoteInternalError("page lost".to_string())))
+            .unwrap();
+        parts.send(Ok(Value::U32(3).into())).unwrap();
+        drop(parts);
+        let mut rest = vec![];
+        loop {
+            ctx.table().get_mut(&stream).unwrap().payload.ready().await;
+            match ctx
+                .get_next(Resource::new_borrow(stream.rep()))
+                .await
+                .unwrap()
+            {
+                Some(next) => rest.push(next.map(Value::from).map_err(|err| format!("{err:?}"))),
+                None => break,
+            }
+        }
+        assert_eq!(
+            rest,
+            vec![
+                Err("RpcError::RemoteInternalError(\"page lost\")".to_string()),
+                Ok(Value::U32(3))
+            ]
+        );
+
+        HostInvokeResultStream::drop(&mut ctx, stream).unwrap();
+    }
+}
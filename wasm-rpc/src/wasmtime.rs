@@ -12,20 +12,254 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
 use crate::{Uri, Value};
 use async_recursion::async_recursion;
 use async_trait::async_trait;
 use golem_wasm_ast::analysis::{
-    AnalysedType, NameOptionTypePair, NameTypePair, TypeBool, TypeChr, TypeEnum, TypeF32, TypeF64,
-    TypeFlags, TypeList, TypeOption, TypeRecord, TypeResult, TypeS16, TypeS32, TypeS64, TypeS8,
-    TypeStr, TypeTuple, TypeU16, TypeU32, TypeU64, TypeU8, TypeVariant,
+    AnalysedResourceId, AnalysedResourceMode, AnalysedType, NameOptionTypePair, NameTypePair,
+    TypeBool, TypeChr, TypeEnum, TypeF32, TypeF64, TypeFlags, TypeHandle, TypeList, TypeOption,
+    TypeRecord, TypeResult, TypeS16, TypeS32, TypeS64, TypeS8, TypeStr, TypeTuple, TypeU16,
+    TypeU32, TypeU64, TypeU8, TypeVariant,
 };
-use wasmtime::component::{types, ResourceAny, Type, Val};
+use wasmtime::component::{types, ResourceAny, ResourceType, Type, Val};
 
+/// An error converting between `Value` and wasmtime's `Val`
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EncodingError {
-    ParamTypeMismatch { details: String },
-    ValueMismatch { details: String },
-    Unknown { details: String },
+    /// The value is of a different kind than its type
+    TypeMismatch {
+        path: ValuePath,
+        /// The type of the value, rendered as WIT
+        expected: String,
+        /// The kind of the value, such as `record` or `u32`
+        actual: String,
+    },
+    /// The value is of the kind of its type, but does not fit it, like an unknown enum case
+    ValueMismatch {
+        path: ValuePath,
+        /// The type of the value, rendered as WIT
+        expected: String,
+        /// The kind of the value, such as `record` or `u32`
+        actual: String,
+        details: String,
+    },
+}
+
+/// Types that can be rendered as WIT in an `EncodingError`
+trait WitType {
+    fn to_wit(&self) -> String;
+}
+
+impl WitType for Type {
+    fn to_wit(&self) -> String {
+        type_to_wit(self)
+    }
+}
+
+impl WitType for AnalysedType {
+    fn to_wit(&self) -> String {
+        analysed_type_to_wit(self)
+    }
+}
+
+impl EncodingError {
+    fn type_mismatch(expected: &impl WitType, actual: &str) -> Self {
+        EncodingError::TypeMismatch {
+            path: ValuePath::default(),
+            expected: expected.to_wit(),
+            actual: actual.to_string(),
+        }
+    }
+
+    fn value_mismatch(expected: &impl WitType, actual: &str, details: impl Into<String>) -> Self {
+        EncodingError::ValueMismatch {
+            path: ValuePath::default(),
+            expected: expected.to_wit(),
+            actual: actual.to_string(),
+            details: details.into(),
+        }
+    }
+
+    /// Where the error occurred within the converted value
+    pub fn path(&self) -> &ValuePath {
+        match self {
+            EncodingError::TypeMismatch { path, .. }
+            | EncodingError::ValueMismatch { path, .. } => path,
+        }
+    }
+
+    /// Places the error within an enclosing value, for example within the parameter it occurred in
+    pub fn at(mut self, segment: PathSegment) -> Self {
+        match &mut self {
+            EncodingError::TypeMismatch { path, .. }
+            | EncodingError::ValueMismatch { path, .. } => path.segments.insert(0, segment),
+        }
+        self
+    }
+}
+
+impl Display for EncodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.path().segments.is_empty() {
+            write!(f, "{}: ", self.path())?;
+        }
+        match self {
+            EncodingError::TypeMismatch {
+                expected, actual, ..
+            } => write!(f, "expected {expected}, got {actual}"),
+            EncodingError::ValueMismatch {
+                expected,
+                actual,
+                details,
+                ..
+            } => write!(f, "{details} (expected {expected}, got {actual})"),
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+/// A location within a value, rendered like `param 2 .items[3].price`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValuePath {
+    pub segments: Vec<PathSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A parameter of a function, by position
+    Param(usize),
+    /// A result of a function, by position
+    Result(usize),
+    /// A record field
+    Field(String),
+    /// A list element
+    Index(usize),
+    /// A tuple item
+    Item(usize),
+    /// The payload of a variant case
+    Case(String),
+    /// The value of a `some` option
+    Some,
+    /// The value of an `ok` result
+    Ok,
+    /// The value of an `err` result
+    Err,
+}
+
+impl Display for ValuePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, segment) in self.segments.iter().enumerate() {
+            if idx > 0
+                && matches!(
+                    self.segments[idx - 1],
+                    PathSegment::Param(_) | PathSegment::Result(_)
+                )
+            {
+                write!(f, " ")?;
+            }
+            match segment {
+                PathSegment::Param(idx) => write!(f, "param {idx}")?,
+                PathSegment::Result(idx) => write!(f, "result {idx}")?,
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::Index(idx) => write!(f, "[{idx}]")?,
+                PathSegment::Item(idx) => write!(f, ".{idx}")?,
+                PathSegment::Case(name) => write!(f, ".{name}")?,
+                PathSegment::Some => write!(f, ".some")?,
+                PathSegment::Ok => write!(f, ".ok")?,
+                PathSegment::Err => write!(f, ".err")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders a wasmtime type as WIT. Named types are rendered by their structure, as wasmtime does
+/// not know their names.
+pub fn type_to_wit(typ: &Type) -> String {
+    match typ {
+        Type::Bool => "bool".to_string(),
+        Type::S8 => "s8".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::S16 => "s16".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::S32 => "s32".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::S64 => "s64".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::Float32 => "float32".to_string(),
+        Type::Float64 => "float64".to_string(),
+        Type::Char => "char".to_string(),
+        Type::String => "string".to_string(),
+        Type::List(list) => format!("list<{}>", type_to_wit(&list.ty())),
+        Type::Record(record) => {
+            let fields = record
+                .fields()
+                .map(|field| format!("{}: {}", field.name, type_to_wit(&field.ty)))
+                .collect::<Vec<_>>();
+            format!("record {{ {} }}", fields.join(", "))
+        }
+        Type::Tuple(tuple) => {
+            let items = tuple.types().map(|ty| type_to_wit(&ty)).collect::<Vec<_>>();
+            format!("tuple<{}>", items.join(", "))
+        }
+        Type::Variant(variant) => {
+            let cases = variant
+                .cases()
+                .map(|case| match case.ty {
+                    Some(ty) => format!("{}({})", case.name, type_to_wit(&ty)),
+                    None => case.name.to_string(),
+                })
+                .collect::<Vec<_>>();
+            format!("variant {{ {} }}", cases.join(", "))
+        }
+        Type::Enum(enm) => format!("enum {{ {} }}", enm.names().collect::<Vec<_>>().join(", ")),
+        Type::Option(option) => format!("option<{}>", type_to_wit(&option.ty())),
+        Type::Result(result) => match (result.ok(), result.err()) {
+            (Some(ok), Some(err)) => format!("result<{}, {}>", type_to_wit(&ok), type_to_wit(&err)),
+            (Some(ok), None) => format!("result<{}>", type_to_wit(&ok)),
+            (None, Some(err)) => format!("result<_, {}>", type_to_wit(&err)),
+            (None, None) => "result".to_string(),
+        },
+        Type::Flags(flags) => format!(
+            "flags {{ {} }}",
+            flags.names().collect::<Vec<_>>().join(", ")
+        ),
+        Type::Own(_) => "own<resource>".to_string(),
+        Type::Borrow(_) => "borrow<resource>".to_string(),
+    }
+}
+
+/// The kind of a wasmtime value, named like `Value::type_case_name`
+fn val_kind(value: &Val) -> &'static str {
+    match value {
+        Val::Bool(_) => "bool",
+        Val::S8(_) => "s8",
+        Val::U8(_) => "u8",
+        Val::S16(_) => "s16",
+        Val::U16(_) => "u16",
+        Val::S32(_) => "s32",
+        Val::U32(_) => "u32",
+        Val::S64(_) => "s64",
+        Val::U64(_) => "u64",
+        Val::Float32(_) => "f32",
+        Val::Float64(_) => "f64",
+        Val::Char(_) => "char",
+        Val::String(_) => "string",
+        Val::List(_) => "list",
+        Val::Record(_) => "record",
+        Val::Tuple(_) => "tuple",
+        Val::Variant(_, _) => "variant",
+        Val::Enum(_) => "enum",
+        Val::Option(_) => "option",
+        Val::Result(_) => "result",
+        Val::Flags(_) => "flags",
+        Val::Resource(_) => "handle",
+    }
 }
 
 #[async_trait]
@@ -60,88 +294,103 @@ pub async fn decode_param(
     match param_type {
         Type::Bool => match param {
             Value::Bool(bool) => Ok(DecodeParamResult::simple(Val::Bool(*bool))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected bool, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::S8 => match param {
             Value::S8(s8) => Ok(DecodeParamResult::simple(Val::S8(*s8))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected s8, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::U8 => match param {
             Value::U8(u8) => Ok(DecodeParamResult::simple(Val::U8(*u8))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected u8, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::S16 => match param {
             Value::S16(s16) => Ok(DecodeParamResult::simple(Val::S16(*s16))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected s16, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::U16 => match param {
             Value::U16(u16) => Ok(DecodeParamResult::simple(Val::U16(*u16))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected u16, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::S32 => match param {
             Value::S32(s32) => Ok(DecodeParamResult::simple(Val::S32(*s32))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected s32, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::U32 => match param {
             Value::U32(u32) => Ok(DecodeParamResult::simple(Val::U32(*u32))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected u32, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::S64 => match param {
             Value::S64(s64) => Ok(DecodeParamResult::simple(Val::S64(*s64))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected s64, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::U64 => match param {
             Value::U64(u64) => Ok(DecodeParamResult::simple(Val::U64(*u64))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected u64, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Float32 => match param {
             Value::F32(f32) => Ok(DecodeParamResult::simple(Val::Float32(*f32))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected f32, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Float64 => match param {
             Value::F64(f64) => Ok(DecodeParamResult::simple(Val::Float64(*f64))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected f64, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Char => match param {
             Value::Char(char) => Ok(DecodeParamResult::simple(Val::Char(*char))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected char, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::String => match param {
             Value::String(string) => Ok(DecodeParamResult::simple(Val::String(string.clone()))),
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected string, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::List(ty) => match param {
             Value::List(values) => {
                 let mut decoded_values = Vec::new();
                 let mut resource_ids_to_drop = Vec::new();
-                for value in values {
-                    let decoded_param = decode_param(value, &ty.ty(), resource_store).await?;
+                for (idx, value) in values.iter().enumerate() {
+                    let decoded_param = decode_param(value, &ty.ty(), resource_store)
+                        .await
+                        .map_err(|err| err.at(PathSegment::Index(idx)))?;
                     decoded_values.push(decoded_param.val);
                     resource_ids_to_drop.extend(decoded_param.resources_to_drop);
                 }
@@ -150,9 +399,10 @@ pub async fn decode_param(
                     resources_to_drop: resource_ids_to_drop,
                 })
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected list, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Record(ty) => match param {
             Value::Record(values) => {
@@ -160,7 +410,9 @@ pub async fn decode_param(
                 let mut resource_ids_to_drop = Vec::new();
 
                 for (value, field) in values.iter().zip(ty.fields()) {
-                    let decoded_param = decode_param(value, &field.ty, resource_store).await?;
+                    let decoded_param = decode_param(value, &field.ty, resource_store)
+                        .await
+                        .map_err(|err| err.at(PathSegment::Field(field.name.to_string())))?;
                     record_values.push((field.name.to_string(), decoded_param.val));
                     resource_ids_to_drop.extend(decoded_param.resources_to_drop);
                 }
@@ -170,17 +422,20 @@ pub async fn decode_param(
                     resources_to_drop: resource_ids_to_drop,
                 })
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected record, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Tuple(ty) => match param {
             Value::Tuple(values) => {
                 let mut tuple_values = Vec::new();
                 let mut resource_ids_to_drop = Vec::new();
 
-                for (value, ty) in values.iter().zip(ty.types()) {
-                    let decoded_param = decode_param(value, &ty, resource_store).await?;
+                for (idx, (value, ty)) in values.iter().zip(ty.types()).enumerate() {
+                    let decoded_param = decode_param(value, &ty, resource_store)
+                        .await
+                        .map_err(|err| err.at(PathSegment::Item(idx)))?;
                     tuple_values.push(decoded_param.val);
                     resource_ids_to_drop.extend(decoded_param.resources_to_drop);
                 }
@@ -190,9 +445,10 @@ pub async fn decode_param(
                     resources_to_drop: resource_ids_to_drop,
                 })
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected tuple, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Variant(ty) => match param {
             Value::Variant {
@@ -200,16 +456,22 @@ pub async fn decode_param(
                 case_value,
             } => {
                 let cases: Vec<types::Case> = ty.cases().collect();
-                let case = cases
-                    .get(*case_idx as usize)
-                    .ok_or(EncodingError::ValueMismatch {
-                        details: format!("could not get case for discriminant {}", case_idx),
-                    })?;
+                let case = cases.get(*case_idx as usize).ok_or_else(|| {
+                    EncodingError::value_mismatch(
+                        param_type,
+                        param.type_case_name(),
+                        format!("could not get case for discriminant {}", case_idx),
+                    )
+                })?;
                 let name = case.name;
                 match case.ty {
                     Some(ref case_ty) => {
                         let decoded_value = match case_value {
-                            Some(v) => Some(decode_param(v, case_ty, resource_store).await?),
+                            Some(v) => Some(
+                                decode_param(v, case_ty, resource_store)
+                                    .await
+                                    .map_err(|err| err.at(PathSegment::Case(name.to_string())))?,
+                            ),
                             None => None,
                         };
                         match decoded_value {
@@ -227,9 +489,11 @@ pub async fn decode_param(
                         }
                     }
                     None => match case_value {
-                        Some(_) => Err(EncodingError::ValueMismatch {
-                            details: "expected no value for unit variant".to_string(),
-                        }),
+                        Some(_) => Err(EncodingError::value_mismatch(
+                            param_type,
+                            param.type_case_name(),
+                            "expected no value for unit variant".to_string(),
+                        )),
                         None => Ok(DecodeParamResult::simple(Val::Variant(
                             name.to_string(),
                             None,
@@ -237,33 +501,35 @@ pub async fn decode_param(
                     },
                 }
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected variant, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Enum(ty) => match param {
             Value::Enum(discriminant) => {
                 let names: Vec<&str> = ty.names().collect();
-                let name: &str =
-                    names
-                        .get(*discriminant as usize)
-                        .ok_or(EncodingError::ValueMismatch {
-                            details: format!(
-                                "could not get name for discriminant {}",
-                                discriminant
-                            ),
-                        })?;
+                let name: &str = names.get(*discriminant as usize).ok_or_else(|| {
+                    EncodingError::value_mismatch(
+                        param_type,
+                        param.type_case_name(),
+                        format!("could not get name for discriminant {}", discriminant),
+                    )
+                })?;
 
                 Ok(DecodeParamResult::simple(Val::Enum(name.to_string())))
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected enum, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Option(ty) => match param {
             Value::Option(value) => match value {
                 Some(value) => {
-                    let decoded_value = decode_param(value, &ty.ty(), resource_store).await?;
+                    let decoded_value = decode_param(value, &ty.ty(), resource_store)
+                        .await
+                        .map_err(|err| err.at(PathSegment::Some))?;
                     Ok(DecodeParamResult {
                         val: Val::Option(Some(Box::new(decoded_value.val))),
                         resources_to_drop: decoded_value.resources_to_drop,
@@ -271,18 +537,27 @@ pub async fn decode_param(
                 }
                 None => Ok(DecodeParamResult::simple(Val::Option(None))),
             },
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected option, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Result(ty) => match param {
             Value::Result(result) => match result {
                 Ok(value) => {
-                    let ok_ty = ty.ok().ok_or(EncodingError::ValueMismatch {
-                        details: "could not get ok type".to_string(),
+                    let ok_ty = ty.ok().ok_or_else(|| {
+                        EncodingError::value_mismatch(
+                            param_type,
+                            param.type_case_name(),
+                            "could not get ok type".to_string(),
+                        )
                     })?;
                     let decoded_value = match value {
-                        Some(v) => Some(decode_param(v, &ok_ty, resource_store).await?),
+                        Some(v) => Some(
+                            decode_param(v, &ok_ty, resource_store)
+                                .await
+                                .map_err(|err| err.at(PathSegment::Ok))?,
+                        ),
                         None => None,
                     };
                     match decoded_value {
@@ -294,11 +569,19 @@ pub async fn decode_param(
                     }
                 }
                 Err(value) => {
-                    let err_ty = ty.err().ok_or(EncodingError::ValueMismatch {
-                        details: "could not get err type".to_string(),
+                    let err_ty = ty.err().ok_or_else(|| {
+                        EncodingError::value_mismatch(
+                            param_type,
+                            param.type_case_name(),
+                            "could not get err type".to_string(),
+                        )
                     })?;
                     let decoded_value = match value {
-                        Some(v) => Some(decode_param(v, &err_ty, resource_store).await?),
+                        Some(v) => Some(
+                            decode_param(v, &err_ty, resource_store)
+                                .await
+                                .map_err(|err| err.at(PathSegment::Err))?,
+                        ),
                         None => None,
                     };
 
@@ -311,9 +594,10 @@ pub async fn decode_param(
                     }
                 }
             },
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected result, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Flags(ty) => match param {
             Value::Flags(flags) => {
@@ -332,9 +616,10 @@ pub async fn decode_param(
 
                 Ok(DecodeParamResult::simple(Val::Flags(active_flags)))
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected flags, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Own(_) => match param {
             Value::Handle { uri, resource_id } => {
@@ -344,40 +629,48 @@ pub async fn decode_param(
                             val: Val::Resource(resource),
                             resources_to_drop: vec![resource],
                         }),
-                        None => Err(EncodingError::ValueMismatch {
-                            details: "resource not found".to_string(),
-                        }),
+                        None => Err(EncodingError::value_mismatch(
+                            param_type,
+                            param.type_case_name(),
+                            "resource not found".to_string(),
+                        )),
                     }
                 } else {
-                    Err(EncodingError::ValueMismatch {
-                        details: "cannot resolve handle belonging to a different worker"
-                            .to_string(),
-                    })
+                    Err(EncodingError::value_mismatch(
+                        param_type,
+                        param.type_case_name(),
+                        "cannot resolve handle belonging to a different worker".to_string(),
+                    ))
                 }
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected handle, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
         Type::Borrow(_) => match param {
             Value::Handle { uri, resource_id } => {
                 if resource_store.self_uri() == *uri {
                     match resource_store.borrow(*resource_id).await {
                         Some(resource) => Ok(DecodeParamResult::simple(Val::Resource(resource))),
-                        None => Err(EncodingError::ValueMismatch {
-                            details: "resource not found".to_string(),
-                        }),
+                        None => Err(EncodingError::value_mismatch(
+                            param_type,
+                            param.type_case_name(),
+                            "resource not found".to_string(),
+                        )),
                     }
                 } else {
-                    Err(EncodingError::ValueMismatch {
-                        details: "cannot resolve handle belonging to a different worker"
-                            .to_string(),
-                    })
+                    Err(EncodingError::value_mismatch(
+                        param_type,
+                        param.type_case_name(),
+                        "cannot resolve handle belonging to a different worker".to_string(),
+                    ))
                 }
             }
-            _ => Err(EncodingError::ParamTypeMismatch {
-                details: format!("expected handle, got {}", param.type_case_name()),
-            }),
+            _ => Err(EncodingError::type_mismatch(
+                param_type,
+                param.type_case_name(),
+            )),
         },
     }
 }
@@ -389,6 +682,7 @@ pub async fn encode_output(
     typ: &Type,
     resource_store: &mut (impl ResourceStore + Send),
 ) -> Result<Value, EncodingError> {
+    let kind = val_kind(value);
     match value {
         Val::Bool(bool) => Ok(Value::Bool(*bool)),
         Val::S8(i8) => Ok(Value::S8(*i8)),
@@ -406,43 +700,44 @@ pub async fn encode_output(
         Val::List(list) => {
             if let Type::List(list_type) = typ {
                 let mut encoded_values = Vec::new();
-                for value in (*list).iter() {
-                    encoded_values
-                        .push(encode_output(value, &list_type.ty(), resource_store).await?);
+                for (idx, value) in (*list).iter().enumerate() {
+                    encoded_values.push(
+                        encode_output(value, &list_type.ty(), resource_store)
+                            .await
+                            .map_err(|err| err.at(PathSegment::Index(idx)))?,
+                    );
                 }
                 Ok(Value::List(encoded_values))
             } else {
-                Err(EncodingError::ValueMismatch {
-                    details: "Got a List value for non-list result type".to_string(),
-                })
+                Err(EncodingError::type_mismatch(typ, kind))
             }
         }
         Val::Record(record) => {
             if let Type::Record(record_type) = typ {
                 let mut encoded_values = Vec::new();
                 for ((_name, value), field) in record.iter().zip(record_type.fields()) {
-                    let field = encode_output(value, &field.ty, resource_store).await?;
+                    let field = encode_output(value, &field.ty, resource_store)
+                        .await
+                        .map_err(|err| err.at(PathSegment::Field(field.name.to_string())))?;
                     encoded_values.push(field);
                 }
                 Ok(Value::Record(encoded_values))
             } else {
-                Err(EncodingError::ValueMismatch {
-                    details: "Got a Record value for non-record result type".to_string(),
-                })
+                Err(EncodingError::type_mismatch(typ, kind))
             }
         }
         Val::Tuple(tuple) => {
             if let Type::Tuple(tuple_type) = typ {
                 let mut encoded_values = Vec::new();
-                for (v, t) in tuple.iter().zip(tuple_type.types()) {
-                    let value = encode_output(v, &t, resource_store).await?;
+                for (idx, (v, t)) in tuple.iter().zip(tuple_type.types()).enumerate() {
+                    let value = encode_output(v, &t, resource_store)
+                        .await
+                        .map_err(|err| err.at(PathSegment::Item(idx)))?;
                     encoded_values.push(value);
                 }
                 Ok(Value::Tuple(encoded_values))
             } else {
-                Err(EncodingError::ValueMismatch {
-                    details: "Got a Tuple value for non-tuple result type".to_string(),
-                })
+                Err(EncodingError::type_mismatch(typ, kind))
             }
         }
         Val::Variant(name, value) => {
@@ -451,20 +746,29 @@ pub async fn encode_output(
                     .cases()
                     .enumerate()
                     .find(|(_idx, case)| case.name == *name)
-                    .ok_or(EncodingError::ValueMismatch {
-                        details: format!("Could not find case for variant {}", name),
+                    .ok_or_else(|| {
+                        EncodingError::value_mismatch(
+                            typ,
+                            kind,
+                            format!("Could not find case for variant {}", name),
+                        )
                     })?;
 
                 let encoded_output = match value {
                     Some(v) => Some(
                         encode_output(
                             v,
-                            &case.ty.ok_or(EncodingError::ValueMismatch {
-                                details: "Could not get type information for case".to_string(),
+                            &case.ty.ok_or_else(|| {
+                                EncodingError::value_mismatch(
+                                    typ,
+                                    kind,
+                                    "Could not get type information for case".to_string(),
+                                )
                             })?,
                             resource_store,
                         )
-                        .await?,
+                        .await
+                        .map_err(|err| err.at(PathSegment::Case(name.to_string())))?,
                     ),
                     None => None,
                 };
@@ -474,9 +778,7 @@ pub async fn encode_output(
                     case_value: encoded_output.map(Box::new),
                 })
             } else {
-                Err(EncodingError::ValueMismatch {
-                    details: "Got a Variant value for non-variant result type".to_string(),
-                })
+                Err(EncodingError::type_mismatch(typ, kind))
             }
         }
         Val::Enum(name) => {
@@ -485,26 +787,27 @@ pub async fn encode_output(
                     .names()
                     .enumerate()
                     .find(|(_idx, n)| n == name)
-                    .ok_or(EncodingError::ValueMismatch {
-                        details: format!("Could not find discriminant for enum {}", name),
+                    .ok_or_else(|| {
+                        EncodingError::value_mismatch(
+                            typ,
+                            kind,
+                            format!("Could not find discriminant for enum {}", name),
+                        )
                     })?;
                 Ok(Value::Enum(discriminant as u32))
             } else {
-                Err(EncodingError::ValueMismatch {
-                    details: "Got an Enum value for non-enum result type".to_string(),
-                })
+                Err(EncodingError::type_mismatch(typ, kind))
             }
         }
         Val::Option(option) => match option {
             Some(value) => {
                 if let Type::Option(option_type) = typ {
-                    let encoded_output =
-                        encode_output(value, &option_type.ty(), resource_store).await?;
+                    let encoded_output = encode_output(value, &option_type.ty(), resource_store)
+                        .await
+                        .map_err(|err| err.at(PathSegment::Some))?;
                     Ok(Value::Option(Some(Box::new(encoded_output))))
                 } else {
-                    Err(EncodingError::ValueMismatch {
-                        details: "Got an Option value for non-option result type".to_string(),
-                    })
+                    Err(EncodingError::type_mismatch(typ, kind))
                 }
             }
             None => Ok(Value::Option(None)),
@@ -515,11 +818,19 @@ pub async fn encode_output(
                     Ok(value) => {
                         let encoded_output = match value {
                             Some(v) => {
-                                let t = result_type.ok().ok_or(EncodingError::ValueMismatch {
-                                    details: "Could not get ok type for result".to_string(),
+                                let t = result_type.ok().ok_or_else(|| {
+                                    EncodingError::value_mismatch(
+                                        typ,
+                                        kind,
+                                        "Could not get ok type for result".to_string(),
+                                    )
                                 })?;
 
-                                Some(encode_output(v, &t, resource_store).await?)
+                                Some(
+                                    encode_output(v, &t, resource_store)
+                                        .await
+                                        .map_err(|err| err.at(PathSegment::Ok))?,
+                                )
                             }
                             None => None,
                         };
@@ -528,10 +839,18 @@ pub async fn encode_output(
                     Err(value) => {
                         let encoded_output = match value {
                             Some(v) => {
-                                let t = result_type.err().ok_or(EncodingError::ValueMismatch {
-                                    details: "Could not get error type for result".to_string(),
+                                let t = result_type.err().ok_or_else(|| {
+                                    EncodingError::value_mismatch(
+                                        typ,
+                                        kind,
+                                        "Could not get error type for result".to_string(),
+                                    )
                                 })?;
-                                Some(encode_output(v, &t, resource_store).await?)
+                                Some(
+                                    encode_output(v, &t, resource_store)
+                                        .await
+                                        .map_err(|err| err.at(PathSegment::Err))?,
+                                )
                             }
                             None => None,
                         };
@@ -539,9 +858,7 @@ pub async fn encode_output(
                     }
                 }
             } else {
-                Err(EncodingError::ValueMismatch {
-                    details: "Got a Result value for non-result result type".to_string(),
-                })
+                Err(EncodingError::type_mismatch(typ, kind))
             }
         }
         Val::Flags(flags) => {
@@ -556,9 +873,7 @@ pub async fn encode_output(
 
                 Ok(Value::Flags(encoded_value))
             } else {
-                Err(EncodingError::ValueMismatch {
-                    details: "Got a Flags value for non-flags result type".to_string(),
-                })
+                Err(EncodingError::type_mismatch(typ, kind))
             }
         }
         Val::Resource(resource) => {
@@ -571,90 +886,938 @@ pub async fn encode_output(
     }
 }
 
-pub fn type_to_analysed_type(typ: &Type) -> Result<AnalysedType, String> {
+/// Assigns the ids identifying resource types in the handles of `AnalysedType`s
+pub trait ResourceTypeRegistry {
+    /// Returns the id of a resource type, which must be the same every time the type is looked up
+    fn resource_id(&mut self, resource_type: &ResourceType) -> AnalysedResourceId;
+}
+
+/// A `ResourceTypeRegistry` numbering resource types in the order they are first seen.
+///
+/// The ids depend on the order in which the types are looked up, so they are only stable within
+/// one registry: two registries for the same component may number its resource types differently,
+/// and ids must not be compared across them.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceTypes {
+    types: Vec<ResourceType>,
+}
+
+impl ResourceTypes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The resource type an id was assigned to
+    pub fn resource_type(&self, resource_id: AnalysedResourceId) -> Option<ResourceType> {
+        self.types.get(resource_id.0 as usize).copied()
+    }
+}
+
+impl ResourceTypeRegistry for ResourceTypes {
+    fn resource_id(&mut self, resource_type: &ResourceType) -> AnalysedResourceId {
+        // wasmtime's resource types can only be compared, not hashed
+        let idx = match self.types.iter().position(|typ| typ == resource_type) {
+            Some(idx) => idx,
+            None => {
+                self.types.push(*resource_type);
+                self.types.len() - 1
+            }
+        };
+        AnalysedResourceId(idx as u64)
+    }
+}
+
+pub fn type_to_analysed_type(
+    typ: &Type,
+    resources: &mut impl ResourceTypeRegistry,
+) -> AnalysedType {
     match typ {
-        Type::Bool => Ok(AnalysedType::Bool(TypeBool)),
-        Type::S8 => Ok(AnalysedType::S8(TypeS8)),
-        Type::U8 => Ok(AnalysedType::U8(TypeU8)),
-        Type::S16 => Ok(AnalysedType::S16(TypeS16)),
-        Type::U16 => Ok(AnalysedType::U16(TypeU16)),
-        Type::S32 => Ok(AnalysedType::S32(TypeS32)),
-        Type::U32 => Ok(AnalysedType::U32(TypeU32)),
-        Type::S64 => Ok(AnalysedType::S64(TypeS64)),
-        Type::U64 => Ok(AnalysedType::U64(TypeU64)),
-        Type::Float32 => Ok(AnalysedType::F32(TypeF32)),
-        Type::Float64 => Ok(AnalysedType::F64(TypeF64)),
-        Type::Char => Ok(AnalysedType::Chr(TypeChr)),
-        Type::String => Ok(AnalysedType::Str(TypeStr)),
-        Type::List(list) => {
-            let inner = type_to_analysed_type(&list.ty())?;
-            Ok(AnalysedType::List(TypeList {
-                inner: Box::new(inner),
-            }))
-        }
+        Type::Bool => AnalysedType::Bool(TypeBool),
+        Type::S8 => AnalysedType::S8(TypeS8),
+        Type::U8 => AnalysedType::U8(TypeU8),
+        Type::S16 => AnalysedType::S16(TypeS16),
+        Type::U16 => AnalysedType::U16(TypeU16),
+        Type::S32 => AnalysedType::S32(TypeS32),
+        Type::U32 => AnalysedType::U32(TypeU32),
+        Type::S64 => AnalysedType::S64(TypeS64),
+        Type::U64 => AnalysedType::U64(TypeU64),
+        Type::Float32 => AnalysedType::F32(TypeF32),
+        Type::Float64 => AnalysedType::F64(TypeF64),
+        Type::Char => AnalysedType::Chr(TypeChr),
+        Type::String => AnalysedType::Str(TypeStr),
+        Type::List(list) => AnalysedType::List(TypeList {
+            inner: Box::new(type_to_analysed_type(&list.ty(), resources)),
+        }),
         Type::Record(record) => {
             let fields = record
                 .fields()
-                .map(|field| {
-                    type_to_analysed_type(&field.ty).map(|t| NameTypePair {
-                        name: field.name.to_string(),
-                        typ: t,
-                    })
+                .map(|field| NameTypePair {
+                    name: field.name.to_string(),
+                    typ: type_to_analysed_type(&field.ty, resources),
                 })
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(AnalysedType::Record(TypeRecord { fields }))
+                .collect();
+            AnalysedType::Record(TypeRecord { fields })
         }
         Type::Tuple(tuple) => {
             let items = tuple
                 .types()
-                .map(|ty| type_to_analysed_type(&ty))
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(AnalysedType::Tuple(TypeTuple { items }))
+                .map(|ty| type_to_analysed_type(&ty, resources))
+                .collect();
+            AnalysedType::Tuple(TypeTuple { items })
         }
         Type::Variant(variant) => {
             let cases = variant
                 .cases()
-                .map(|case| match case.ty {
-                    Some(ty) => type_to_analysed_type(&ty).map(|t| NameOptionTypePair {
-                        name: case.name.to_string(),
-                        typ: Some(t),
-                    }),
-                    None => Ok(NameOptionTypePair {
-                        name: case.name.to_string(),
-                        typ: None,
-                    }),
+                .map(|case| NameOptionTypePair {
+                    name: case.name.to_string(),
+                    typ: case.ty.map(|ty| type_to_analysed_type(&ty, resources)),
                 })
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(AnalysedType::Variant(TypeVariant { cases }))
+                .collect();
+            AnalysedType::Variant(TypeVariant { cases })
         }
         Type::Enum(enm) => {
             let cases = enm.names().map(|name| name.to_string()).collect();
-            Ok(AnalysedType::Enum(TypeEnum { cases }))
+            AnalysedType::Enum(TypeEnum { cases })
+        }
+        Type::Option(option) => AnalysedType::Option(TypeOption {
+            inner: Box::new(type_to_analysed_type(&option.ty(), resources)),
+        }),
+        Type::Result(result) => {
+            let ok = result
+                .ok()
+                .map(|ty| Box::new(type_to_analysed_type(&ty, resources)));
+            let err = result
+                .err()
+                .map(|ty| Box::new(type_to_analysed_type(&ty, resources)));
+            AnalysedType::Result(TypeResult { ok, err })
+        }
+        Type::Flags(flags) => {
+            let names = flags.names().map(|name| name.to_string()).collect();
+            AnalysedType::Flags(TypeFlags { names })
+        }
+        Type::Own(resource_type) => AnalysedType::Handle(TypeHandle {
+            resource_id: resources.resource_id(resource_type),
+            mode: AnalysedResourceMode::Owned,
+        }),
+        Type::Borrow(resource_type) => AnalysedType::Handle(TypeHandle {
+            resource_id: resources.resource_id(resource_type),
+            mode: AnalysedResourceMode::Borrowed,
+        }),
+    }
+}
+
+/// Renders an analysed type as WIT, like `type_to_wit`
+pub fn analysed_type_to_wit(typ: &AnalysedType) -> String {
+    match typ {
+        AnalysedType::Bool(_) => "bool".to_string(),
+        AnalysedType::S8(_) => "s8".to_string(),
+        AnalysedType::U8(_) => "u8".to_string(),
+        AnalysedType::S16(_) => "s16".to_string(),
+        AnalysedType::U16(_) => "u16".to_string(),
+        AnalysedType::S32(_) => "s32".to_string(),
+        AnalysedType::U32(_) => "u32".to_string(),
+        AnalysedType::S64(_) => "s64".to_string(),
+        AnalysedType::U64(_) => "u64".to_string(),
+        AnalysedType::F32(_) => "float32".to_string(),
+        AnalysedType::F64(_) => "float64".to_string(),
+        AnalysedType::Chr(_) => "char".to_string(),
+        AnalysedType::Str(_) => "string".to_string(),
+        AnalysedType::List(list) => format!("list<{}>", analysed_type_to_wit(&list.inner)),
+        AnalysedType::Record(record) => {
+            let fields = record
+                .fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, analysed_type_to_wit(&field.typ)))
+                .collect::<Vec<_>>();
+            format!("record {{ {} }}", fields.join(", "))
+        }
+        AnalysedType::Tuple(tuple) => {
+            let items = tuple
+                .items
+                .iter()
+                .map(analysed_type_to_wit)
+                .collect::<Vec<_>>();
+            format!("tuple<{}>", items.join(", "))
+        }
+        AnalysedType::Variant(variant) => {
+            let cases = variant
+                .cases
+                .iter()
+                .map(|case| match &case.typ {
+                    Some(typ) => format!("{}({})", case.name, analysed_type_to_wit(typ)),
+                    None => case.name.clone(),
+                })
+                .collect::<Vec<_>>();
+            format!("variant {{ {} }}", cases.join(", "))
         }
-        Type::Option(option) => {
-            let inner = type_to_analysed_type(&option.ty())?;
-            Ok(AnalysedType::Option(TypeOption {
-                inner: Box::new(inner),
+        AnalysedType::Enum(enm) => format!("enum {{ {} }}", enm.cases.join(", ")),
+        AnalysedType::Option(option) => format!("option<{}>", analysed_type_to_wit(&option.inner)),
+        AnalysedType::Result(result) => match (&result.ok, &result.err) {
+            (Some(ok), Some(err)) => format!(
+                "result<{}, {}>",
+                analysed_type_to_wit(ok),
+                analysed_type_to_wit(err)
+            ),
+            (Some(ok), None) => format!("result<{}>", analysed_type_to_wit(ok)),
+            (None, Some(err)) => format!("result<_, {}>", analysed_type_to_wit(err)),
+            (None, None) => "result".to_string(),
+        },
+        AnalysedType::Flags(flags) => format!("flags {{ {} }}", flags.names.join(", ")),
+        AnalysedType::Handle(TypeHandle {
+            mode: AnalysedResourceMode::Owned,
+            ..
+        }) => "own<resource>".to_string(),
+        AnalysedType::Handle(TypeHandle {
+            mode: AnalysedResourceMode::Borrowed,
+            ..
+        }) => "borrow<resource>".to_string(),
+    }
+}
+
+/// Checks that a `Value` is of an `AnalysedType`, without needing an instantiated component.
+///
+/// The value is normalised on the way, by padding flags to the number of flags of the type, so
+/// it can be turned into a `Val` by `ValidatedParams::to_vals` without further checks.
+pub fn validate_value(value: Value, typ: &AnalysedType) -> Result<Value, EncodingError> {
+    let kind = value.type_case_name();
+    match (typ, value) {
+        (AnalysedType::Bool(_), value @ Value::Bool(_))
+        | (AnalysedType::S8(_), value @ Value::S8(_))
+        | (AnalysedType::U8(_), value @ Value::U8(_))
+        | (AnalysedType::S16(_), value @ Value::S16(_))
+        | (AnalysedType::U16(_), value @ Value::U16(_))
+        | (AnalysedType::S32(_), value @ Value::S32(_))
+        | (AnalysedType::U32(_), value @ Value::U32(_))
+        | (AnalysedType::S64(_), value @ Value::S64(_))
+        | (AnalysedType::U64(_), value @ Value::U64(_))
+        | (AnalysedType::F32(_), value @ Value::F32(_))
+        | (AnalysedType::F64(_), value @ Value::F64(_))
+        | (AnalysedType::Chr(_), value @ Value::Char(_))
+        | (AnalysedType::Str(_), value @ Value::String(_))
+        | (AnalysedType::Handle(_), value @ Value::Handle { .. }) => Ok(value),
+        (AnalysedType::List(list), Value::List(values)) => values
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                validate_value(value, &list.inner).map_err(|err| err.at(PathSegment::Index(idx)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::List),
+        (AnalysedType::Record(record), Value::Record(values)) => {
+            if values.len() != record.fields.len() {
+                return Err(EncodingError::value_mismatch(
+                    typ,
+                    kind,
+                    format!(
+                        "expected {} fields, got {}",
+                        record.fields.len(),
+                        values.len()
+                    ),
+                ));
+            }
+            values
+                .into_iter()
+                .zip(&record.fields)
+                .map(|(value, field)| {
+                    validate_value(value, &field.typ)
+                        .map_err(|err| err.at(PathSegment::Field(field.name.clone())))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Record)
+        }
+        (AnalysedType::Tuple(tuple), Value::Tuple(values)) => {
+            if values.len() != tuple.items.len() {
+                return Err(EncodingError::value_mismatch(
+                    typ,
+                    kind,
+                    format!("expected {} items, got {}", tuple.items.len(), values.len()),
+                ));
+            }
+            values
+                .into_iter()
+                .zip(&tuple.items)
+                .enumerate()
+                .map(|(idx, (value, typ))| {
+                    validate_value(value, typ).map_err(|err| err.at(PathSegment::Item(idx)))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Tuple)
+        }
+        (
+            AnalysedType::Variant(variant),
+            Value::Variant {
+                case_idx,
+                case_value,
+            },
+        ) => {
+            let case = variant.cases.get(case_idx as usize).ok_or_else(|| {
+                EncodingError::value_mismatch(typ, kind, format!("unknown case {case_idx}"))
+            })?;
+            let case_value = match (&case.typ, case_value) {
+                (Some(case_type), Some(case_value)) => Some(Box::new(
+                    validate_value(*case_value, case_type)
+                        .map_err(|err| err.at(PathSegment::Case(case.name.clone())))?,
+                )),
+                (None, None) => None,
+                (Some(_), None) => {
+                    return Err(EncodingError::value_mismatch(
+                        typ,
+                        kind,
+                        format!("missing value for case {}", case.name),
+                    ))
+                }
+                (None, Some(_)) => {
+                    return Err(EncodingError::value_mismatch(
+                        typ,
+                        kind,
+                        format!("unexpected value for case {}", case.name),
+                    ))
+                }
+            };
+            Ok(Value::Variant {
+                case_idx,
+                case_value,
+            })
+        }
+        (AnalysedType::Enum(enm), Value::Enum(case_idx)) => {
+            if (case_idx as usize) < enm.cases.len() {
+                Ok(Value::Enum(case_idx))
+            } else {
+                Err(EncodingError::value_mismatch(
+                    typ,
+                    kind,
+                    format!("unknown case {case_idx}"),
+                ))
+            }
+        }
+        (AnalysedType::Flags(flags), Value::Flags(mut values)) => {
+            if values.len() > flags.names.len() {
+                return Err(EncodingError::value_mismatch(
+                    typ,
+                    kind,
+                    format!(
+                        "expected at most {} flags, got {}",
+                        flags.names.len(),
+                        values.len()
+                    ),
+                ));
+            }
+            values.resize(flags.names.len(), false);
+            Ok(Value::Flags(values))
+        }
+        (AnalysedType::Option(option), Value::Option(value)) => match value {
+            Some(value) => Ok(Value::Option(Some(Box::new(
+                validate_value(*value, &option.inner).map_err(|err| err.at(PathSegment::Some))?,
+            )))),
+            None => Ok(Value::Option(None)),
+        },
+        (AnalysedType::Result(result), Value::Result(value)) => {
+            let (value, value_type, segment, case) = match value {
+                Ok(value) => (value, &result.ok, PathSegment::Ok, "ok"),
+                Err(value) => (value, &result.err, PathSegment::Err, "err"),
+            };
+            let value = match (value_type, value) {
+                (Some(value_type), Some(value)) => Some(Box::new(
+                    validate_value(*value, value_type).map_err(|err| err.at(segment))?,
+                )),
+                (None, None) => None,
+                (Some(_), None) => {
+                    return Err(EncodingError::value_mismatch(
+                        typ,
+                        kind,
+                        format!("missing value for case {case}"),
+                    ))
+                }
+                (None, Some(_)) => {
+                    return Err(EncodingError::value_mismatch(
+                        typ,
+                        kind,
+                        format!("unexpected value for case {case}"),
+                    ))
+                }
+            };
+            Ok(Value::Result(if case == "ok" {
+                Ok(value)
+            } else {
+                Err(value)
             }))
         }
-        Type::Result(result) => {
-            let ok = match result.ok() {
-                Some(ty) => Some(Box::new(type_to_analysed_type(&ty)?)),
-                None => None,
+        _ => Err(EncodingError::type_mismatch(typ, kind)),
+    }
+}
+
+/// The parameter types of a function, to validate invocations against before the component is
+/// instantiated.
+///
+/// Deriving a signature walks the types of the function, so signatures are meant to be created
+/// once per function and kept, for example in a `SignatureCache`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionSignature {
+    pub params: Vec<AnalysedType>,
+}
+
+impl FunctionSignature {
+    pub fn new(params: Vec<AnalysedType>) -> Self {
+        Self { params }
+    }
+
+    pub fn from_types(params: &[Type], resources: &mut impl ResourceTypeRegistry) -> Self {
+        let params = params
+            .iter()
+            .map(|typ| type_to_analysed_type(typ, resources))
+            .collect();
+        Self { params }
+    }
+
+    /// Checks and normalises the parameters of an invocation, binding them to this signature
+    pub fn validate(
+        self: &Arc<Self>,
+        params: Vec<Value>,
+    ) -> Result<ValidatedParams, EncodingError> {
+        if params.len() != self.params.len() {
+            return Err(EncodingError::ValueMismatch {
+                path: ValuePath::default(),
+                expected: format!(
+                    "({})",
+                    self.params
+                        .iter()
+                        .map(analysed_type_to_wit)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                actual: "tuple".to_string(),
+                details: format!(
+                    "expected {} parameters, got {}",
+                    self.params.len(),
+                    params.len()
+                ),
+            });
+        }
+        let params = params
+            .into_iter()
+            .zip(&self.params)
+            .enumerate()
+            .map(|(idx, (param, typ))| {
+                validate_value(param, typ).map_err(|err| err.at(PathSegment::Param(idx)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ValidatedParams {
+            signature: self.clone(),
+            params,
+        })
+    }
+}
+
+/// Parameters checked by `FunctionSignature::validate`, along with the signature they were
+/// checked against
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedParams {
+    signature: Arc<FunctionSignature>,
+    params: Vec<Value>,
+}
+
+impl ValidatedParams {
+    pub fn signature(&self) -> &Arc<FunctionSignature> {
+        &self.signature
+    }
+
+    pub fn params(&self) -> &[Value] {
+        &self.params
+    }
+
+    /// Builds the `Val`s of the parameters. Only handles are checked, as they can only be
+    /// resolved by the `ResourceStore` of the instance.
+    pub async fn to_vals(
+        &self,
+        resource_store: &mut (impl ResourceStore + Send),
+    ) -> Result<Vec<DecodeParamResult>, EncodingError> {
+        let mut vals = Vec::with_capacity(self.params.len());
+        for (idx, (param, typ)) in self.params.iter().zip(&self.signature.params).enumerate() {
+            let val = validated_value_to_val(param, typ, resource_store)
+                .await
+                .map_err(|err| err.at(PathSegment::Param(idx)))?;
+            vals.push(val);
+        }
+        Ok(vals)
+    }
+}
+
+/// Function signatures by function name. Only the signatures are cached, the parameters of every
+/// invocation are still validated against them.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureCache {
+    signatures: HashMap<String, Arc<FunctionSignature>>,
+}
+
+impl SignatureCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, function_name: &str) -> Option<Arc<FunctionSignature>> {
+        self.signatures.get(function_name).cloned()
+    }
+
+    /// Returns the signature of a function, deriving it with `signature` the first time
+    pub fn get_or_try_insert(
+        &mut self,
+        function_name: &str,
+        signature: impl FnOnce() -> Result<FunctionSignature, String>,
+    ) -> Result<Arc<FunctionSignature>, String> {
+        if let Some(signature) = self.signatures.get(function_name) {
+            return Ok(signature.clone());
+        }
+        let signature = Arc::new(signature()?);
+        self.signatures
+            .insert(function_name.to_string(), signature.clone());
+        Ok(signature)
+    }
+}
+
+/// Converts a value checked by `validate_value` to a wasmtime Val, taking the names of fields and
+/// cases from its type
+#[async_recursion]
+async fn validated_value_to_val(
+    value: &Value,
+    typ: &AnalysedType,
+    resource_store: &mut (impl ResourceStore + Send),
+) -> Result<DecodeParamResult, EncodingError> {
+    match (typ, value) {
+        (_, Value::Bool(bool)) => Ok(DecodeParamResult::simple(Val::Bool(*bool))),
+        (_, Value::S8(s8)) => Ok(DecodeParamResult::simple(Val::S8(*s8))),
+        (_, Value::U8(u8)) => Ok(DecodeParamResult::simple(Val::U8(*u8))),
+        (_, Value::S16(s16)) => Ok(DecodeParamResult::simple(Val::S16(*s16))),
+        (_, Value::U16(u16)) => Ok(DecodeParamResult::simple(Val::U16(*u16))),
+        (_, Value::S32(s32)) => Ok(DecodeParamResult::simple(Val::S32(*s32))),
+        (_, Value::U32(u32)) => Ok(DecodeParamResult::simple(Val::U32(*u32))),
+        (_, Value::S64(s64)) => Ok(DecodeParamResult::simple(Val::S64(*s64))),
+        (_, Value::U64(u64)) => Ok(DecodeParamResult::simple(Val::U64(*u64))),
+        (_, Value::F32(f32)) => Ok(DecodeParamResult::simple(Val::Float32(*f32))),
+        (_, Value::F64(f64)) => Ok(DecodeParamResult::simple(Val::Float64(*f64))),
+        (_, Value::Char(char)) => Ok(DecodeParamResult::simple(Val::Char(*char))),
+        (_, Value::String(string)) => Ok(DecodeParamResult::simple(Val::String(string.clone()))),
+        (AnalysedType::List(list), Value::List(values)) => {
+            let mut vals = Vec::with_capacity(values.len());
+            let mut resources_to_drop = Vec::new();
+            for value in values {
+                let decoded = validated_value_to_val(value, &list.inner, resource_store).await?;
+                vals.push(decoded.val);
+                resources_to_drop.extend(decoded.resources_to_drop);
+            }
+            Ok(DecodeParamResult {
+                val: Val::List(vals),
+                resources_to_drop,
+            })
+        }
+        (AnalysedType::Record(record), Value::Record(values)) => {
+            let mut vals = Vec::with_capacity(values.len());
+            let mut resources_to_drop = Vec::new();
+            for (value, field) in values.iter().zip(&record.fields) {
+                let decoded = validated_value_to_val(value, &field.typ, resource_store).await?;
+                vals.push((field.name.clone(), decoded.val));
+                resources_to_drop.extend(decoded.resources_to_drop);
+            }
+            Ok(DecodeParamResult {
+                val: Val::Record(vals),
+                resources_to_drop,
+            })
+        }
+        (AnalysedType::Tuple(tuple), Value::Tuple(values)) => {
+            let mut vals = Vec::with_capacity(values.len());
+            let mut resources_to_drop = Vec::new();
+            for (value, typ) in values.iter().zip(&tuple.items) {
+                let decoded = validated_value_to_val(value, typ, resource_store).await?;
+                vals.push(decoded.val);
+                resources_to_drop.extend(decoded.resources_to_drop);
+            }
+            Ok(DecodeParamResult {
+                val: Val::Tuple(vals),
+                resources_to_drop,
+            })
+        }
+        (
+            AnalysedType::Variant(variant),
+            Value::Variant {
+                case_idx,
+                case_value,
+            },
+        ) => {
+            let case = variant.cases.get(*case_idx as usize).ok_or_else(|| {
+                EncodingError::value_mismatch(
+                    typ,
+                    value.type_case_name(),
+                    format!("unknown case index {case_idx}"),
+                )
+            })?;
+            match (&case.typ, case_value) {
+                (Some(case_type), Some(case_value)) => {
+                    let decoded =
+                        validated_value_to_val(case_value, case_type, resource_store).await?;
+                    Ok(DecodeParamResult {
+                        val: Val::Variant(case.name.clone(), Some(Box::new(decoded.val))),
+                        resources_to_drop: decoded.resources_to_drop,
+                    })
+                }
+                _ => Ok(DecodeParamResult::simple(Val::Variant(
+                    case.name.clone(),
+                    None,
+                ))),
+            }
+        }
+        (AnalysedType::Enum(enm), Value::Enum(case_idx)) => {
+            let case = enm.cases.get(*case_idx as usize).ok_or_else(|| {
+                EncodingError::value_mismatch(
+                    typ,
+                    value.type_case_name(),
+                    format!("unknown case index {case_idx}"),
+                )
+            })?;
+            Ok(DecodeParamResult::simple(Val::Enum(case.clone())))
+        }
+        (AnalysedType::Flags(flags), Value::Flags(values)) => {
+            let active_flags = flags
+                .names
+                .iter()
+                .zip(values)
+                .filter(|(_, enabled)| **enabled)
+                .map(|(name, _)| name.clone())
+                .collect();
+            Ok(DecodeParamResult::simple(Val::Flags(active_flags)))
+        }
+        (AnalysedType::Option(option), Value::Option(value)) => match value {
+            Some(value) => {
+                let decoded = validated_value_to_val(value, &option.inner, resource_store).await?;
+                Ok(DecodeParamResult {
+                    val: Val::Option(Some(Box::new(decoded.val))),
+                    resources_to_drop: decoded.resources_to_drop,
+                })
+            }
+            None => Ok(DecodeParamResult::simple(Val::Option(None))),
+        },
+        (AnalysedType::Result(result), Value::Result(value)) => {
+            let (value, value_type, is_ok) = match value {
+                Ok(value) => (value, &result.ok, true),
+                Err(value) => (value, &result.err, false),
             };
-            let err = match result.err() {
-                Some(ty) => Some(Box::new(type_to_analysed_type(&ty)?)),
-                None => None,
+            let decoded = match (value, value_type) {
+                (Some(value), Some(value_type)) => {
+                    Some(validated_value_to_val(value, value_type, resource_store).await?)
+                }
+                _ => None,
             };
-            Ok(AnalysedType::Result(TypeResult { ok, err }))
+            let (val, resources_to_drop) = match decoded {
+                Some(decoded) => (Some(Box::new(decoded.val)), decoded.resources_to_drop),
+                None => (None, Vec::new()),
+            };
+            Ok(DecodeParamResult {
+                val: Val::Result(if is_ok { Ok(val) } else { Err(val) }),
+                resources_to_drop,
+            })
         }
-        Type::Flags(flags) => {
-            let names = flags.names().map(|name| name.to_string()).collect();
-            Ok(AnalysedType::Flags(TypeFlags { names }))
+        (AnalysedType::Handle(handle), Value::Handle { uri, resource_id }) => {
+            if resource_store.self_uri() != *uri {
+                return Err(EncodingError::value_mismatch(
+                    typ,
+                    value.type_case_name(),
+                    "cannot resolve handle belonging to a different worker",
+                ));
+            }
+            match handle.mode {
+                AnalysedResourceMode::Owned => match resource_store.get(*resource_id).await {
+                    Some(resource) => Ok(DecodeParamResult {
+                        val: Val::Resource(resource),
+                        resources_to_drop: vec![resource],
+                    }),
+                    None => Err(EncodingError::value_mismatch(
+                        typ,
+                        value.type_case_name(),
+                        "resource not found",
+                    )),
+                },
+                AnalysedResourceMode::Borrowed => match resource_store.borrow(*resource_id).await {
+                    Some(resource) => Ok(DecodeParamResult::simple(Val::Resource(resource))),
+                    None => Err(EncodingError::value_mismatch(
+                        typ,
+                        value.type_case_name(),
+                        "resource not found",
+                    )),
+                },
+            }
+        }
+        _ => Err(EncodingError::type_mismatch(typ, value.type_case_name())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wasmtime::{
+        type_to_analysed_type, validated_value_to_val, EncodingError, FunctionSignature,
+        PathSegment, ResourceStore, ResourceTypes, SignatureCache, ValuePath,
+    };
+    use crate::{Uri, Value};
+    use async_trait::async_trait;
+    use golem_wasm_ast::analysis::{
+        AnalysedResourceId, AnalysedResourceMode, AnalysedType, NameOptionTypePair, NameTypePair,
+        TypeEnum, TypeFlags, TypeHandle, TypeList, TypeRecord, TypeStr, TypeU32, TypeVariant,
+    };
+    use std::sync::Arc;
+    use wasmtime::component::{ResourceAny, ResourceType, Type, Val};
+
+    struct NoResources;
+
+    #[async_trait]
+    impl ResourceStore for NoResources {
+        fn self_uri(&self) -> Uri {
+            Uri {
+                value: "urn:worker:test".to_string(),
+            }
         }
-        Type::Own(_) => Err("Cannot extract information about owned resource type".to_string()),
-        Type::Borrow(_) => {
-            Err("Cannot extract information about borrowed resource type".to_string())
+
+        async fn add(&mut self, _resource: ResourceAny) -> u64 {
+            unreachable!()
+        }
+
+        async fn get(&mut self, _resource_id: u64) -> Option<ResourceAny> {
+            None
         }
+
+        async fn borrow(&self, _resource_id: u64) -> Option<ResourceAny> {
+            None
+        }
+    }
+
+    fn order_signature() -> FunctionSignature {
+        FunctionSignature::new(vec![
+            AnalysedType::Str(TypeStr),
+            AnalysedType::List(TypeList {
+                inner: Box::new(AnalysedType::Record(TypeRecord {
+                    fields: vec![
+                        NameTypePair {
+                            name: "name".to_string(),
+                            typ: AnalysedType::Str(TypeStr),
+                        },
+                        NameTypePair {
+                            name: "amount".to_string(),
+                            typ: AnalysedType::U32(TypeU32),
+                        },
+                    ],
+                })),
+            }),
+            AnalysedType::Flags(TypeFlags {
+                names: vec![
+                    "express".to_string(),
+                    "gift".to_string(),
+                    "insured".to_string(),
+                ],
+            }),
+            AnalysedType::Variant(TypeVariant {
+                cases: vec![
+                    NameOptionTypePair {
+                        name: "pickup".to_string(),
+                        typ: None,
+                    },
+                    NameOptionTypePair {
+                        name: "delivery".to_string(),
+                        typ: Some(AnalysedType::Str(TypeStr)),
+                    },
+                ],
+            }),
+        ])
+    }
+
+    fn item(name: &str, amount: Value) -> Value {
+        Value::Record(vec![Value::String(name.to_string()), amount])
+    }
+
+    #[test]
+    fn encoding_errors_render_their_path() {
+        let error = EncodingError::TypeMismatch {
+            path: ValuePath::default(),
+            expected: "float64".to_string(),
+            actual: "string".to_string(),
+        }
+        .at(PathSegment::Field("price".to_string()))
+        .at(PathSegment::Index(3))
+        .at(PathSegment::Field("items".to_string()))
+        .at(PathSegment::Param(2));
+        assert_eq!(
+            error.to_string(),
+            "param 2 .items[3].price: expected float64, got string"
+        );
+
+        let error = EncodingError::ValueMismatch {
+            path: ValuePath::default(),
+            expected: "enum { red, green }".to_string(),
+            actual: "enum".to_string(),
+            details: "could not get name for discriminant 2".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "could not get name for discriminant 2 (expected enum { red, green }, got enum)"
+        );
+
+        let error = error
+            .at(PathSegment::Some)
+            .at(PathSegment::Item(1))
+            .at(PathSegment::Result(0));
+        assert_eq!(error.path().to_string(), "result 0 .1.some");
+    }
+
+    #[test]
+    fn validation_reports_the_path_of_invalid_values() {
+        let signature = Arc::new(order_signature());
+
+        let error = signature
+            .validate(vec![
+                Value::String("order-1".to_string()),
+                Value::List(vec![
+                    item("apple", Value::U32(1)),
+                    item("pear", Value::String("two".to_string())),
+                ]),
+                Value::Flags(vec![true]),
+                Value::Variant {
+                    case_idx: 0,
+                    case_value: None,
+                },
+            ])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "param 1 [1].amount: expected u32, got string"
+        );
+
+        let error = signature
+            .validate(vec![
+                Value::String("order-1".to_string()),
+                Value::List(vec![]),
+                Value::Flags(vec![]),
+                Value::Variant {
+                    case_idx: 1,
+                    case_value: None,
+                },
+            ])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "param 3: missing value for case delivery (expected variant { pickup, delivery(string) }, got variant)"
+        );
+
+        let error = signature
+            .validate(vec![Value::String("order-1".to_string())])
+            .unwrap_err();
+        assert_eq!(error.path(), &ValuePath::default());
+    }
+
+    #[tokio::test]
+    async fn validated_params_convert_to_vals() {
+        let signature = Arc::new(order_signature());
+        let params = signature
+            .validate(vec![
+                Value::String("order-1".to_string()),
+                Value::List(vec![item("apple", Value::U32(3))]),
+                Value::Flags(vec![false, true]),
+                Value::Variant {
+                    case_idx: 1,
+                    case_value: Some(Box::new(Value::String("home".to_string()))),
+                },
+            ])
+            .unwrap();
+        assert!(Arc::ptr_eq(params.signature(), &signature));
+        assert_eq!(params.params()[2], Value::Flags(vec![false, true, false]));
+
+        let vals = params
+            .to_vals(&mut NoResources)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|decoded| decoded.val)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vals,
+            vec![
+                Val::String("order-1".to_string()),
+                Val::List(vec![Val::Record(vec![
+                    ("name".to_string(), Val::String("apple".to_string())),
+                    ("amount".to_string(), Val::U32(3)),
+                ])]),
+                Val::Flags(vec!["gift".to_string()]),
+                Val::Variant(
+                    "delivery".to_string(),
+                    Some(Box::new(Val::String("home".to_string())))
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_case_indices_are_errors() {
+        let enm = AnalysedType::Enum(TypeEnum {
+            cases: vec!["a".to_string()],
+        });
+        let result = validated_value_to_val(&Value::Enum(1), &enm, &mut NoResources).await;
+        assert!(matches!(result, Err(EncodingError::ValueMismatch { .. })));
+
+        let variant = AnalysedType::Variant(TypeVariant {
+            cases: vec![NameOptionTypePair {
+                name: "a".to_string(),
+                typ: None,
+            }],
+        });
+        let value = Value::Variant {
+            case_idx: 1,
+            case_value: None,
+        };
+        let result = validated_value_to_val(&value, &variant, &mut NoResources).await;
+        assert!(matches!(result, Err(EncodingError::ValueMismatch { .. })));
+    }
+
+    #[test]
+    fn signatures_are_derived_once() {
+        let mut cache = SignatureCache::new();
+        let first = cache
+            .get_or_try_insert("order", || Ok(order_signature()))
+            .unwrap();
+        let second = cache
+            .get_or_try_insert("order", || Err("derived twice".to_string()))
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(cache.get("cancel").is_none());
+        assert!(cache
+            .get_or_try_insert("cancel", || Err("no such function".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn resource_handles_are_analysed_with_stable_ids() {
+        struct Cart;
+        struct Session;
+
+        let mut resources = ResourceTypes::new();
+        let cart = ResourceType::host::<Cart>();
+        let session = ResourceType::host::<Session>();
+
+        assert_eq!(
+            type_to_analysed_type(&Type::Borrow(session), &mut resources),
+            AnalysedType::Handle(TypeHandle {
+                resource_id: AnalysedResourceId(0),
+                mode: AnalysedResourceMode::Borrowed,
+            })
+        );
+        assert_eq!(
+            type_to_analysed_type(&Type::Own(cart), &mut resources),
+            AnalysedType::Handle(TypeHandle {
+                resource_id: AnalysedResourceId(1),
+                mode: AnalysedResourceMode::Owned,
+            })
+        );
+        assert_eq!(
+            type_to_analysed_type(&Type::Own(session), &mut resources),
+            AnalysedType::Handle(TypeHandle {
+                resource_id: AnalysedResourceId(0),
+                mode: AnalysedResourceMode::Owned,
+            })
+        );
+        assert_eq!(resources.resource_type(AnalysedResourceId(1)), Some(cart));
+        assert_eq!(resources.resource_type(AnalysedResourceId(2)), None);
     }
 }